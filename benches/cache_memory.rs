@@ -0,0 +1,71 @@
+//! Compares the memory used to record a year of `Minute` requests in a `Cache`, which stores
+//! them as a `TimeRangeSet`, with storing every requested key in a `BTreeSet`, as the `Cache`
+//! previously did.
+//!
+//! Run with `cargo bench --bench cache_memory`.
+
+use resolution::{Cache, CacheResponse, FromMonotonic, Minute, TimeResolution};
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    collections::{BTreeMap, BTreeSet},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+// counts the bytes currently allocated, so the memory retained by each structure can be measured
+struct Counting;
+
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATED.fetch_add(layout.size(), Ordering::Relaxed);
+        System.alloc(layout)
+    }
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        ALLOCATED.fetch_sub(layout.size(), Ordering::Relaxed);
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+const DAYS: i64 = 365;
+const MINUTES_PER_DAY: i64 = 1440;
+
+// one request per day, for each minute of the day, as a dashboard refreshing daily would make
+fn requests() -> impl Iterator<Item = BTreeSet<Minute>> {
+    (0..DAYS).map(|day| {
+        (day * MINUTES_PER_DAY..(day + 1) * MINUTES_PER_DAY)
+            .map(Minute::from_monotonic)
+            .collect()
+    })
+}
+
+fn main() {
+    let before = ALLOCATED.load(Ordering::Relaxed);
+    let mut keys = BTreeSet::new();
+    for request in requests() {
+        keys.extend(request);
+    }
+    let per_key = ALLOCATED.load(Ordering::Relaxed) - before;
+
+    let before = ALLOCATED.load(Ordering::Relaxed);
+    let mut cache = Cache::<Minute, u64>::empty();
+    for request in requests() {
+        cache.add(request, BTreeMap::new());
+    }
+    let ranges = ALLOCATED.load(Ordering::Relaxed) - before;
+
+    // the cache still answers for every requested minute
+    let first = Minute::from_monotonic(0);
+    let all = first.succ_n(DAYS as u64 * MINUTES_PER_DAY as u64 - 1);
+    assert!(matches!(
+        cache.get([first, all].into()),
+        CacheResponse::Hit(_)
+    ));
+
+    println!("{} minutes requested over {DAYS} days", keys.len());
+    println!("BTreeSet of keys:       {per_key:>10} bytes");
+    println!("Cache (TimeRangeSet):   {ranges:>10} bytes");
+}
@@ -0,0 +1,44 @@
+//! Compares `TimeRange::contains`, which only uses the monotonic indexes, with checking the same
+//! point by converting both to a UTC `DateTime`, as `contains` previously did.
+//!
+//! Run with `cargo bench --bench contains`.
+
+use resolution::{FromMonotonic, Minute, TimeRange, TimeResolution};
+use std::{hint::black_box, num::NonZeroU64, time::Instant};
+
+const POINTS: i64 = 10_000_000;
+
+fn main() {
+    let range = TimeRange::new(
+        Minute::from_monotonic(POINTS / 4),
+        NonZeroU64::new(POINTS as u64 / 2).unwrap(),
+    );
+    let (start, end) = (range.start().start_datetime(), range.end().start_datetime());
+
+    let monotonic = time(|point| range.contains(point));
+    let datetime = time(|point| {
+        let point = point.start_datetime();
+        point >= start && point <= end
+    });
+    assert_eq!(monotonic.1, datetime.1);
+
+    println!("{POINTS} points, {} contained", monotonic.1);
+    println!(
+        "contains (monotonic): {:>8.2} ns/point",
+        monotonic.0 / POINTS as f64
+    );
+    println!(
+        "contains (DateTime):  {:>8.2} ns/point",
+        datetime.0 / POINTS as f64
+    );
+}
+
+// the total nanoseconds taken to check each point, and how many were contained
+fn time(contains: impl Fn(Minute) -> bool) -> (f64, u64) {
+    let started = Instant::now();
+    let contained = (0..POINTS)
+        .map(|i| contains(black_box(Minute::from_monotonic(i))))
+        .filter(|c| *c)
+        .count();
+    (started.elapsed().as_nanos() as f64, contained as u64)
+}
@@ -0,0 +1,102 @@
+#[cfg(feature = "alloc")]
+use alloc::collections::BTreeSet;
+use chrono::{Datelike, NaiveDate, Weekday};
+
+/// `BusinessCalendar` decides which dates are business days, used for stepping with
+/// `Day::succ_business` and `Day::pred_business`.
+pub trait BusinessCalendar {
+    fn is_business_day(&self, date: NaiveDate) -> bool;
+}
+
+/// `Weekends` treats every Monday to Friday as a business day.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Weekends;
+
+impl BusinessCalendar for Weekends {
+    fn is_business_day(&self, date: NaiveDate) -> bool {
+        !matches!(date.weekday(), Weekday::Sat | Weekday::Sun)
+    }
+}
+
+/// `Holidays` treats every Monday to Friday as a business day, except for the given holidays.
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Holidays(pub BTreeSet<NaiveDate>);
+
+#[cfg(feature = "alloc")]
+impl BusinessCalendar for Holidays {
+    fn is_business_day(&self, date: NaiveDate) -> bool {
+        Weekends.is_business_day(date) && !self.0.contains(&date)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl FromIterator<NaiveDate> for Holidays {
+    fn from_iter<I: IntoIterator<Item = NaiveDate>>(iter: I) -> Self {
+        Holidays(iter.into_iter().collect())
+    }
+}
+
+impl<C: BusinessCalendar + ?Sized> BusinessCalendar for &C {
+    fn is_business_day(&self, date: NaiveDate) -> bool {
+        (**self).is_business_day(date)
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+    use crate::Day;
+
+    fn day(s: &str) -> Day {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_succ_business() {
+        // Thursday
+        let trade = day("2021-12-23");
+        assert_eq!(trade.succ_business(0, &Weekends), Some(trade));
+        assert_eq!(trade.succ_business(1, &Weekends), Some(day("2021-12-24")));
+        assert_eq!(trade.succ_business(2, &Weekends), Some(day("2021-12-27")));
+        assert_eq!(trade.pred_business(4, &Weekends), Some(day("2021-12-17")));
+
+        let holidays = [day("2021-12-24"), day("2021-12-27"), day("2021-12-28")]
+            .into_iter()
+            .map(|d| crate::DateResolution::start(&d))
+            .collect::<Holidays>();
+        assert_eq!(trade.succ_business(2, &holidays), Some(day("2021-12-30")));
+        assert_eq!(
+            day("2021-12-29").pred_business(1, &holidays),
+            Some(day("2021-12-23"))
+        );
+
+        // starting from a non-business day
+        assert_eq!(
+            day("2021-12-25").succ_business(1, &Weekends),
+            Some(day("2021-12-27"))
+        );
+        assert_eq!(
+            day("2021-12-25").pred_business(1, &Weekends),
+            Some(day("2021-12-24"))
+        );
+
+        // gives up rather than searching forever
+        struct Closed;
+        impl BusinessCalendar for Closed {
+            fn is_business_day(&self, _: NaiveDate) -> bool {
+                false
+            }
+        }
+        assert_eq!(trade.succ_business(0, &Closed), Some(trade));
+        assert_eq!(trade.succ_business(1, &Closed), None);
+        assert_eq!(trade.pred_business(1, &Closed), None);
+
+        // or stepping out of range
+        let max = <Day as crate::Bounded>::MAX;
+        assert_eq!(max.succ_business(1, &Weekends), None);
+        assert_eq!(max.pred_business(1, &Weekends).map(|d| d < max), Some(true));
+        let min = <Day as crate::Bounded>::MIN;
+        assert_eq!(min.pred_business(1, &Weekends), None);
+    }
+}
@@ -0,0 +1,86 @@
+use chrono::{DateTime, Utc};
+
+/// `Clock` is a source of the current time, used to find the current period with
+/// `DateResolutionExt::current_in` or `SubDateResolution::current_in`.
+///
+/// Taking a `Clock` rather than reading the system time directly allows logic depending on the
+/// current period to be tested deterministically, by using a `FixedClock`.
+pub trait Clock {
+    fn now(&self) -> DateTime<Utc>;
+}
+
+/// `SystemClock` reads the current time from the system.
+#[cfg(feature = "std")]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SystemClock;
+
+#[cfg(feature = "std")]
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        std::time::SystemTime::now().into()
+    }
+}
+
+/// `FixedClock` always returns the same time, which can be changed by setting the inner value.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct FixedClock(pub DateTime<Utc>);
+
+impl Clock for FixedClock {
+    fn now(&self) -> DateTime<Utc> {
+        self.0
+    }
+}
+
+impl<C: Clock + ?Sized> Clock for &C {
+    fn now(&self) -> DateTime<Utc> {
+        (**self).now()
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+    use crate::{
+        DateResolution, DateResolutionExt, Day, FiveMinute, FixedEast, Monday, Month,
+        SubDateResolution, TimeResolution, Week, Zoned,
+    };
+
+    #[test]
+    fn test_current_in() {
+        let mut clock = FixedClock(
+            DateTime::parse_from_rfc3339("2021-03-31T23:58:00Z")
+                .unwrap()
+                .to_utc(),
+        );
+
+        assert_eq!(Day::current_in(clock, ()), "2021-03-31".parse().unwrap());
+        assert_eq!(Month::current_in(clock, ()), "Mar-2021".parse().unwrap());
+        assert_eq!(
+            Week::<Monday>::current_in(clock, ()),
+            "Week starting 2021-03-29".parse().unwrap()
+        );
+        // already the next day in a zone east of UTC
+        let aest = Zoned::<Day, FixedEast<{ 10 * 60 * 60 }>>::current_in(clock, FixedEast);
+        assert_eq!(aest.start(), Day::current_in(clock, ()).succ().start());
+
+        let five = FiveMinute::current_in(clock, ());
+        assert_eq!(
+            five.start_datetime(),
+            clock.now() - chrono::Duration::minutes(3)
+        );
+
+        clock.0 += chrono::Duration::minutes(5);
+        assert_eq!(Day::current_in(clock, ()), "2021-04-01".parse().unwrap());
+        assert_eq!(Month::current_in(clock, ()), "Apr-2021".parse().unwrap());
+        assert_eq!(FiveMinute::current_in(clock, ()), five.succ());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_system_clock() {
+        let before = SystemClock.now();
+        let day = Day::current_in(SystemClock, ());
+        assert!(day.start() >= before.date_naive());
+        assert!(day.start() <= SystemClock.now().date_naive());
+    }
+}
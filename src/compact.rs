@@ -0,0 +1,453 @@
+use crate::{DateResolution, FromMonotonic, Monotonic, TimeRange, TimeResolution};
+use alloc::string::String;
+use core::{fmt, marker, num, str};
+use serde::de::{self, Deserialize};
+
+/// `Compact` serializes any period as its monotonic `i64`, rather than the string or struct
+/// form used by the period itself, which is much smaller in binary formats.
+///
+/// The resolution is not written, as it is known from the type, including the start day of a
+/// `Week` and the length of `Minutes`, so the same type must be used when deserializing.
+///
+/// ```
+/// use resolution::{Compact, Day};
+///
+/// let day = "2021-01-01".parse::<Day>().unwrap();
+/// let json = serde_json::to_string(&Compact(day)).unwrap();
+/// assert_eq!(json, "738156");
+/// assert_eq!(serde_json::from_str::<Compact<Day>>(&json).unwrap().0, day);
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Compact<R>(pub R);
+
+impl<R> From<R> for Compact<R> {
+    fn from(value: R) -> Compact<R> {
+        Compact(value)
+    }
+}
+
+impl<R: Monotonic> serde::Serialize for Compact<R> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_i64(self.0.to_monotonic())
+    }
+}
+
+impl<'de, R: FromMonotonic> de::Deserialize<'de> for Compact<R> {
+    fn deserialize<D>(deserializer: D) -> Result<Compact<R>, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        deserialize_monotonic(deserializer).map(Compact)
+    }
+}
+
+/// For a `TimeRange`, `Compact` writes exactly 16 bytes in every format: the monotonic `i64` of
+/// the first period followed by the `u64` number of periods, both big-endian, with the sign bit of
+/// the first period flipped. Comparing the bytes then orders ranges by their first period and
+/// then their length, so they can be used as keys in ordered key-value stores. This encoding is
+/// stable, and as it is a fixed size array, has no length prefix in formats such as `bincode` or
+/// `postcard`. Deserializing rejects an empty range, or one which ends after the last period.
+///
+/// ```
+/// use resolution::{Compact, Day, TimeRange};
+///
+/// let start = "2021-01-01".parse::<Day>().unwrap();
+/// let range = TimeRange::from_bounds(start, "2021-01-31".parse().unwrap());
+/// let json = serde_json::to_string(&Compact(range)).unwrap();
+/// assert_eq!(json, "[128,0,0,0,0,11,67,108,0,0,0,0,0,0,0,31]");
+/// assert_eq!(serde_json::from_str::<Compact<TimeRange<Day>>>(&json).unwrap().0, range);
+/// ```
+impl<P: TimeResolution> serde::Serialize for Compact<TimeRange<P>> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut bytes = [0; 16];
+        bytes[..8].copy_from_slice(&self.0.start().to_monotonic().to_be_bytes());
+        bytes[0] ^= 0x80;
+        bytes[8..].copy_from_slice(&self.0.len().get().to_be_bytes());
+        serde::Serialize::serialize(&bytes, serializer)
+    }
+}
+
+impl<'de, P: TimeResolution + FromMonotonic> de::Deserialize<'de> for Compact<TimeRange<P>> {
+    fn deserialize<D>(deserializer: D) -> Result<Compact<TimeRange<P>>, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        let mut bytes = <[u8; 16]>::deserialize(deserializer)?;
+        bytes[0] ^= 0x80;
+        let (start, len) = bytes.split_at(8);
+        let start = i64::from_be_bytes(start.try_into().expect("8 bytes"));
+        let len = u64::from_be_bytes(len.try_into().expect("8 bytes"));
+        let len = num::NonZeroU64::new(len)
+            .ok_or_else(|| de::Error::custom("A TimeRange must have at least one period"))?;
+        let start = P::from_monotonic(start);
+        start
+            .checked_succ_n(len.get() - 1)
+            .ok_or_else(|| de::Error::custom("The end of the TimeRange is out of range"))?;
+        Ok(Compact(TimeRange::new(start, len)))
+    }
+}
+
+/// `StartDate` serializes any date period as the ISO 8601 date it starts on, eg `2021-12-06`, in
+/// every format. Unlike the monotonic `i64`, this doesn't depend on the epoch of the resolution
+/// (2021-01-04 for `Week<Monday>`), so is a stable form for storing periods long term.
+///
+/// When deserializing, the date must be the start of a period, so for a `Week` it must be on the
+/// start day of the week.
+///
+/// ```
+/// use resolution::{Monday, StartDate, Week};
+///
+/// let week = "Week starting 2021-12-06".parse::<Week<Monday>>().unwrap();
+/// let json = serde_json::to_string(&StartDate(week)).unwrap();
+/// assert_eq!(json, r#""2021-12-06""#);
+/// assert_eq!(serde_json::from_str::<StartDate<Week<Monday>>>(&json).unwrap().0, week);
+/// assert!(serde_json::from_str::<StartDate<Week<Monday>>>(r#""2021-12-07""#).is_err());
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct StartDate<R>(pub R);
+
+impl<R> From<R> for StartDate<R> {
+    fn from(value: R) -> StartDate<R> {
+        StartDate(value)
+    }
+}
+
+impl<R: DateResolution> serde::Serialize for StartDate<R> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serde::Serialize::serialize(&self.0.start(), serializer)
+    }
+}
+
+impl<'de, R: DateResolution<Params = ()>> de::Deserialize<'de> for StartDate<R> {
+    fn deserialize<D>(deserializer: D) -> Result<StartDate<R>, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        let date = chrono::NaiveDate::deserialize(deserializer)?;
+        let period = R::from_date(date, ());
+        if period.start() != date {
+            return Err(de::Error::custom(format_args!(
+                "{} is not the start of a period of {}",
+                date,
+                period.name()
+            )));
+        }
+        Ok(StartDate(period))
+    }
+}
+
+/// Serialize a period as a string using its `Display` for human readable formats, such as JSON,
+/// or otherwise as its monotonic `i64`, as with `Compact`.
+pub fn serialize_readable<R, S>(value: &R, serializer: S) -> Result<S::Ok, S::Error>
+where
+    R: Monotonic + fmt::Display,
+    S: serde::Serializer,
+{
+    if serializer.is_human_readable() {
+        serializer.collect_str(value)
+    } else {
+        serializer.serialize_i64(value.to_monotonic())
+    }
+}
+
+/// The inverse of `serialize_readable`, parsing strings with `FromStr`.
+pub fn deserialize_readable<'de, R, D>(deserializer: D) -> Result<R, D::Error>
+where
+    R: FromMonotonic + str::FromStr,
+    R::Err: fmt::Display,
+    D: de::Deserializer<'de>,
+{
+    if deserializer.is_human_readable() {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(de::Error::custom)
+    } else {
+        deserialize_monotonic(deserializer)
+    }
+}
+
+/// As `deserialize_readable`, but for human readable formats also accepts the struct form `L`
+/// which was previously written for the type.
+pub(crate) fn deserialize_readable_or_legacy<'de, R, L, D>(deserializer: D) -> Result<R, D::Error>
+where
+    R: FromMonotonic + str::FromStr + TryFrom<L>,
+    <R as str::FromStr>::Err: fmt::Display,
+    <R as TryFrom<L>>::Error: fmt::Display,
+    L: de::Deserialize<'de>,
+    D: de::Deserializer<'de>,
+{
+    struct ReadableVisitor<R, L>(marker::PhantomData<(R, L)>);
+
+    impl<'de, R, L> de::Visitor<'de> for ReadableVisitor<R, L>
+    where
+        R: str::FromStr + TryFrom<L>,
+        <R as str::FromStr>::Err: fmt::Display,
+        <R as TryFrom<L>>::Error: fmt::Display,
+        L: de::Deserialize<'de>,
+    {
+        type Value = R;
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str("a string or a map")
+        }
+        fn visit_str<E: de::Error>(self, v: &str) -> Result<R, E> {
+            v.parse().map_err(de::Error::custom)
+        }
+        fn visit_map<A: de::MapAccess<'de>>(self, map: A) -> Result<R, A::Error> {
+            let legacy = L::deserialize(de::value::MapAccessDeserializer::new(map))?;
+            R::try_from(legacy).map_err(de::Error::custom)
+        }
+    }
+
+    if deserializer.is_human_readable() {
+        deserializer.deserialize_any(ReadableVisitor(marker::PhantomData))
+    } else {
+        deserialize_monotonic(deserializer)
+    }
+}
+
+fn deserialize_monotonic<'de, R, D>(deserializer: D) -> Result<R, D::Error>
+where
+    R: FromMonotonic,
+    D: de::Deserializer<'de>,
+{
+    i64::deserialize(deserializer).map(R::from_monotonic)
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::{Day, FiveMinute, Month, Quarter, Sunday, TimeResolution, Week, Year};
+    use alloc::{format, string::ToString};
+
+    fn round_trip<R: FromMonotonic + PartialEq + core::fmt::Debug + Copy>(value: R) {
+        let json = serde_json::to_string(&Compact(value)).unwrap();
+        assert_eq!(json, value.to_monotonic().to_string());
+        assert_eq!(serde_json::from_str::<Compact<R>>(&json).unwrap().0, value);
+    }
+
+    #[test]
+    fn test_compact() {
+        let date = chrono::NaiveDate::from_ymd_opt(2021, 2, 3).unwrap();
+        round_trip(Day::from(date));
+        round_trip(Week::<Sunday>::from(date));
+        round_trip(Month::from(date));
+        round_trip(Quarter::from(date));
+        round_trip(Year::from(date));
+        round_trip(FiveMinute::from_monotonic(-5));
+
+        let day = Day::from(date);
+        let days = [day, day.succ()].map(Compact);
+        assert_eq!(
+            serde_json::to_string(&days).unwrap(),
+            format!("[{},{}]", day.to_monotonic(), day.to_monotonic() + 1)
+        );
+    }
+
+    #[test]
+    fn test_compact_range() {
+        fn bytes<P: TimeResolution>(range: TimeRange<P>) -> [u8; 16] {
+            let json = serde_json::to_string(&Compact(range)).unwrap();
+            serde_json::from_str(&json).unwrap()
+        }
+        fn round_trip<P: TimeResolution + FromMonotonic + core::fmt::Debug>(range: TimeRange<P>) {
+            let json = serde_json::to_string(&Compact(range)).unwrap();
+            assert_eq!(
+                serde_json::from_str::<Compact<TimeRange<P>>>(&json)
+                    .unwrap()
+                    .0,
+                range
+            );
+            // the same bytes are used for formats which aren't human readable
+            let binary = Binary(serde_json::from_str(&json).unwrap());
+            assert_eq!(
+                <Compact<TimeRange<P>> as Deserialize>::deserialize(binary)
+                    .unwrap()
+                    .0,
+                range
+            );
+        }
+
+        let len = num::NonZeroU64::new(3).unwrap();
+        let range = |start| TimeRange::new(FiveMinute::from_monotonic(start), len);
+        round_trip(range(0));
+        round_trip(range(-5));
+        round_trip(range(i64::MIN));
+        round_trip(TimeRange::new(Month::from_monotonic(i64::MAX - 2), len));
+        assert_eq!(
+            bytes(range(-1)),
+            [0x7f, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0, 0, 0, 0, 0, 0, 0, 3]
+        );
+
+        // the bytes are ordered in the same way as the ranges
+        let ranges = [
+            range(i64::MIN),
+            range(-1),
+            TimeRange::new(FiveMinute::from_monotonic(0), num::NonZeroU64::MIN),
+            range(0),
+            range(1),
+            range(i64::MAX - 2),
+        ];
+        assert!(ranges.windows(2).all(|w| bytes(w[0]) < bytes(w[1])));
+
+        // a range can't be empty
+        assert!(serde_json::from_str::<Compact<TimeRange<Day>>>(
+            "[128,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0]"
+        )
+        .is_err());
+
+        // nor can it end after the last period
+        let overflowing = serde_json::to_string(&Compact(TimeRange::new(
+            Month::from_monotonic(i64::MAX),
+            num::NonZeroU64::MAX,
+        )))
+        .unwrap();
+        assert!(serde_json::from_str::<Compact<TimeRange<Month>>>(&overflowing).is_err());
+        assert!(serde_json::from_str::<Compact<TimeRange<Day>>>(
+            "[255,255,255,255,255,255,255,255,0,0,0,0,0,0,0,2]"
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_start_date() {
+        let date = chrono::NaiveDate::from_ymd_opt(2021, 2, 3).unwrap();
+        let week = Week::<Sunday>::from(date);
+        let json = serde_json::to_string(&StartDate(week)).unwrap();
+        assert_eq!(json, r#""2021-01-31""#);
+        assert_eq!(
+            serde_json::from_str::<StartDate<Week<Sunday>>>(&json)
+                .unwrap()
+                .0,
+            week
+        );
+        // not a Sunday
+        assert!(serde_json::from_str::<StartDate<Week<Sunday>>>(r#""2021-02-01""#).is_err());
+
+        let month = Month::from(date);
+        let json = serde_json::to_string(&StartDate(month)).unwrap();
+        assert_eq!(json, r#""2021-02-01""#);
+        assert_eq!(
+            serde_json::from_str::<StartDate<Month>>(&json).unwrap().0,
+            month
+        );
+        assert!(serde_json::from_str::<StartDate<Month>>(r#""2021-02-03""#).is_err());
+
+        // the same form is used for formats which aren't human readable
+        let binary = Binary(serde_json::Value::String("2021-01-31".into()));
+        assert_eq!(
+            <StartDate<Week<Sunday>> as Deserialize>::deserialize(binary)
+                .unwrap()
+                .0,
+            week
+        );
+    }
+
+    // delegates to `serde_json`, but reports that the format is not human readable
+    pub(crate) struct Binary(pub(crate) serde_json::Value);
+
+    impl<'de> de::Deserializer<'de> for Binary {
+        type Error = serde_json::Error;
+        fn deserialize_any<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+            de::Deserializer::deserialize_any(self.0, visitor)
+        }
+        fn is_human_readable(&self) -> bool {
+            false
+        }
+        serde::forward_to_deserialize_any! {
+            bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+            bytes byte_buf option unit unit_struct newtype_struct seq tuple
+            tuple_struct map struct enum identifier ignored_any
+        }
+    }
+
+    #[test]
+    fn test_human_readable() {
+        let date = chrono::NaiveDate::from_ymd_opt(2021, 2, 3).unwrap();
+        let day = Day::from(date);
+        let week = Week::<Sunday>::from(date);
+        let minutes = FiveMinute::from_monotonic(5_000_000);
+
+        // strings for human readable formats
+        assert_eq!(serde_json::to_string(&day).unwrap(), r#""2021-02-03""#);
+        assert_eq!(
+            serde_json::to_string(&week).unwrap(),
+            r#""Week starting 2021-01-31""#
+        );
+        assert_eq!(
+            serde_json::to_string(&minutes).unwrap(),
+            r#""2017-07-14 02:40 => 2017-07-14 02:45""#
+        );
+        assert_eq!(
+            serde_json::from_str::<Week<Sunday>>(r#""Week starting 2021-01-31""#).unwrap(),
+            week
+        );
+        assert_eq!(
+            serde_json::from_str::<FiveMinute>(r#""2017-07-14 02:40 => 2017-07-14 02:45""#)
+                .unwrap(),
+            minutes
+        );
+
+        // the struct forms previously written are still accepted
+        let legacy = format!(r#"{{"n":{},"start_day":"Sunday"}}"#, week.to_monotonic());
+        assert_eq!(serde_json::from_str::<Week<Sunday>>(&legacy).unwrap(), week);
+        assert!(serde_json::from_str::<Week<crate::Monday>>(&legacy).is_err());
+        let legacy = r#"{"index":5000000,"length":5}"#;
+        assert_eq!(serde_json::from_str::<FiveMinute>(legacy).unwrap(), minutes);
+        assert!(serde_json::from_str::<crate::HalfHour>(legacy).is_err());
+
+        // integers otherwise
+        let binary = |idx: i64| Binary(serde_json::Value::from(idx));
+        assert_eq!(Day::deserialize(binary(day.to_monotonic())).unwrap(), day);
+        assert_eq!(
+            Week::<Sunday>::deserialize(binary(week.to_monotonic())).unwrap(),
+            week
+        );
+        assert_eq!(FiveMinute::deserialize(binary(5_000_000)).unwrap(), minutes);
+        let month = Month::from(date);
+        assert_eq!(
+            Month::deserialize(binary(month.to_monotonic())).unwrap(),
+            month
+        );
+        let quarter = Quarter::from(date);
+        assert_eq!(
+            Quarter::deserialize(binary(quarter.to_monotonic())).unwrap(),
+            quarter
+        );
+        assert!(Day::deserialize(Binary(serde_json::Value::from("2021-02-03"))).is_err());
+
+        // zoned periods use the UTC timestamp of their start
+        type Zoned = crate::Zoned<FiveMinute, crate::FixedEast<36000>>;
+        let zoned = Zoned::from(
+            minutes
+                .start_datetime()
+                .with_timezone(&crate::FixedEast::<36000>),
+        );
+        assert_eq!(
+            Zoned::deserialize(binary(minutes.start_datetime().timestamp())).unwrap(),
+            zoned
+        );
+        assert!(Zoned::deserialize(binary(i64::MAX)).is_err());
+    }
+
+    #[cfg(feature = "tz")]
+    #[test]
+    fn test_human_readable_tz() {
+        type Zoned = crate::Zoned<FiveMinute, chrono_tz::Tz>;
+        let start = FiveMinute::from_monotonic(5_000_000).start_datetime();
+        let zoned = Zoned::from(start.with_timezone(&chrono_tz::Australia::Sydney));
+        let binary = Binary(serde_json::json!([start.timestamp(), "Australia/Sydney"]));
+        assert_eq!(Zoned::deserialize(binary).unwrap(), zoned);
+        assert_eq!(
+            serde_json::from_str::<Zoned>(&serde_json::to_string(&zoned).unwrap()).unwrap(),
+            zoned
+        );
+    }
+}
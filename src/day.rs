@@ -1,24 +1,21 @@
-use crate::DateResolution;
-use alloc::{
-    fmt, str,
-    string::{String, ToString},
-};
+use crate::{DateResolution, TimeResolution};
+#[cfg(feature = "alloc")]
+use alloc::borrow;
 use chrono::{DateTime, Datelike, NaiveDate, NaiveTime, Utc};
+use core::{fmt, str};
 #[cfg(feature = "serde")]
 use serde::de;
 
 const DATE_FORMAT: &str = "%Y-%m-%d";
 
+/// Serialized as a string for human readable formats, or otherwise as its monotonic `i64`.
 #[cfg(feature = "serde")]
 impl<'de> de::Deserialize<'de> for Day {
-    fn deserialize<D>(deserializer: D) -> std::result::Result<Day, D::Error>
+    fn deserialize<D>(deserializer: D) -> Result<Day, D::Error>
     where
         D: de::Deserializer<'de>,
     {
-        let s = String::deserialize(deserializer)?;
-        let date =
-            chrono::NaiveDate::parse_from_str(&s, DATE_FORMAT).map_err(serde::de::Error::custom)?;
-        Ok(date.into())
+        crate::compact::deserialize_readable(deserializer)
     }
 }
 
@@ -28,17 +25,22 @@ impl serde::Serialize for Day {
     where
         S: serde::Serializer,
     {
-        let s = self.to_string();
-        serializer.serialize_str(&s)
+        crate::compact::serialize_readable(self, serializer)
     }
 }
 
+/// The monotonic index of a `Day` is the number of days since 0000-01-01, not the Unix epoch of
+/// 1970-01-01. Use `Day::from_unix_days` and `Day::to_unix_days` to exchange days since the Unix
+/// epoch with other systems.
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[repr(transparent)]
 pub struct Day(i64);
 
-fn base() -> chrono::NaiveDate {
-    chrono::NaiveDate::from_ymd_opt(0, 1, 1).expect("valid date")
-}
+// SAFETY: `Day` is `repr(transparent)` over an `i64`, so every bit pattern is a valid `Day`
+#[cfg(feature = "bytemuck")]
+unsafe impl bytemuck::Zeroable for Day {}
+#[cfg(feature = "bytemuck")]
+unsafe impl bytemuck::Pod for Day {}
 
 impl str::FromStr for Day {
     type Err = crate::Error;
@@ -49,6 +51,7 @@ impl str::FromStr for Day {
 }
 
 impl fmt::Display for Day {
+    /// This is always ISO 8601, eg `2021-01-01`, including for the alternate form, `{:#}`.
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.start())
     }
@@ -56,7 +59,7 @@ impl fmt::Display for Day {
 
 impl crate::DateResolution for Day {
     fn start(&self) -> chrono::NaiveDate {
-        base() + chrono::Duration::days(self.0)
+        Day::EPOCH + chrono::Duration::days(self.0)
     }
 
     type Params = ();
@@ -64,7 +67,7 @@ impl crate::DateResolution for Day {
     fn params(&self) -> Self::Params {}
 
     fn from_date(date: NaiveDate, _params: Self::Params) -> Self {
-        Day((date - base()).num_days())
+        Day((date - Day::EPOCH).num_days())
     }
 }
 
@@ -74,27 +77,103 @@ impl crate::DateResolution for Day {
 //     }
 // }
 
-impl<D: Datelike> From<D> for Day {
-    fn from(value: D) -> Day {
-        Day::from_date(
-            chrono::NaiveDate::from_ymd_opt(value.year(), value.month(), value.day()).unwrap(),
-            (),
-        )
+impl From<NaiveDate> for Day {
+    fn from(value: NaiveDate) -> Day {
+        Day::from_date(value, ())
+    }
+}
+
+impl From<chrono::NaiveDateTime> for Day {
+    fn from(value: chrono::NaiveDateTime) -> Day {
+        value.date().into()
+    }
+}
+
+impl<Z: chrono::TimeZone> From<DateTime<Z>> for Day {
+    fn from(value: DateTime<Z>) -> Day {
+        value.date_naive().into()
+    }
+}
+
+/// The `Datelike` accessors refer to the date of the `Day`. Note that the inherent `year` and
+/// `month` methods, which return periods, take precedence over `Datelike::year` and
+/// `Datelike::month` unless the trait methods are called explicitly.
+impl Datelike for Day {
+    fn year(&self) -> i32 {
+        self.start().year()
+    }
+    fn month(&self) -> u32 {
+        self.start().month()
+    }
+    fn month0(&self) -> u32 {
+        self.start().month0()
+    }
+    fn day(&self) -> u32 {
+        self.start().day()
+    }
+    fn day0(&self) -> u32 {
+        self.start().day0()
+    }
+    fn ordinal(&self) -> u32 {
+        self.start().ordinal()
+    }
+    fn ordinal0(&self) -> u32 {
+        self.start().ordinal0()
+    }
+    fn weekday(&self) -> chrono::Weekday {
+        self.start().weekday()
+    }
+    fn iso_week(&self) -> chrono::IsoWeek {
+        self.start().iso_week()
+    }
+    fn with_year(&self, year: i32) -> Option<Day> {
+        self.start().with_year(year).map(Day::from)
+    }
+    fn with_month(&self, month: u32) -> Option<Day> {
+        self.start().with_month(month).map(Day::from)
+    }
+    fn with_month0(&self, month0: u32) -> Option<Day> {
+        self.start().with_month0(month0).map(Day::from)
+    }
+    fn with_day(&self, day: u32) -> Option<Day> {
+        self.start().with_day(day).map(Day::from)
+    }
+    fn with_day0(&self, day0: u32) -> Option<Day> {
+        self.start().with_day0(day0).map(Day::from)
+    }
+    fn with_ordinal(&self, ordinal: u32) -> Option<Day> {
+        self.start().with_ordinal(ordinal).map(Day::from)
+    }
+    fn with_ordinal0(&self, ordinal0: u32) -> Option<Day> {
+        self.start().with_ordinal0(ordinal0).map(Day::from)
     }
 }
 
 impl crate::TimeResolution for Day {
     fn succ_n(&self, n: u64) -> Day {
-        Day(self.0 + i64::try_from(n).unwrap())
+        self.checked_succ_n(n).expect("Day out of range")
     }
     fn pred_n(&self, n: u64) -> Day {
-        Day(self.0 - i64::try_from(n).unwrap())
+        self.checked_pred_n(n).expect("Day out of range")
+    }
+    fn checked_succ_n(&self, n: u64) -> Option<Day> {
+        self.0.checked_add_unsigned(n).map(Day)
+    }
+    fn checked_pred_n(&self, n: u64) -> Option<Day> {
+        self.0.checked_sub_unsigned(n).map(Day)
     }
     fn start_datetime(&self) -> DateTime<Utc> {
         self.start().and_time(NaiveTime::MIN).and_utc()
     }
-    fn name(&self) -> String {
-        "Day".to_string()
+    fn write_name(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        out.write_str("Day")
+    }
+    #[cfg(feature = "alloc")]
+    fn name(&self) -> borrow::Cow<'static, str> {
+        "Day".into()
+    }
+    fn fixed_duration() -> Option<chrono::Duration> {
+        Some(chrono::Duration::days(1))
     }
 }
 
@@ -113,7 +192,14 @@ impl crate::FromMonotonic for Day {
     }
 }
 
+impl crate::Bounded for Day {
+    const MIN: Day = Day(crate::MIN_DAY);
+    const MAX: Day = Day(crate::MAX_DAY - 1);
+}
+
 impl Day {
+    /// The date of the `Day` with a monotonic index of `0`, 0000-01-01.
+    pub const EPOCH: NaiveDate = NaiveDate::from_ymd_opt(0, 1, 1).expect("valid date");
     pub fn year(&self) -> super::Year {
         self.start().into()
     }
@@ -129,18 +215,94 @@ impl Day {
     pub fn year_num(&self) -> i32 {
         self.start().year()
     }
+    pub fn quarter_num(&self) -> u32 {
+        self.start().month0() / 3 + 1
+    }
     pub fn month_num(&self) -> u32 {
         self.start().month()
     }
+    pub fn day_num(&self) -> u32 {
+        self.start().day()
+    }
     pub fn new(date: NaiveDate) -> Self {
         date.into()
     }
+    /// The day `days` after 1970-01-01, or before it if `days` is negative.
+    pub fn from_unix_days(days: i64) -> Self {
+        Day(days + crate::EPOCH_DAY)
+    }
+    /// The number of days since 1970-01-01, which is negative for earlier days.
+    pub fn to_unix_days(&self) -> i64 {
+        self.0 - crate::EPOCH_DAY
+    }
+    /// The `n`th business day after this day according to `calendar`, for example the settlement
+    /// date of a T+2 trade with `trade_date.succ_business(2, &Weekends)`. When `n` is `0` this
+    /// is the same day, even if it isn't a business day.
+    ///
+    /// This is `None` if the search would step past `Day::MAX`, or finds more than a year of
+    /// consecutive non-business days, such as with a calendar which has no business days.
+    pub fn succ_business<C: crate::BusinessCalendar>(&self, n: u64, calendar: &C) -> Option<Day> {
+        self.step_business(n, calendar, true)
+    }
+    /// The `n`th business day before this day according to `calendar`. When `n` is `0` this is
+    /// the same day, even if it isn't a business day.
+    ///
+    /// This is `None` if the search would step past `Day::MIN`, or finds more than a year of
+    /// consecutive non-business days.
+    pub fn pred_business<C: crate::BusinessCalendar>(&self, n: u64, calendar: &C) -> Option<Day> {
+        self.step_business(n, calendar, false)
+    }
+    fn step_business<C: crate::BusinessCalendar>(
+        &self,
+        n: u64,
+        calendar: &C,
+        fwd: bool,
+    ) -> Option<Day> {
+        // the most non-business days skipped in a row before giving up
+        const MAX_SKIPPED: u32 = 366;
+
+        let mut day = *self;
+        for _ in 0..n {
+            let mut skipped = 0;
+            loop {
+                day = if fwd {
+                    Some(day.succ()).filter(|next| *next <= <Day as crate::Bounded>::MAX)?
+                } else {
+                    Some(day.pred()).filter(|prev| *prev >= <Day as crate::Bounded>::MIN)?
+                };
+                if calendar.is_business_day(day.start()) {
+                    break;
+                }
+                skipped += 1;
+                if skipped > MAX_SKIPPED {
+                    return None;
+                }
+            }
+        }
+        Some(day)
+    }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "alloc"))]
 mod tests {
     use super::*;
     use crate::{DateResolution, TimeResolution};
+    use alloc::string::ToString;
+
+    #[test]
+    fn test_unix_days() {
+        let epoch = Day::from_unix_days(0);
+        assert_eq!(epoch.to_string(), "1970-01-01");
+        assert_eq!(epoch.to_unix_days(), 0);
+        let day = "2021-12-06".parse::<Day>().unwrap();
+        assert_eq!(Day::from_unix_days(day.to_unix_days()), day);
+        assert_eq!(day.to_unix_days(), 18_967);
+        assert_eq!(epoch.pred().to_unix_days(), -1);
+        assert_eq!(
+            day.to_unix_days() * 86_400,
+            day.start_datetime().timestamp()
+        );
+    }
 
     #[cfg(feature = "serde")]
     #[test]
@@ -197,4 +359,46 @@ mod tests {
             chrono::NaiveDate::from_ymd_opt(-1, 12, 30).unwrap()
         );
     }
+
+    #[test]
+    fn test_pre_epoch() {
+        use crate::{DateResolutionExt, Minutes};
+
+        for date in [(-1, 12, 31), (-500, 2, 28), (500, 3, 1), (1969, 12, 31)] {
+            let day = Day::from(chrono::NaiveDate::from_ymd_opt(date.0, date.1, date.2).unwrap());
+            assert_eq!(day, day.to_string().parse().unwrap());
+        }
+        assert_eq!("-0001-12-31".parse::<Day>().unwrap(), Day(-1));
+
+        let day = "1969-12-31".parse::<Day>().unwrap();
+        let half_hours = day.to_sub_date_resolution::<Minutes<30>>();
+        assert_eq!(half_hours.len().get(), 48);
+        assert_eq!(half_hours.start().start_datetime(), day.start_datetime());
+        assert_eq!(
+            half_hours.end().succ().start_datetime(),
+            day.succ().start_datetime()
+        );
+    }
+
+    #[test]
+    fn test_datelike() {
+        fn describe<D: Datelike>(date: D) -> (i32, u32, u32, chrono::Weekday) {
+            (date.year(), date.month(), date.day(), date.weekday())
+        }
+
+        let day = "2021-08-15".parse::<Day>().unwrap();
+        assert_eq!(describe(day), (2021, 8, 15, chrono::Weekday::Sun));
+        assert_eq!(describe(day), describe(day.start()));
+        assert_eq!(day.ordinal(), 227);
+        assert_eq!(day.iso_week(), day.start().iso_week());
+
+        assert_eq!(day.with_day(31), Some("2021-08-31".parse().unwrap()));
+        assert_eq!(day.with_year(2020), Some("2020-08-15".parse().unwrap()));
+        assert_eq!(day.with_month(2).and_then(|d| d.with_day(30)), None);
+
+        // the inherent methods still return periods
+        assert_eq!(day.year(), "2021".parse().unwrap());
+        assert_eq!((day.year_num(), day.quarter_num()), (2021, 3));
+        assert_eq!((day.month_num(), day.day_num()), (8, 15));
+    }
 }
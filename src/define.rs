@@ -0,0 +1,518 @@
+/// Define a custom resolution with a fixed length, for periods which aren't covered by the
+/// resolutions in this crate, such as four hour blocks starting at 02:00, or fortnights.
+///
+/// A sub-date resolution is given its length in seconds, which must evenly divide a day, and
+/// optionally the offset in seconds of its periods from midnight UTC. A date resolution is given
+/// its length in days and the date that the period with a monotonic index of `0` starts on.
+///
+/// The generated type implements `TimeResolution`, `Monotonic`, `FromMonotonic`, either
+/// `SubDateResolution` or `DateResolution` (both with `Params = ()`), `From<DateTime<Utc>>`,
+/// `Display` and `FromStr`, as well as `serde` when that feature of this crate is enabled.
+/// Sub-date periods are written as an ISO 8601 interval, eg
+/// `2021-01-01T02:00:00Z/2021-01-01T06:00:00Z`, and date periods as eg
+/// `Fortnight starting 2021-01-04`.
+///
+/// To use the resolution with `rescale` and `Contains`, also register it with
+/// `impl_duration_ordering`.
+///
+/// ```
+/// use resolution::{DateResolution, SubDateResolution, TimeResolution};
+///
+/// resolution::define_resolution! {
+///     /// Four hour blocks starting at 02:00 UTC
+///     pub struct FourHour: SubDateResolution { seconds: 4 * 60 * 60, offset: 2 * 60 * 60 }
+/// }
+///
+/// resolution::define_resolution! {
+///     /// Fortnights starting on a Monday
+///     pub struct Fortnight: DateResolution {
+///         days: 14,
+///         epoch: chrono::NaiveDate::from_ymd_opt(2021, 1, 4).expect("valid date"),
+///     }
+/// }
+///
+/// # #[cfg(feature = "alloc")] {
+/// let block = "2021-01-01T02:00:00Z/2021-01-01T06:00:00Z".parse::<FourHour>().unwrap();
+/// assert_eq!(block.succ().to_string(), "2021-01-01T06:00:00Z/2021-01-01T10:00:00Z");
+/// assert_eq!(FourHour::PERIODS_PER_DAY, 6);
+///
+/// let fortnight = Fortnight::from(chrono::NaiveDate::from_ymd_opt(2021, 1, 20).unwrap());
+/// assert_eq!(fortnight.to_string(), "Fortnight starting 2021-01-18");
+/// assert_eq!(fortnight.name(), "Fortnight");
+/// # }
+/// ```
+#[macro_export]
+macro_rules! define_resolution {
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident: SubDateResolution {
+            seconds: $seconds:expr
+            $(, offset: $offset:expr)?
+            $(,)?
+        }
+    ) => {
+        $crate::__define_resolution_common!($(#[$meta])* $vis $name);
+
+        impl $name {
+            /// The length of each period, in seconds.
+            pub const SECONDS: i64 = $seconds;
+            /// The offset of the start of each period from midnight UTC, in seconds.
+            pub const OFFSET: i64 = 0 $(+ $offset)?;
+        }
+
+        const _: () = assert!(
+            $name::SECONDS > 0
+                && 86_400 % $name::SECONDS == 0
+                && 0 <= $name::OFFSET
+                && $name::OFFSET < $name::SECONDS,
+            "The length must evenly divide a day, and the offset must be shorter than the length"
+        );
+
+        impl $crate::TimeResolution for $name {
+            fn succ_n(&self, n: u64) -> Self {
+                $crate::TimeResolution::checked_succ_n(self, n)
+                    .expect(concat!(stringify!($name), " out of range"))
+            }
+            fn pred_n(&self, n: u64) -> Self {
+                $crate::TimeResolution::checked_pred_n(self, n)
+                    .expect(concat!(stringify!($name), " out of range"))
+            }
+            fn checked_succ_n(&self, n: u64) -> ::core::option::Option<Self> {
+                let index = self.index.checked_add_unsigned(n)?;
+                ::core::option::Option::Some($name { index })
+            }
+            fn checked_pred_n(&self, n: u64) -> ::core::option::Option<Self> {
+                let index = self.index.checked_sub_unsigned(n)?;
+                ::core::option::Option::Some($name { index })
+            }
+            fn start_datetime(&self) -> $crate::__private::UtcDateTime {
+                $crate::__private::chrono::DateTime::from_timestamp(
+                    self.index * Self::SECONDS + Self::OFFSET,
+                    0,
+                )
+                .expect("valid timestamp")
+            }
+            fn write_name(&self, out: &mut dyn ::core::fmt::Write) -> ::core::fmt::Result {
+                out.write_str(stringify!($name))
+            }
+            fn fixed_duration() -> ::core::option::Option<$crate::__private::chrono::Duration> {
+                let length = $crate::__private::chrono::Duration::seconds(Self::SECONDS);
+                ::core::option::Option::Some(length)
+            }
+        }
+
+        impl $crate::SubDateResolution for $name {
+            type Params = ();
+            const PERIODS_PER_DAY: u32 = (86_400 / $name::SECONDS) as u32;
+            fn exact_duration() -> $crate::__private::chrono::Duration {
+                $crate::__private::chrono::Duration::seconds(Self::SECONDS)
+            }
+            fn params(&self) -> Self::Params {}
+            fn occurs_on_date(&self) -> $crate::__private::chrono::NaiveDate {
+                $crate::TimeResolution::start_datetime(self).date_naive()
+            }
+            fn from_utc_datetime(
+                datetime: $crate::__private::UtcDateTime,
+                _params: Self::Params,
+            ) -> Self {
+                datetime.into()
+            }
+            fn first_on_day(
+                day: $crate::__private::chrono::NaiveDate,
+                _params: Self::Params,
+            ) -> Self {
+                let secs = day
+                    .and_time($crate::__private::chrono::NaiveTime::MIN)
+                    .and_utc()
+                    .timestamp()
+                    - Self::OFFSET;
+                // the first period starting on or after midnight
+                $name {
+                    index: -(-secs).div_euclid(Self::SECONDS),
+                }
+            }
+        }
+
+        impl ::core::convert::From<$crate::__private::UtcDateTime> for $name {
+            fn from(datetime: $crate::__private::UtcDateTime) -> Self {
+                $name {
+                    index: (datetime.timestamp() - Self::OFFSET).div_euclid(Self::SECONDS),
+                }
+            }
+        }
+
+        impl ::core::fmt::Display for $name {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                $crate::__private::write_interval(
+                    f,
+                    $crate::TimeResolution::start_datetime(self),
+                    $crate::TimeResolution::start_datetime(&$crate::TimeResolution::succ(self)),
+                )
+            }
+        }
+
+        impl ::core::str::FromStr for $name {
+            type Err = $crate::Error;
+            fn from_str(s: &str) -> ::core::result::Result<Self, Self::Err> {
+                $crate::__private::parse_interval(s, stringify!($name))
+            }
+        }
+    };
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident: DateResolution {
+            days: $days:expr,
+            epoch: $epoch:expr
+            $(,)?
+        }
+    ) => {
+        $crate::__define_resolution_common!($(#[$meta])* $vis $name);
+
+        impl $name {
+            /// The length of each period, in days.
+            pub const DAYS: i64 = $days;
+            /// The start date of the period with a monotonic index of `0`.
+            pub const EPOCH: $crate::__private::chrono::NaiveDate = $epoch;
+        }
+
+        const _: () = assert!($name::DAYS > 0, "The length must be at least one day");
+
+        impl $crate::TimeResolution for $name {
+            fn succ_n(&self, n: u64) -> Self {
+                $crate::TimeResolution::checked_succ_n(self, n)
+                    .expect(concat!(stringify!($name), " out of range"))
+            }
+            fn pred_n(&self, n: u64) -> Self {
+                $crate::TimeResolution::checked_pred_n(self, n)
+                    .expect(concat!(stringify!($name), " out of range"))
+            }
+            fn checked_succ_n(&self, n: u64) -> ::core::option::Option<Self> {
+                let index = self.index.checked_add_unsigned(n)?;
+                ::core::option::Option::Some($name { index })
+            }
+            fn checked_pred_n(&self, n: u64) -> ::core::option::Option<Self> {
+                let index = self.index.checked_sub_unsigned(n)?;
+                ::core::option::Option::Some($name { index })
+            }
+            fn start_datetime(&self) -> $crate::__private::UtcDateTime {
+                $crate::DateResolution::start(self)
+                    .and_time($crate::__private::chrono::NaiveTime::MIN)
+                    .and_utc()
+            }
+            fn write_name(&self, out: &mut dyn ::core::fmt::Write) -> ::core::fmt::Result {
+                out.write_str(stringify!($name))
+            }
+            fn fixed_duration() -> ::core::option::Option<$crate::__private::chrono::Duration> {
+                ::core::option::Option::Some($crate::__private::chrono::Duration::days(Self::DAYS))
+            }
+        }
+
+        impl $crate::DateResolution for $name {
+            type Params = ();
+            fn params(&self) -> Self::Params {}
+            fn start(&self) -> $crate::__private::chrono::NaiveDate {
+                Self::EPOCH + $crate::__private::chrono::Duration::days(self.index * Self::DAYS)
+            }
+            fn from_date(
+                date: $crate::__private::chrono::NaiveDate,
+                _params: Self::Params,
+            ) -> Self {
+                $name {
+                    index: (date - Self::EPOCH).num_days().div_euclid(Self::DAYS),
+                }
+            }
+        }
+
+        impl ::core::convert::From<$crate::__private::chrono::NaiveDate> for $name {
+            fn from(date: $crate::__private::chrono::NaiveDate) -> Self {
+                $crate::DateResolution::from_date(date, ())
+            }
+        }
+
+        impl ::core::convert::From<$crate::__private::UtcDateTime> for $name {
+            fn from(datetime: $crate::__private::UtcDateTime) -> Self {
+                $crate::DateResolution::from_date(datetime.date_naive(), ())
+            }
+        }
+
+        impl ::core::fmt::Display for $name {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                write!(
+                    f,
+                    "{} starting {}",
+                    stringify!($name),
+                    $crate::DateResolution::start(self)
+                )
+            }
+        }
+
+        impl ::core::str::FromStr for $name {
+            type Err = $crate::Error;
+            fn from_str(s: &str) -> ::core::result::Result<Self, Self::Err> {
+                $crate::__private::parse_starting(s, stringify!($name))
+            }
+        }
+    };
+}
+
+// the parts of `define_resolution` shared by sub-date and date resolutions
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __define_resolution_common {
+    ($(#[$meta:meta])* $vis:vis $name:ident) => {
+        $(#[$meta])*
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        $vis struct $name {
+            index: i64,
+        }
+
+        impl $crate::Monotonic for $name {
+            fn to_monotonic(&self) -> i64 {
+                self.index
+            }
+            fn between(&self, other: Self) -> i64 {
+                other.index - self.index
+            }
+        }
+
+        impl $crate::FromMonotonic for $name {
+            fn from_monotonic(index: i64) -> Self {
+                $name { index }
+            }
+        }
+
+        $crate::__define_resolution_serde!($name);
+    };
+}
+
+#[cfg(feature = "serde")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __define_resolution_serde {
+    ($name:ident) => {
+        impl $crate::__private::serde::Serialize for $name {
+            fn serialize<S>(&self, serializer: S) -> ::core::result::Result<S::Ok, S::Error>
+            where
+                S: $crate::__private::serde::Serializer,
+            {
+                $crate::__private::serialize_readable(self, serializer)
+            }
+        }
+
+        impl<'de> $crate::__private::serde::Deserialize<'de> for $name {
+            fn deserialize<D>(deserializer: D) -> ::core::result::Result<Self, D::Error>
+            where
+                D: $crate::__private::serde::Deserializer<'de>,
+            {
+                $crate::__private::deserialize_readable(deserializer)
+            }
+        }
+    };
+}
+
+#[cfg(not(feature = "serde"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __define_resolution_serde {
+    ($name:ident) => {};
+}
+
+// used by the expansion of `define_resolution`, so that it works without the caller depending on
+// the same crates, or features of them, as this crate
+#[doc(hidden)]
+pub mod __private {
+    #[cfg(feature = "serde")]
+    pub use crate::compact::{deserialize_readable, serialize_readable};
+    pub use chrono;
+    #[cfg(feature = "serde")]
+    pub use serde;
+
+    pub type UtcDateTime = chrono::DateTime<chrono::Utc>;
+
+    use chrono::{DateTime, Datelike, Timelike, Utc};
+    use core::fmt;
+
+    fn write_datetime(f: &mut fmt::Formatter<'_>, n: DateTime<Utc>) -> fmt::Result {
+        crate::write_iso_year(f, n.year().into())?;
+        write!(
+            f,
+            "-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+            n.month(),
+            n.day(),
+            n.hour(),
+            n.minute(),
+            n.second()
+        )
+    }
+
+    pub fn write_interval(
+        f: &mut fmt::Formatter<'_>,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> fmt::Result {
+        write_datetime(f, start)?;
+        f.write_str("/")?;
+        write_datetime(f, end)
+    }
+
+    // parse the ISO 8601 interval written by `write_interval`, checking that it is a single period
+    pub fn parse_interval<R>(s: &str, ty_name: &'static str) -> Result<R, crate::Error>
+    where
+        R: crate::TimeResolution + From<DateTime<Utc>>,
+    {
+        let invalid = || crate::Error::custom(ty_name, format_args!("{s}"));
+        let (start, end) = s.split_once('/').ok_or_else(invalid)?;
+        let start = DateTime::parse_from_rfc3339(start)?.to_utc();
+        let end = DateTime::parse_from_rfc3339(end)?.to_utc();
+        let period = R::from(start);
+        if period.start_datetime() != start || period.succ().start_datetime() != end {
+            return Err(invalid());
+        }
+        Ok(period)
+    }
+
+    // parse the `{ty_name} starting {date}` form, checking that the date is the start of a period
+    pub fn parse_starting<R>(s: &str, ty_name: &'static str) -> Result<R, crate::Error>
+    where
+        R: crate::DateResolution<Params = ()>,
+    {
+        let invalid = || crate::Error::custom(ty_name, format_args!("{s}"));
+        let date = s
+            .strip_prefix(ty_name)
+            .and_then(|rest| rest.strip_prefix(" starting "))
+            .ok_or_else(invalid)?;
+        let date = chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d")?;
+        let period = R::from_date(date, ());
+        if period.start() != date {
+            return Err(invalid());
+        }
+        Ok(period)
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use crate::{Contains, Day, Minute, Monday, Week};
+    use crate::{
+        DateResolution, DateResolutionExt, FromMonotonic, Monotonic, SubDateResolution,
+        TimeResolution,
+    };
+    use alloc::string::ToString;
+    use chrono::NaiveDate;
+
+    crate::define_resolution! {
+        pub struct FourHour: SubDateResolution { seconds: 4 * 60 * 60, offset: 2 * 60 * 60 }
+    }
+
+    crate::define_resolution! {
+        struct HalfMinute: SubDateResolution { seconds: 30 }
+    }
+
+    crate::define_resolution! {
+        pub(crate) struct Fortnight: DateResolution {
+            days: 14,
+            epoch: NaiveDate::from_ymd_opt(2021, 1, 4).expect("valid date"),
+        }
+    }
+
+    crate::impl_duration_ordering!(HalfMinute: shorter_than(Minute, Day, Fortnight));
+    crate::impl_duration_ordering!(Fortnight: longer_than(Minute, Day, Week<Monday>));
+
+    fn date(s: &str) -> NaiveDate {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_sub_date() {
+        let block = FourHour::from(date("2021-01-01").and_hms_opt(1, 59, 0).unwrap().and_utc());
+        assert_eq!(
+            block.to_string(),
+            "2020-12-31T22:00:00Z/2021-01-01T02:00:00Z"
+        );
+        assert_eq!(block.occurs_on_date(), date("2020-12-31"));
+        assert_eq!(FourHour::first_on_day(date("2021-01-01"), ()), block.succ());
+        assert_eq!(
+            FourHour::last_on_day(date("2021-01-01"), ()),
+            block.succ_n(6)
+        );
+        assert_eq!(FourHour::PERIODS_PER_DAY, 6);
+        assert_eq!(FourHour::exact_duration(), chrono::Duration::hours(4));
+        assert_eq!(block.name(), "FourHour");
+
+        let half = "2021-01-01T10:00:30Z/2021-01-01T10:01:00Z"
+            .parse::<HalfMinute>()
+            .unwrap();
+        assert_eq!(half.to_string().parse::<HalfMinute>().unwrap(), half);
+        assert_eq!(half.start_datetime().timestamp(), 1_609_495_230);
+        assert_eq!(HalfMinute::from_monotonic(half.to_monotonic()), half);
+        assert_eq!(HalfMinute::PERIODS_PER_DAY, 2880);
+
+        // not a single period
+        assert!("2021-01-01T10:00:30Z/2021-01-01T10:01:30Z"
+            .parse::<HalfMinute>()
+            .is_err());
+        assert!("2021-01-01T10:00:15Z/2021-01-01T10:00:45Z"
+            .parse::<HalfMinute>()
+            .is_err());
+    }
+
+    #[test]
+    fn test_date() {
+        let fortnight = Fortnight::from(date("2021-01-20"));
+        assert_eq!(fortnight.start(), date("2021-01-18"));
+        assert_eq!(fortnight.end(), date("2021-01-31"));
+        assert_eq!(fortnight.to_monotonic(), 1);
+        assert_eq!(
+            Fortnight::from(date("2021-01-03")).start(),
+            date("2020-12-21")
+        );
+        assert_eq!(fortnight.to_string(), "Fortnight starting 2021-01-18");
+        assert_eq!(
+            "Fortnight starting 2021-01-18"
+                .parse::<Fortnight>()
+                .unwrap(),
+            fortnight
+        );
+        assert_eq!(fortnight.num_days(), 14);
+        assert_eq!(
+            Fortnight::fixed_duration(),
+            Some(chrono::Duration::days(14))
+        );
+
+        assert!("Fortnight starting 2021-01-19"
+            .parse::<Fortnight>()
+            .is_err());
+        assert!("Week starting 2021-01-18".parse::<Fortnight>().is_err());
+    }
+
+    #[test]
+    fn test_duration_ordering() {
+        let fortnight = Fortnight::from(date("2021-01-20"));
+        let weeks = fortnight.rescale::<Week<Monday>>();
+        assert_eq!(weeks.len().get(), 2);
+        assert_eq!(weeks.start().start(), date("2021-01-18"));
+        assert_eq!(fortnight.rescale::<Day>().len().get(), 14);
+        assert_eq!(fortnight.rescale_to::<HalfMinute>().len().get(), 14 * 2880);
+        assert!(fortnight.contains(Day::from(date("2021-01-31"))));
+        assert!(!fortnight.contains(Day::from(date("2021-02-01"))));
+
+        let minute = "2021-01-01 10:00".parse::<Minute>().unwrap();
+        let halves = minute.rescale_to::<HalfMinute>();
+        assert_eq!(halves.len().get(), 2);
+        assert!(minute.contains(halves.end()));
+        assert!(!minute.contains(halves.end().succ()));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let fortnight = Fortnight::from(date("2021-01-20"));
+        let json = serde_json::to_string(&fortnight).unwrap();
+        assert_eq!(json, "\"Fortnight starting 2021-01-18\"");
+        assert_eq!(serde_json::from_str::<Fortnight>(&json).unwrap(), fortnight);
+
+        let block = FourHour::from_monotonic(100);
+        let json = serde_json::to_string(&block).unwrap();
+        assert_eq!(serde_json::from_str::<FourHour>(&json).unwrap(), block);
+    }
+}
@@ -0,0 +1,101 @@
+use alloc::{borrow, boxed::Box};
+use chrono::{DateTime, Utc};
+use core::{any, fmt};
+
+use crate::TimeResolution;
+
+/// `DynTimeResolution` is an object-safe companion to `TimeResolution`, so that periods of
+/// different resolutions can be stored together as `Box<dyn DynTimeResolution>`. It is
+/// implemented for every `TimeResolution` which is also `Debug`, `Send`, `Sync` and `'static`,
+/// which includes all those in this crate.
+///
+/// The methods are prefixed with `dyn_` so they don't conflict with those of `TimeResolution`
+/// when both traits are in scope. The concrete period can be recovered with `as_any`.
+pub trait DynTimeResolution: fmt::Debug + Send + Sync + 'static {
+    fn dyn_succ_n(&self, n: u64) -> Box<dyn DynTimeResolution>;
+    fn dyn_pred_n(&self, n: u64) -> Box<dyn DynTimeResolution>;
+    fn dyn_succ(&self) -> Box<dyn DynTimeResolution> {
+        self.dyn_succ_n(1)
+    }
+    fn dyn_pred(&self) -> Box<dyn DynTimeResolution> {
+        self.dyn_pred_n(1)
+    }
+    fn dyn_start_datetime(&self) -> DateTime<Utc>;
+    fn dyn_name(&self) -> borrow::Cow<'static, str>;
+    fn dyn_to_monotonic(&self) -> i64;
+    fn as_any(&self) -> &dyn any::Any;
+}
+
+impl<T> DynTimeResolution for T
+where
+    T: TimeResolution + fmt::Debug + Send + Sync + 'static,
+{
+    fn dyn_succ_n(&self, n: u64) -> Box<dyn DynTimeResolution> {
+        Box::new(self.succ_n(n))
+    }
+    fn dyn_pred_n(&self, n: u64) -> Box<dyn DynTimeResolution> {
+        Box::new(self.pred_n(n))
+    }
+    fn dyn_start_datetime(&self) -> DateTime<Utc> {
+        self.start_datetime()
+    }
+    fn dyn_name(&self) -> borrow::Cow<'static, str> {
+        self.name()
+    }
+    fn dyn_to_monotonic(&self) -> i64 {
+        self.to_monotonic()
+    }
+    fn as_any(&self) -> &dyn any::Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Day, FiveMinute, Monday, Month, Week};
+    use alloc::vec::Vec;
+
+    #[test]
+    fn test_heterogeneous() {
+        let periods: Vec<Box<dyn DynTimeResolution>> = alloc::vec![
+            Box::new("2021-12-06".parse::<Day>().unwrap()),
+            Box::new("Dec-2021".parse::<Month>().unwrap()),
+            Box::new("Week starting 2021-12-06".parse::<Week<Monday>>().unwrap()),
+            Box::new(
+                "2021-12-06 10:05 => 2021-12-06 10:10"
+                    .parse::<FiveMinute>()
+                    .unwrap()
+            ),
+        ];
+
+        let names = periods.iter().map(|p| p.dyn_name()).collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            ["Day", "Month", "Week[StartDay:Monday]", "Minutes[Length:5]"]
+        );
+
+        let next = periods.iter().map(|p| p.dyn_succ()).collect::<Vec<_>>();
+        assert_eq!(
+            next[0].as_any().downcast_ref::<Day>(),
+            Some(&"2021-12-07".parse::<Day>().unwrap())
+        );
+        assert_eq!(
+            next[1].dyn_start_datetime(),
+            "Jan-2022".parse::<Month>().unwrap().start_datetime()
+        );
+        assert_eq!(
+            next[3].dyn_to_monotonic(),
+            periods[3].dyn_to_monotonic() + 1
+        );
+        assert_eq!(
+            next[2].dyn_pred().dyn_start_datetime(),
+            periods[2].dyn_start_datetime()
+        );
+        assert_eq!(
+            periods[0].dyn_pred_n(6).dyn_start_datetime(),
+            periods[0].dyn_start_datetime() - chrono::Duration::days(6)
+        );
+        assert!(periods[1].as_any().downcast_ref::<Day>().is_none());
+    }
+}
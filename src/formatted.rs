@@ -0,0 +1,116 @@
+use crate::{TimeRange, TimeResolution};
+use core::fmt;
+
+/// `Formatted` displays a period using a strftime-like pattern, as with `chrono`'s `format`,
+/// applied to the start of the period in UTC.
+///
+/// This works for any `TimeResolution`, including sub-date resolutions:
+///
+/// ```
+/// use resolution::{Formatted, Minute};
+///
+/// let minute = "2021-01-01 10:07".parse::<Minute>().unwrap();
+/// assert_eq!(Formatted(minute, "%d/%m/%Y %H%M").to_string(), "01/01/2021 1007");
+/// ```
+///
+/// For a `TimeRange`, the pattern is instead literal text, containing the placeholders
+/// `{start}` and `{end}`, which write the first and last periods of the range using their
+/// `Display`, or `{start:PATTERN}` and `{end:PATTERN}`, which write the start of those
+/// periods using the strftime-like `PATTERN`:
+///
+/// ```
+/// use resolution::{Day, Formatted, TimeRange, TimeResolution};
+///
+/// let start = "2021-01-01".parse::<Day>().unwrap();
+/// let range = TimeRange::from_unordered_bounds(start, start.succ_n(6));
+/// assert_eq!(
+///     Formatted(range, "{start:%e %b} to {end:%e %b %Y}").to_string(),
+///     " 1 Jan to  7 Jan 2021"
+/// );
+/// assert_eq!(Formatted(range, "{start} - {end}").to_string(), "2021-01-01 - 2021-01-07");
+/// ```
+///
+/// As with `chrono`, an invalid pattern causes `Display` to return an error, so methods such as
+/// `to_string` will panic.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Formatted<'a, R>(pub R, pub &'a str);
+
+impl<R: TimeResolution> fmt::Display for Formatted<'_, R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0.start_datetime().format(self.1))
+    }
+}
+
+impl<P: TimeResolution + fmt::Display> fmt::Display for Formatted<'_, TimeRange<P>> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut rest = self.1;
+        while let Some(open) = rest.find('{') {
+            f.write_str(&rest[..open])?;
+            rest = &rest[open..];
+            let placeholder = rest.find('}').and_then(|close| {
+                let (name, pattern) = match rest[1..close].split_once(':') {
+                    Some((name, pattern)) => (name, Some(pattern)),
+                    None => (&rest[1..close], None),
+                };
+                let period = match name {
+                    "start" => self.0.start(),
+                    "end" => self.0.end(),
+                    _ => return None,
+                };
+                Some((close, period, pattern))
+            });
+            match placeholder {
+                Some((close, period, Some(pattern))) => {
+                    write!(f, "{}", period.start_datetime().format(pattern))?;
+                    rest = &rest[close + 1..];
+                }
+                Some((close, period, None)) => {
+                    write!(f, "{}", period)?;
+                    rest = &rest[close + 1..];
+                }
+                // not a placeholder, so written as is
+                None => {
+                    f.write_str("{")?;
+                    rest = &rest[1..];
+                }
+            }
+        }
+        f.write_str(rest)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Day, Minutes, Monday, Month, Week};
+    use alloc::string::ToString;
+
+    #[test]
+    fn test_formatted() {
+        let month = "Feb-2021".parse::<Month>().unwrap();
+        assert_eq!(Formatted(month, "%B %Y").to_string(), "February 2021");
+        let week = Week::<Monday>::from(chrono::NaiveDate::from_ymd_opt(2021, 2, 3).unwrap());
+        assert_eq!(Formatted(week, "%G-W%V").to_string(), "2021-W05");
+        let minutes = "2021-02-03 10:05 => 2021-02-03 10:10"
+            .parse::<Minutes<5>>()
+            .unwrap();
+        assert_eq!(Formatted(minutes, "%H:%M").to_string(), "10:05");
+
+        let start = "2021-02-03".parse::<Day>().unwrap();
+        let range = TimeRange::from_unordered_bounds(start, start.succ_n(2));
+        assert_eq!(
+            Formatted(range, "{start:%d/%m} {end:%d/%m}").to_string(),
+            "03/02 05/02"
+        );
+        assert_eq!(
+            Formatted(range, "from {start} until {end}").to_string(),
+            "from 2021-02-03 until 2021-02-05"
+        );
+        // braces which aren't placeholders are written as is
+        assert_eq!(
+            Formatted(range, "{other} {start:%d} { {end:%d}").to_string(),
+            "{other} 03 { 05"
+        );
+        assert_eq!(Formatted(range, "{start").to_string(), "{start");
+    }
+}
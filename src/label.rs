@@ -0,0 +1,94 @@
+use crate::TimeResolution;
+use alloc::{
+    collections,
+    string::{String, ToString},
+};
+use core::{fmt, num};
+
+/// `LabelCache` memoises the `Display` output of periods, keeping at most `capacity` labels
+/// and evicting the least recently used label when full.
+///
+/// This is useful when writing out reports or CSVs where the same period is formatted for many rows.
+pub struct LabelCache<P: TimeResolution + fmt::Display> {
+    capacity: num::NonZeroUsize,
+    // the label for each period, along with the tick at which it was last used
+    labels: collections::BTreeMap<P, (String, u64)>,
+    // the periods in the cache, keyed by the tick at which they were last used
+    recency: collections::BTreeMap<u64, P>,
+    tick: u64,
+}
+
+impl<P: TimeResolution + fmt::Display> LabelCache<P> {
+    pub fn new(capacity: num::NonZeroUsize) -> LabelCache<P> {
+        LabelCache {
+            capacity,
+            labels: collections::BTreeMap::new(),
+            recency: collections::BTreeMap::new(),
+            tick: 0,
+        }
+    }
+    pub fn label(&mut self, period: P) -> &str {
+        self.tick += 1;
+        let tick = self.tick;
+
+        if let Some((_, last_used)) = self.labels.get_mut(&period) {
+            self.recency.remove(last_used);
+            *last_used = tick;
+        } else {
+            if self.labels.len() >= self.capacity.get() {
+                if let Some((_, oldest)) = self.recency.pop_first() {
+                    self.labels.remove(&oldest);
+                }
+            }
+            self.labels.insert(period, (period.to_string(), tick));
+        }
+        self.recency.insert(tick, period);
+
+        self.labels
+            .get(&period)
+            .map(|(label, _)| label.as_str())
+            .expect("Label was inserted above")
+    }
+    pub fn len(&self) -> usize {
+        self.labels.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.labels.is_empty()
+    }
+    pub fn clear(&mut self) {
+        self.labels.clear();
+        self.recency.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Month;
+
+    #[test]
+    fn test_label_cache() {
+        let jan = "Jan-2021".parse::<Month>().unwrap();
+        let feb = jan.succ();
+        let mar = feb.succ();
+
+        let mut cache = LabelCache::new(num::NonZeroUsize::new(2).unwrap());
+        assert!(cache.is_empty());
+
+        assert_eq!(cache.label(jan), "Jan-2021");
+        assert_eq!(cache.label(feb), "Feb-2021");
+        assert_eq!(cache.label(jan), "Jan-2021");
+        assert_eq!(cache.len(), 2);
+
+        // feb is the least recently used, so is evicted
+        assert_eq!(cache.label(mar), "Mar-2021");
+        assert_eq!(cache.len(), 2);
+        assert!(cache.labels.contains_key(&jan));
+        assert!(!cache.labels.contains_key(&feb));
+        assert!(cache.labels.contains_key(&mar));
+
+        cache.clear();
+        assert!(cache.is_empty());
+        assert_eq!(cache.label(feb), "Feb-2021");
+    }
+}
@@ -1,18 +1,21 @@
 #![no_std]
+#![cfg_attr(resolution_nightly, feature(step_trait))]
+#[cfg(feature = "alloc")]
 extern crate alloc;
 #[cfg(feature = "std")]
 extern crate std;
 
-use core::{
-    any, fmt,
-    num::{self, ParseIntError},
-    str,
-};
+use core::{any, cmp, fmt, num, str};
 
 mod range;
-use alloc::{format, string::String};
+#[cfg(feature = "alloc")]
+use alloc::{borrow, string::String};
 use chrono::{DateTime, NaiveDate, Utc};
-pub use range::{Cache, CacheResponse, TimeRange, TimeRangeComparison, TimeRangeIter};
+#[cfg(feature = "alloc")]
+pub use range::{
+    missing_ranges, Cache, CacheEvent, CacheResponse, CacheStats, MergeDecision, TimeRangeSet,
+};
+pub use range::{TimeRange, TimeRangeChunks, TimeRangeComparison, TimeRangeIter};
 
 mod minutes;
 pub use minutes::{DaySubdivison, Minutes};
@@ -36,7 +39,72 @@ mod year;
 pub use year::Year;
 
 mod zoned;
-pub use zoned::{FixedTimeZone, Zoned};
+pub use zoned::{FixedEast, FixedTimeZone, Zoned};
+
+#[cfg(feature = "tz")]
+pub use chrono_tz::Tz;
+
+mod clock;
+#[cfg(feature = "std")]
+pub use clock::SystemClock;
+pub use clock::{Clock, FixedClock};
+
+#[cfg(feature = "alloc")]
+mod dynamic;
+#[cfg(feature = "alloc")]
+pub use dynamic::DynTimeResolution;
+
+mod calendar;
+#[cfg(feature = "alloc")]
+pub use calendar::Holidays;
+pub use calendar::{BusinessCalendar, Weekends};
+
+mod define;
+#[doc(hidden)]
+pub use define::__private;
+
+#[cfg(feature = "alloc")]
+mod label;
+#[cfg(feature = "alloc")]
+pub use label::LabelCache;
+
+#[cfg(feature = "serde")]
+mod compact;
+#[cfg(feature = "serde")]
+pub use compact::{Compact, StartDate};
+#[cfg(feature = "serde_with")]
+pub mod serde_as;
+
+#[cfg(feature = "proptest")]
+pub mod strategies;
+
+#[cfg(feature = "rand")]
+mod random;
+#[cfg(feature = "rand")]
+pub use random::SubRange;
+
+#[cfg(feature = "sqlx-postgres")]
+mod postgres;
+
+#[cfg(feature = "rayon")]
+mod parallel;
+#[cfg(feature = "rayon")]
+pub use parallel::TimeRangeParIter;
+
+#[cfg(feature = "polars")]
+mod series;
+#[cfg(feature = "polars")]
+pub use series::periods_from_series;
+
+#[cfg(feature = "alloc")]
+mod formatted;
+#[cfg(feature = "alloc")]
+pub use formatted::Formatted;
+
+#[cfg(feature = "async")]
+mod provider;
+#[cfg(feature = "async")]
+pub use provider::CachedProvider;
 
 pub trait LongerThan<T>: LongerThanOrEqual<T> {}
 
@@ -53,99 +121,374 @@ pub trait ShorterThanOrEqual<T> {}
 
 impl<Long, Short> ShorterThanOrEqual<Long> for Short where Long: LongerThan<Short> {}
 
-// TODO: use macro for this
+// each resolution is longer than those which evenly divide it, such that every period of the
+// longer resolution is made up of whole periods of the shorter resolution
+macro_rules! impl_longer_than {
+    ([$($generics:tt)*] $long:ty => $short:ty $(, $rest:ty)*) => {
+        impl<$($generics)*> LongerThanOrEqual<$short> for $long {}
+        impl<$($generics)*> LongerThan<$short> for $long {}
+        impl_longer_than!([$($generics)*] $long => $($rest),*);
+    };
+    ([$($generics:tt)*] $long:ty =>) => {};
+}
 
 impl LongerThanOrEqual<Minute> for Minute {}
-impl LongerThanOrEqual<Minute> for FiveMinute {}
-impl LongerThanOrEqual<Minute> for HalfHour {}
-impl LongerThanOrEqual<Minute> for Hour {}
-impl LongerThanOrEqual<Minute> for Day {}
-impl<D> LongerThanOrEqual<Minute> for Week<D> where D: StartDay {}
-impl LongerThanOrEqual<Minute> for Quarter {}
-impl LongerThanOrEqual<Minute> for Year {}
-
-impl LongerThan<Minute> for FiveMinute {}
-impl LongerThan<Minute> for HalfHour {}
-impl LongerThan<Minute> for Hour {}
-impl LongerThan<Minute> for Day {}
-impl<D> LongerThan<Minute> for Week<D> where D: StartDay {}
-impl LongerThan<Minute> for Quarter {}
-impl LongerThan<Minute> for Year {}
-
 impl LongerThanOrEqual<FiveMinute> for FiveMinute {}
-impl LongerThanOrEqual<FiveMinute> for HalfHour {}
-impl LongerThanOrEqual<FiveMinute> for Hour {}
-impl LongerThanOrEqual<FiveMinute> for Day {}
-impl<D> LongerThanOrEqual<FiveMinute> for Week<D> where D: StartDay {}
-impl LongerThanOrEqual<FiveMinute> for Quarter {}
-impl LongerThanOrEqual<FiveMinute> for Year {}
-
-impl LongerThan<FiveMinute> for HalfHour {}
-impl LongerThan<FiveMinute> for Hour {}
-impl LongerThan<FiveMinute> for Day {}
-impl<D> LongerThan<FiveMinute> for Week<D> where D: StartDay {}
-impl LongerThan<FiveMinute> for Quarter {}
-impl LongerThan<FiveMinute> for Year {}
-
 impl LongerThanOrEqual<HalfHour> for HalfHour {}
-impl LongerThanOrEqual<HalfHour> for Hour {}
-impl LongerThanOrEqual<HalfHour> for Day {}
-impl<D> LongerThanOrEqual<HalfHour> for Week<D> where D: StartDay {}
-impl LongerThanOrEqual<HalfHour> for Quarter {}
-impl LongerThanOrEqual<HalfHour> for Year {}
-
-impl LongerThan<HalfHour> for Hour {}
-impl LongerThan<HalfHour> for Day {}
-impl<D> LongerThan<HalfHour> for Week<D> where D: StartDay {}
-impl LongerThan<HalfHour> for Quarter {}
-impl LongerThan<HalfHour> for Year {}
+impl LongerThanOrEqual<Hour> for Hour {}
+impl LongerThanOrEqual<Day> for Day {}
+impl<D> LongerThanOrEqual<Week<D>> for Week<D> where D: StartDay {}
+impl LongerThanOrEqual<Month> for Month {}
+impl LongerThanOrEqual<Quarter> for Quarter {}
+impl LongerThanOrEqual<Year> for Year {}
+
+impl_longer_than!([] FiveMinute => Minute);
+impl_longer_than!([] HalfHour => Minute, FiveMinute);
+impl_longer_than!([] Hour => Minute, FiveMinute, HalfHour);
+impl_longer_than!([] Day => Minute, FiveMinute, HalfHour, Hour);
+impl_longer_than!([D: StartDay] Week<D> => Minute, FiveMinute, HalfHour, Hour, Day);
+impl_longer_than!([] Month => Minute, FiveMinute, HalfHour, Hour, Day);
+impl_longer_than!([] Quarter => Minute, FiveMinute, HalfHour, Hour, Day, Month);
+impl_longer_than!([] Year => Minute, FiveMinute, HalfHour, Hour, Day, Month, Quarter);
+
+/// Register a resolution defined outside this crate, such as with `define_resolution`, in the
+/// `LongerThan` and `LongerThanOrEqual` ordering of resolutions, so it can be used with
+/// `DateResolutionExt::rescale`, `TimeResolution::rescale_to` and `Contains`.
+///
+/// Every period of the resolution must be made up of whole periods of each resolution it is
+/// `longer_than`, and similarly for those it is `shorter_than`. This also implements
+/// `LongerThanOrEqual` for the resolution with itself, so should be used once per resolution,
+/// and an ordering between two custom resolutions should only be given for one of them.
+///
+/// ```
+/// use resolution::{Contains, Day, Hour, Minute, Month, TimeResolution};
+///
+/// resolution::define_resolution! {
+///     pub struct TenSecond: SubDateResolution { seconds: 10 }
+/// }
+/// resolution::impl_duration_ordering!(TenSecond: shorter_than(Minute, Hour, Day, Month));
+///
+/// # #[cfg(feature = "alloc")] {
+/// let hour = "2021-01-01 10:00 => 2021-01-01 11:00".parse::<Hour>().unwrap();
+/// assert_eq!(hour.rescale_to::<TenSecond>().len().get(), 360);
+/// assert!(hour.contains(TenSecond::from(hour.start_datetime())));
+/// # }
+/// ```
+#[macro_export]
+macro_rules! impl_duration_ordering {
+    (
+        $ty:ty:
+        $(longer_than($($short:ty),* $(,)?))?
+        $(,)?
+        $(shorter_than($($long:ty),* $(,)?))?
+    ) => {
+        impl $crate::LongerThanOrEqual<$ty> for $ty {}
+        $($(
+            impl $crate::LongerThanOrEqual<$short> for $ty {}
+            impl $crate::LongerThan<$short> for $ty {}
+        )*)?
+        $($(
+            impl $crate::LongerThanOrEqual<$ty> for $long {}
+            impl $crate::LongerThan<$ty> for $long {}
+        )*)?
+    };
+}
+
+/// `Contains` checks whether a single period of the same or a shorter resolution is within this
+/// period, for example `month.contains(day)`.
+pub trait Contains<Other> {
+    fn contains(&self, other: Other) -> bool;
+}
+
+impl<Long, Short> Contains<Short> for Long
+where
+    Long: TimeResolution + LongerThanOrEqual<Short>,
+    Short: TimeResolution,
+{
+    fn contains(&self, other: Short) -> bool {
+        // as the shorter periods evenly divide the longer one, it is enough to check the start
+        let start = other.start_datetime();
+        self.start_datetime() <= start && start < self.succ().start_datetime()
+    }
+}
+
+// `period + n` and `period - n` offset the period by `n` periods, as with `succ_n` and `pred_n`,
+// and `a - b` is the number of periods from `b` to `a`. On nightly, with
+// `RUSTFLAGS="--cfg resolution_nightly"`, `Step` is also implemented so that ranges such as
+// `start..=end` can be iterated
+macro_rules! impl_ops {
+    ([$($generics:tt)*] $ty:ty $(where $($bounds:tt)*)?) => {
+        impl<$($generics)*> core::ops::Add<i64> for $ty $(where $($bounds)*)? {
+            type Output = $ty;
+            fn add(self, rhs: i64) -> $ty {
+                if rhs >= 0 {
+                    self.succ_n(rhs.unsigned_abs())
+                } else {
+                    self.pred_n(rhs.unsigned_abs())
+                }
+            }
+        }
+
+        impl<$($generics)*> core::ops::Sub<i64> for $ty $(where $($bounds)*)? {
+            type Output = $ty;
+            fn sub(self, rhs: i64) -> $ty {
+                if rhs >= 0 {
+                    self.pred_n(rhs.unsigned_abs())
+                } else {
+                    self.succ_n(rhs.unsigned_abs())
+                }
+            }
+        }
+
+        impl<$($generics)*> core::ops::Sub<$ty> for $ty $(where $($bounds)*)? {
+            type Output = i64;
+            /// The signed number of periods from `rhs` to `self`, which is positive when `self`
+            /// is later than `rhs`, so that `rhs + (self - rhs) == self`.
+            ///
+            /// This is the same as `rhs.between(self)`.
+            fn sub(self, rhs: $ty) -> i64 {
+                rhs.between(self)
+            }
+        }
+
+        impl<$($generics)*> core::ops::AddAssign<i64> for $ty $(where $($bounds)*)? {
+            fn add_assign(&mut self, rhs: i64) {
+                *self = *self + rhs;
+            }
+        }
+
+        impl<$($generics)*> core::ops::SubAssign<i64> for $ty $(where $($bounds)*)? {
+            fn sub_assign(&mut self, rhs: i64) {
+                *self = *self - rhs;
+            }
+        }
+
+        #[cfg(resolution_nightly)]
+        impl<$($generics)*> core::iter::Step for $ty $(where $($bounds)*)? {
+            fn steps_between(start: &$ty, end: &$ty) -> (usize, Option<usize>) {
+                match usize::try_from(start.between(*end)) {
+                    Ok(n) => (n, Some(n)),
+                    Err(_) if end > start => (usize::MAX, None),
+                    Err(_) => (0, None),
+                }
+            }
+            fn forward_checked(start: $ty, count: usize) -> Option<$ty> {
+                start.checked_succ_n(u64::try_from(count).ok()?)
+            }
+            fn backward_checked(start: $ty, count: usize) -> Option<$ty> {
+                start.checked_pred_n(u64::try_from(count).ok()?)
+            }
+        }
+    };
+}
+
+impl_ops!([const N: u32] Minutes<N>);
+impl_ops!([] Day);
+impl_ops!([D: StartDay] Week<D>);
+impl_ops!([] Month);
+impl_ops!([] Quarter);
+impl_ops!([] Year);
+impl_ops!([R: TimeResolution, Z: chrono::TimeZone + Copy + fmt::Debug] Zoned<R, Z> where Zoned<R, Z>: TimeResolution);
 
 /// This function is useful for formatting types implementing `Monotonic` when they are stored
 /// in their `i64` form instead of their `TimeResolution` form. Provided you have the `TypeId` handy
 /// you can find out what they were intended to be. This function handeles all the cases implemented
 /// in this library and users can handle others via the function in the `handle_unknown` parameter.
+///
+/// The output is prefixed by the kind of resolution, and for `Week`s this includes the start day,
+/// eg `Week[Monday]:Week starting 2021-12-06`. See `write_erased_resolution` to avoid allocating.
+#[cfg(feature = "alloc")]
 pub fn format_erased_resolution(
     handle_unknown: fn(any::TypeId, i64) -> String,
     tid: any::TypeId,
     val: i64,
 ) -> String {
+    let mut out = String::new();
+    write_erased_resolution(
+        &mut out,
+        |out, tid, val| {
+            out.push_str(&handle_unknown(tid, val));
+            Ok(())
+        },
+        tid,
+        val,
+    )
+    .expect("writing to a String doesn't fail");
+    out
+}
+
+/// As `format_erased_resolution`, but writing to `out` rather than allocating a `String`. Any
+/// resolution not implemented in this library is written by `handle_unknown`.
+pub fn write_erased_resolution<W: fmt::Write>(
+    out: &mut W,
+    handle_unknown: impl FnOnce(&mut W, any::TypeId, i64) -> fmt::Result,
+    tid: any::TypeId,
+    val: i64,
+) -> fmt::Result {
     if tid == any::TypeId::of::<Minute>() {
-        format!("Minute:{}", Minute::from_monotonic(val))
+        write!(out, "Minute:{}", Minute::from_monotonic(val))
     } else if tid == any::TypeId::of::<FiveMinute>() {
-        format!("FiveMinute:{}", FiveMinute::from_monotonic(val))
+        write!(out, "FiveMinute:{}", FiveMinute::from_monotonic(val))
     } else if tid == any::TypeId::of::<HalfHour>() {
-        format!("HalfHour:{}", HalfHour::from_monotonic(val))
+        write!(out, "HalfHour:{}", HalfHour::from_monotonic(val))
     } else if tid == any::TypeId::of::<Hour>() {
-        format!("Hour:{}", Hour::from_monotonic(val))
+        write!(out, "Hour:{}", Hour::from_monotonic(val))
     } else if tid == any::TypeId::of::<Day>() {
-        format!("Day:{}", Day::from_monotonic(val))
+        write!(out, "Day:{}", Day::from_monotonic(val))
     } else if tid == any::TypeId::of::<Week<week::Monday>>() {
-        format!("Week:{}", Week::<week::Monday>::from_monotonic(val))
+        write!(
+            out,
+            "Week[Monday]:{}",
+            Week::<week::Monday>::from_monotonic(val)
+        )
     } else if tid == any::TypeId::of::<Week<week::Tuesday>>() {
-        format!("Week:{}", Week::<week::Tuesday>::from_monotonic(val))
+        write!(
+            out,
+            "Week[Tuesday]:{}",
+            Week::<week::Tuesday>::from_monotonic(val)
+        )
     } else if tid == any::TypeId::of::<Week<week::Wednesday>>() {
-        format!("Week:{}", Week::<week::Wednesday>::from_monotonic(val))
+        write!(
+            out,
+            "Week[Wednesday]:{}",
+            Week::<week::Wednesday>::from_monotonic(val)
+        )
     } else if tid == any::TypeId::of::<Week<week::Thursday>>() {
-        format!("Week:{}", Week::<week::Thursday>::from_monotonic(val))
+        write!(
+            out,
+            "Week[Thursday]:{}",
+            Week::<week::Thursday>::from_monotonic(val)
+        )
     } else if tid == any::TypeId::of::<Week<week::Friday>>() {
-        format!("Week:{}", Week::<week::Friday>::from_monotonic(val))
+        write!(
+            out,
+            "Week[Friday]:{}",
+            Week::<week::Friday>::from_monotonic(val)
+        )
     } else if tid == any::TypeId::of::<Week<week::Saturday>>() {
-        format!("Week:{}", Week::<week::Saturday>::from_monotonic(val))
+        write!(
+            out,
+            "Week[Saturday]:{}",
+            Week::<week::Saturday>::from_monotonic(val)
+        )
     } else if tid == any::TypeId::of::<Week<week::Sunday>>() {
-        format!("Week:{}", Week::<week::Sunday>::from_monotonic(val))
+        write!(
+            out,
+            "Week[Sunday]:{}",
+            Week::<week::Sunday>::from_monotonic(val)
+        )
     } else if tid == any::TypeId::of::<Month>() {
-        format!("Month:{}", Month::from_monotonic(val))
+        write!(out, "Month:{}", Month::from_monotonic(val))
     } else if tid == any::TypeId::of::<Quarter>() {
-        format!("Quarter:{}", Quarter::from_monotonic(val))
+        write!(out, "Quarter:{}", Quarter::from_monotonic(val))
     } else if tid == any::TypeId::of::<Year>() {
-        format!("Year:{}", Year::from_monotonic(val))
+        write!(out, "Year:{}", Year::from_monotonic(val))
     } else {
-        handle_unknown(tid, val)
+        handle_unknown(out, tid, val)
     }
 }
 
+// the `TypeId` and `i64` form of a resolution, or the error from parsing it
+type ParsedErased = core::result::Result<(any::TypeId, i64), Error>;
+
+/// The inverse of `format_erased_resolution`, this parses `value`, as written by `Display`, into
+/// the `TypeId` and `i64` form of the resolution named by `kind`. The `kind` can be either the
+/// prefix written by `format_erased_resolution`, eg `Week[Monday]`, or the `TimeResolution::name`,
+/// eg `Week[StartDay:Monday]`. Any other kind is passed to `handle_unknown`.
+///
+/// The output of `format_erased_resolution` can be split into the `kind` and `value` on the first
+/// `:`.
+pub fn parse_erased_resolution(
+    handle_unknown: fn(&str, &str) -> ParsedErased,
+    kind: &str,
+    value: &str,
+) -> ParsedErased {
+    fn parse<R>(value: &str) -> ParsedErased
+    where
+        R: str::FromStr<Err = Error> + Monotonic + 'static,
+    {
+        Ok((any::TypeId::of::<R>(), value.parse::<R>()?.to_monotonic()))
+    }
+    match kind {
+        "Minute" | "Minutes[Length:1]" => parse::<Minute>(value),
+        "FiveMinute" | "Minutes[Length:5]" => parse::<FiveMinute>(value),
+        "HalfHour" | "Minutes[Length:30]" => parse::<HalfHour>(value),
+        "Hour" | "Minutes[Length:60]" => parse::<Hour>(value),
+        "Day" => parse::<Day>(value),
+        "Week[Monday]" | "Week[StartDay:Monday]" => parse::<Week<week::Monday>>(value),
+        "Week[Tuesday]" | "Week[StartDay:Tuesday]" => parse::<Week<week::Tuesday>>(value),
+        "Week[Wednesday]" | "Week[StartDay:Wednesday]" => parse::<Week<week::Wednesday>>(value),
+        "Week[Thursday]" | "Week[StartDay:Thursday]" => parse::<Week<week::Thursday>>(value),
+        "Week[Friday]" | "Week[StartDay:Friday]" => parse::<Week<week::Friday>>(value),
+        "Week[Saturday]" | "Week[StartDay:Saturday]" => parse::<Week<week::Saturday>>(value),
+        "Week[Sunday]" | "Week[StartDay:Sunday]" => parse::<Week<week::Sunday>>(value),
+        "Month" => parse::<Month>(value),
+        "Quarter" => parse::<Quarter>(value),
+        "Year" => parse::<Year>(value),
+        _ => handle_unknown(kind, value),
+    }
+}
+
+// as per ISO 8601 and chrono, years outside of 0-9999 are written with a sign
+pub(crate) fn write_iso_year(f: &mut fmt::Formatter<'_>, year: i64) -> fmt::Result {
+    if (0..=9999).contains(&year) {
+        write!(f, "{:04}", year)
+    } else {
+        write!(f, "{:+05}", year)
+    }
+}
+
+/// Which periods to include when counting the periods of a resolution over a date interval
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CountPolicy {
+    /// Count every period which has at least one date within the interval
+    Intersecting,
+    /// Count only the periods which have all of their dates within the interval
+    Contained,
+}
+
+/// Count the periods of `R` over the dates from `start` to `end` (both inclusive), without
+/// creating the periods themselves. This returns zero if `end` is before `start`.
+pub fn count_periods<R>(start: NaiveDate, end: NaiveDate, policy: CountPolicy) -> u64
+where
+    R: DateResolution<Params = ()>,
+{
+    if end < start {
+        return 0;
+    }
+    let first = R::from_date(start, ());
+    let last = R::from_date(end, ());
+    let intersecting = 1 + u64::try_from(first.between(last)).expect("end is after start");
+    match policy {
+        CountPolicy::Intersecting => intersecting,
+        CountPolicy::Contained => {
+            let partial_first = u64::from(first.start() < start);
+            let partial_last = u64::from(last.end() > end);
+            intersecting.saturating_sub(partial_first + partial_last)
+        }
+    }
+}
+
+/// The number of whole periods of `R` that fit within `duration`. This is `None` for resolutions
+/// that don't have a fixed duration (and for negative durations).
+pub fn periods_in_duration<R>(duration: chrono::Duration) -> Option<u64>
+where
+    R: TimeResolution,
+{
+    let period = R::fixed_duration()?;
+    if duration < chrono::Duration::zero() {
+        return None;
+    }
+    u64::try_from(duration.num_milliseconds() / period.num_milliseconds()).ok()
+}
+
+/// The variants which record the input as a `String` are only available with the `alloc` feature.
+/// Without it, parsing instead returns `InvalidInput`, `ParseIntAt` and `ParseDateAt`, which keep
+/// the same details other than the input and message.
 #[derive(Debug)]
+#[non_exhaustive]
 pub enum Error {
+    #[cfg(feature = "alloc")]
     GotNonMatchingNewData {
         point: String,
         old: String,
@@ -153,6 +496,7 @@ pub enum Error {
     },
     ParseInt(num::ParseIntError),
     ParseDate(chrono::ParseError),
+    #[cfg(feature = "alloc")]
     ParseCustom {
         ty_name: &'static str,
         input: String,
@@ -168,14 +512,173 @@ pub enum Error {
         actual: usize,
         format: &'static str,
     },
-    ParseIntDetailed(ParseIntError, String),
+    #[cfg(feature = "alloc")]
+    ParseIntDetailed {
+        source: num::ParseIntError,
+        // the name of the field which failed to parse, and its byte offset within the input
+        field: &'static str,
+        position: usize,
+        input: String,
+        format: &'static str,
+    },
+    #[cfg(feature = "alloc")]
     ParseDateInternal {
         message: String,
+        // the byte offset within the input of the first field making up the invalid date or time
+        position: usize,
         input: String,
         format: &'static str,
     },
+    RangeTooLong {
+        max: u64,
+        actual: u64,
+    },
+    NonContiguous {
+        expected: DateTime<Utc>,
+        actual: DateTime<Utc>,
+    },
+    ReversedBounds {
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    },
+    InvalidPeriodNumber {
+        number: u32,
+        periods: u32,
+    },
+    InvalidQuarterNumber {
+        number: u8,
+    },
+    YearOutOfRange {
+        year: i64,
+    },
+    /// `ParseCustom` without the input, when the `alloc` feature is disabled.
+    InvalidInput {
+        ty_name: &'static str,
+    },
+    /// `ParseIntDetailed` without the input, when the `alloc` feature is disabled.
+    ParseIntAt {
+        source: num::ParseIntError,
+        field: &'static str,
+        position: usize,
+        format: &'static str,
+    },
+    /// `ParseDateInternal` without the input or message, when the `alloc` feature is disabled.
+    ParseDateAt {
+        position: usize,
+        format: &'static str,
+    },
 }
 
+impl Error {
+    // the constructors for errors from parsing, which only record the input and message when
+    // they can be allocated
+    #[cfg_attr(not(feature = "alloc"), allow(unused_variables))]
+    pub(crate) fn custom(ty_name: &'static str, input: fmt::Arguments) -> Error {
+        #[cfg(feature = "alloc")]
+        return Error::ParseCustom {
+            ty_name,
+            input: alloc::fmt::format(input),
+        };
+        #[cfg(not(feature = "alloc"))]
+        Error::InvalidInput { ty_name }
+    }
+
+    #[cfg_attr(not(feature = "alloc"), allow(unused_variables))]
+    pub(crate) fn parse_int(
+        source: num::ParseIntError,
+        field: &'static str,
+        position: usize,
+        input: &str,
+        format: &'static str,
+    ) -> Error {
+        #[cfg(feature = "alloc")]
+        return Error::ParseIntDetailed {
+            source,
+            field,
+            position,
+            input: input.into(),
+            format,
+        };
+        #[cfg(not(feature = "alloc"))]
+        Error::ParseIntAt {
+            source,
+            field,
+            position,
+            format,
+        }
+    }
+
+    #[cfg_attr(not(feature = "alloc"), allow(unused_variables))]
+    pub(crate) fn parse_date(
+        message: fmt::Arguments,
+        position: usize,
+        input: &str,
+        format: &'static str,
+    ) -> Error {
+        #[cfg(feature = "alloc")]
+        return Error::ParseDateInternal {
+            message: alloc::fmt::format(message),
+            position,
+            input: input.into(),
+            format,
+        };
+        #[cfg(not(feature = "alloc"))]
+        Error::ParseDateAt { position, format }
+    }
+
+    // make the position of an error from parsing part of an input relative to the whole input
+    #[cfg_attr(not(feature = "alloc"), allow(unused_variables))]
+    pub(crate) fn within(self, offset: usize, whole: &str) -> Error {
+        match self {
+            Error::ParseIntAt {
+                source,
+                field,
+                position,
+                format,
+            } => Error::ParseIntAt {
+                source,
+                field,
+                position: position + offset,
+                format,
+            },
+            Error::ParseDateAt { position, format } => Error::ParseDateAt {
+                position: position + offset,
+                format,
+            },
+            #[cfg(feature = "alloc")]
+            Error::ParseIntDetailed {
+                source,
+                field,
+                position,
+                format,
+                ..
+            } => Error::ParseIntDetailed {
+                source,
+                field,
+                position: position + offset,
+                input: whole.into(),
+                format,
+            },
+            #[cfg(feature = "alloc")]
+            Error::ParseDateInternal {
+                message,
+                position,
+                format,
+                ..
+            } => Error::ParseDateInternal {
+                message,
+                position: position + offset,
+                input: whole.into(),
+                format,
+            },
+            other => other,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
+
 impl From<num::ParseIntError> for Error {
     fn from(e: num::ParseIntError) -> Error {
         Error::ParseInt(e)
@@ -191,12 +694,14 @@ impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         use Error::*;
         match self {
+            #[cfg(feature = "alloc")]
             GotNonMatchingNewData { point, old, new } => write!(
                 f,
                 "Got new data for {point}: {new} different from data already in the cache {old}"
             ),
             ParseInt(e) => write!(f, "Error parsing int: {e}"),
             ParseDate(e) => write!(f, "Error parsing date/time: {e}"),
+            #[cfg(feature = "alloc")]
             ParseCustom { ty_name, input } => {
                 write!(f, "Error parsing {ty_name} from input: {input}")
             }
@@ -220,19 +725,66 @@ impl fmt::Display for Error {
                 f,
                 "Unexpected input length for format {format}, got {actual} but needed {required}"
             ),
-            ParseIntDetailed(e, detail) => {
-                write!(f, "Error parsing {detail} as integer: {e}")
-            }
+            #[cfg(feature = "alloc")]
+            ParseIntDetailed {
+                source,
+                field,
+                position,
+                input,
+                format,
+            } => write!(
+                f,
+                "Error parsing {field} field at position {position} of {input} as integer using format {format}: {source}"
+            ),
+            #[cfg(feature = "alloc")]
             ParseDateInternal {
                 message,
+                position,
                 input,
                 format,
             } => {
                 write!(
                     f,
-                    "Error parsing {input} as date due to {message} using format {format}"
+                    "Error parsing {input} as date due to {message} at position {position} using format {format}"
                 )
             }
+            RangeTooLong { max, actual } => write!(
+                f,
+                "Time range has {actual} periods which is more than the maximum of {max}"
+            ),
+            NonContiguous { expected, actual } => write!(
+                f,
+                "Expected the period starting at {expected} but got the period starting at {actual}"
+            ),
+            ReversedBounds { start, end } => write!(
+                f,
+                "Time range cannot start with the period at {start} as it is after the end at {end}"
+            ),
+            InvalidPeriodNumber { number, periods } => write!(
+                f,
+                "Period number {number} is not between 1 and the {periods} periods in a day"
+            ),
+            InvalidQuarterNumber { number } => {
+                write!(f, "Quarter number {number} is not between 1 and 4")
+            }
+            YearOutOfRange { year } => write!(
+                f,
+                "Year {year} is outside the supported range of {MIN_YEAR} to {MAX_YEAR}"
+            ),
+            InvalidInput { ty_name } => write!(f, "Error parsing {ty_name}"),
+            ParseIntAt {
+                source,
+                field,
+                position,
+                format,
+            } => write!(
+                f,
+                "Error parsing {field} field at position {position} as integer using format {format}: {source}"
+            ),
+            ParseDateAt { position, format } => write!(
+                f,
+                "Error parsing date at position {position} using format {format}"
+            ),
         }
     }
 }
@@ -240,13 +792,24 @@ impl fmt::Display for Error {
 #[cfg(feature = "std")]
 pub type Result<T> = std::result::Result<T, Error>;
 
+/// An alias for `FromStr<Err = Error>`, implemented for every type which parses with the crate's
+/// `Error`. It is a supertrait of `TimeResolution` so that generic code can parse periods
+/// without naming the bound itself.
+pub trait ParseResolution: str::FromStr<Err = Error> {}
+
+impl<T: str::FromStr<Err = Error>> ParseResolution for T {}
+
 /// `TimeResolution` should be used for contigious series of periods in time
 ///
 /// This makes sense for the time part of a discrete timeseries, with observations
 /// occurring at regular times. Some examples are:
 /// * A cash-flow report aggregated to days or months
 /// * Dispatch periods in the Australian Electricity Market (and similar concepts in other energy markets)
-pub trait TimeResolution: Copy + Eq + Ord + Monotonic {
+///
+/// Every period can be written with `Display` and parsed back with `FromStr`, such that
+/// `period.to_string().parse()` is always `Ok(period)`. Some types also accept other forms when
+/// parsing, such as the ISO 8601 forms written by the alternate `{:#}`.
+pub trait TimeResolution: Copy + Eq + Ord + Monotonic + fmt::Display + ParseResolution {
     fn succ(&self) -> Self {
         self.succ_n(1)
     }
@@ -258,13 +821,128 @@ pub trait TimeResolution: Copy + Eq + Ord + Monotonic {
     // the default impls are probably inefficient
     // makes sense to require just the n
     // and give the 1 for free
+    /// The period `n` periods after this one.
+    ///
+    /// Panics if the result is out of range, see `checked_succ_n`.
     fn succ_n(&self, n: u64) -> Self;
 
+    /// The period `n` periods before this one.
+    ///
+    /// Panics if the result is out of range, see `checked_pred_n`.
     fn pred_n(&self, n: u64) -> Self;
 
+    /// The period `n` periods after this one, or `None` if the result is out of range.
+    ///
+    /// The default implementation checks that the result can be represented by `to_monotonic`,
+    /// so should be overriden where this isn't enough to avoid `succ_n` panicking.
+    fn checked_succ_n(&self, n: u64) -> Option<Self> {
+        self.to_monotonic().checked_add_unsigned(n)?;
+        Some(self.succ_n(n))
+    }
+
+    /// The period `n` periods before this one, or `None` if the result is out of range.
+    ///
+    /// The default implementation checks that the result can be represented by `to_monotonic`,
+    /// so should be overriden where this isn't enough to avoid `pred_n` panicking.
+    fn checked_pred_n(&self, n: u64) -> Option<Self> {
+        self.to_monotonic().checked_sub_unsigned(n)?;
+        Some(self.pred_n(n))
+    }
+
     fn start_datetime(&self) -> DateTime<Utc>;
 
-    fn name(&self) -> String;
+    /// Write the name of the resolution, eg `Day` or `Week[StartDay:Monday]`, without allocating.
+    ///
+    /// This defaults to the type name from `core::any::type_name`, so resolutions which override
+    /// `name` should also override this to match.
+    fn write_name(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        out.write_str(any::type_name::<Self>())
+    }
+
+    /// The name of the resolution, as written by `write_name`. This is borrowed for all but
+    /// unusual `Minutes` lengths and `Zoned` periods, so is cheap to use as a label or key.
+    #[cfg(feature = "alloc")]
+    fn name(&self) -> borrow::Cow<'static, str> {
+        let mut name = String::new();
+        self.write_name(&mut name)
+            .expect("writing to a String doesn't fail");
+        name.into()
+    }
+
+    /// The `Quarter` containing the start of this period, in UTC.
+    fn quarter(&self) -> Quarter {
+        self.start_datetime().into()
+    }
+
+    /// The `Week` containing the start of this period, in UTC.
+    fn week<D: StartDay>(&self) -> Week<D> {
+        self.start_datetime().into()
+    }
+
+    /// The period of `Out` containing the start of this period, using `params` for resolutions
+    /// such as `Zoned` which need them. For a `Zoned` target, this is found from the local date
+    /// in its zone rather than the UTC date.
+    fn convert_with<Out: DateResolution>(&self, params: Out::Params) -> Out {
+        Out::from_utc_datetime(self.start_datetime(), params)
+    }
+
+    /// The number of periods of `Out` within this period, for example `month.count_in::<Day>()`,
+    /// without creating the periods themselves.
+    fn count_in<Out>(&self) -> u64
+    where
+        Out: TimeResolution + From<DateTime<Utc>>,
+        Self: LongerThanOrEqual<Out>,
+    {
+        self.rescale_to::<Out>().len().get()
+    }
+
+    /// The range of periods of `Out` which make up this period, for example
+    /// `day.rescale_to::<FiveMinute>()` or `year.rescale_to::<Month>()`.
+    ///
+    /// Unlike `DateResolutionExt::rescale` and `DateResolutionExt::to_sub_date_resolution`, this
+    /// works for both date and sub-date targets, and from sub-date periods, but requires that
+    /// `Out` can be created from a `DateTime<Utc>`.
+    fn rescale_to<Out>(&self) -> range::TimeRange<Out>
+    where
+        Out: TimeResolution + From<DateTime<Utc>>,
+        Self: LongerThanOrEqual<Out>,
+    {
+        range::TimeRange::from_ordered_bounds(
+            Out::from(self.start_datetime()),
+            Out::from(self.succ().start_datetime()).pred(),
+        )
+    }
+
+    /// Compare the start of this period with the start of `other`, which can be of any
+    /// resolution.
+    fn cmp_start<Other: TimeResolution>(&self, other: &Other) -> cmp::Ordering {
+        self.start_datetime().cmp(&other.start_datetime())
+    }
+
+    /// Whether this period starts before `other`, which can be of any resolution.
+    fn is_before<Other: TimeResolution>(&self, other: &Other) -> bool {
+        self.cmp_start(other).is_lt()
+    }
+
+    /// Whether this period starts after `other`, which can be of any resolution.
+    fn is_after<Other: TimeResolution>(&self, other: &Other) -> bool {
+        self.cmp_start(other).is_gt()
+    }
+
+    /// The Unix timestamps, in seconds, of the start of this period (inclusive) and the start of
+    /// the next period (exclusive), for example to filter on a timestamp column in a database.
+    fn timestamp_range(&self) -> (i64, i64) {
+        (
+            self.start_datetime().timestamp(),
+            self.succ().start_datetime().timestamp(),
+        )
+    }
+
+    /// The duration of every period of this resolution, or `None` if the duration varies
+    /// between periods (for example with `Month`).
+    fn fixed_duration() -> Option<chrono::Duration> {
+        None
+    }
 }
 
 /// `Monotonic` is used to enable multiple different resolutions to be stored together
@@ -272,6 +950,10 @@ pub trait TimeResolution: Copy + Eq + Ord + Monotonic {
 /// It is named monotonic as it is intended to provide a monotonic (order preserving) function
 /// from a given implementor of `TimeResolution`, to allow converting backwards and forwards
 /// between the values of the `TimeResolution` implementor and `i64`s
+///
+/// Each implementor counts from its own epoch, and periods before that epoch (for example before
+/// 1970 for `Minutes`, or before year 0 for `Day`) have negative values. These are supported in the
+/// same way as any other period, including for parsing and display.
 pub trait Monotonic {
     // we choose i64 rather than u64
     // as the behaviour on subtraction is nicer!
@@ -281,12 +963,66 @@ pub trait Monotonic {
 
 pub trait FromMonotonic: Monotonic {
     fn from_monotonic(idx: i64) -> Self;
+
+    /// The period `idx` periods after `epoch`, for converting monotonic values which were
+    /// persisted relative to a different epoch, such as the start of a dataset.
+    fn from_monotonic_since(idx: i64, epoch: &Self) -> Self
+    where
+        Self: Sized,
+    {
+        Self::from_monotonic(epoch.to_monotonic() + idx)
+    }
+
+    /// The number of periods from `epoch` to this period, the inverse of `from_monotonic_since`.
+    fn to_monotonic_since(&self, epoch: &Self) -> i64 {
+        self.to_monotonic() - epoch.to_monotonic()
+    }
+}
+
+// the first and last years supported by `chrono`
+pub(crate) const MIN_YEAR: i64 = -262_143;
+pub(crate) const MAX_YEAR: i64 = 262_142;
+// the first and last dates supported by `chrono`, as the number of days since 0000-01-01
+pub(crate) const MIN_DAY: i64 = -95_745_764;
+pub(crate) const MAX_DAY: i64 = 95_745_764;
+// the number of days from 0000-01-01 to the Unix epoch, 1970-01-01
+pub(crate) const EPOCH_DAY: i64 = 719_528;
+
+/// `Bounded` gives the first and last periods of a resolution which are within the dates
+/// supported by `chrono`, so can be used as sentinel values, or to clamp periods, without methods
+/// such as `start` panicking.
+///
+/// `MAX` is the last period where the start of the following period is also supported, so that
+/// the end of `MAX` can be found.
+pub trait Bounded: FromMonotonic + Sized {
+    const MIN: Self;
+    const MAX: Self;
+
+    /// As `from_monotonic`, but `idx` is clamped so the result is between `MIN` and `MAX`.
+    fn from_monotonic_saturating(idx: i64) -> Self {
+        Self::from_monotonic(idx.clamp(Self::MIN.to_monotonic(), Self::MAX.to_monotonic()))
+    }
+
+    /// As `from_monotonic`, but `None` if `idx` is not between `MIN` and `MAX`, for example when
+    /// reading values from untrusted input.
+    fn from_monotonic_checked(idx: i64) -> Option<Self> {
+        (Self::MIN.to_monotonic()..=Self::MAX.to_monotonic())
+            .contains(&idx)
+            .then(|| Self::from_monotonic(idx))
+    }
 }
 
 /// `SubDateResolution` should only be implemented for periods of strictly less than one day in length
 pub trait SubDateResolution: TimeResolution {
     type Params: Copy;
 
+    /// The number of periods in each day, for example `48` for `Minutes<30>`.
+    const PERIODS_PER_DAY: u32;
+
+    /// The duration of every period. Unlike `TimeResolution::fixed_duration` this is always
+    /// known, as every sub-date resolution evenly divides a day.
+    fn exact_duration() -> chrono::Duration;
+
     fn params(&self) -> Self::Params;
 
     fn occurs_on_date(&self) -> chrono::NaiveDate;
@@ -299,6 +1035,45 @@ pub trait SubDateResolution: TimeResolution {
     fn last_on_day(day: chrono::NaiveDate, params: Self::Params) -> Self {
         Self::first_on_day(day + chrono::Duration::days(1), params).pred()
     }
+
+    /// The period containing the current time according to `clock`.
+    fn current_in<C: Clock>(clock: C, params: Self::Params) -> Self {
+        Self::from_utc_datetime(clock.now(), params)
+    }
+
+    /// Whether `datetime` is within this period, including the start of the period but not the
+    /// start of the next period.
+    fn contains_datetime(&self, datetime: DateTime<Utc>) -> bool {
+        self.start_datetime() <= datetime && datetime < self.succ().start_datetime()
+    }
+
+    /// The period containing the Unix timestamp `secs`, or `None` if it is out of range.
+    fn from_timestamp(secs: i64, params: Self::Params) -> Option<Self> {
+        Some(Self::from_utc_datetime(
+            DateTime::from_timestamp(secs, 0)?,
+            params,
+        ))
+    }
+
+    /// The period containing the Unix timestamp `millis`, or `None` if it is out of range.
+    fn from_timestamp_millis(millis: i64, params: Self::Params) -> Option<Self> {
+        Some(Self::from_utc_datetime(
+            DateTime::from_timestamp_millis(millis)?,
+            params,
+        ))
+    }
+
+    /// The period containing the Unix timestamp `nanos`. Every `i64` is in range.
+    fn from_timestamp_nanos(nanos: i64, params: Self::Params) -> Self {
+        Self::from_utc_datetime(DateTime::from_timestamp_nanos(nanos), params)
+    }
+
+    /// Parse an RFC 3339 timestamp with any offset, eg `2021-01-01T10:07:30+10:00`, into the
+    /// period containing it, truncating any time after the start of the period.
+    fn parse_rfc3339(input: &str, params: Self::Params) -> core::result::Result<Self, Error> {
+        let datetime = DateTime::parse_from_rfc3339(input)?;
+        Ok(Self::from_utc_datetime(datetime.to_utc(), params))
+    }
 }
 
 /// `DateResolution` should only be implemented for periods of one or more days in length
@@ -309,6 +1084,12 @@ pub trait DateResolution: TimeResolution {
 
     fn from_date(date: NaiveDate, params: Self::Params) -> Self;
 
+    /// The period containing `datetime`. By default this is the period containing its UTC date,
+    /// but resolutions which are in a time zone, such as `Zoned`, use the local date in that zone.
+    fn from_utc_datetime(datetime: DateTime<Utc>, params: Self::Params) -> Self {
+        Self::from_date(datetime.date_naive(), params)
+    }
+
     fn start(&self) -> chrono::NaiveDate;
 }
 
@@ -323,19 +1104,60 @@ pub trait DateResolutionExt: DateResolution {
         self.start().format(fmt)
     }
 
+    /// The period containing the current UTC date according to `clock`. For the current period
+    /// in another time zone, see `Zoned::current_in`.
+    fn current_in<C: Clock>(clock: C, params: Self::Params) -> Self {
+        Self::from_date(clock.now().date_naive(), params)
+    }
+
+    /// The period containing the UTC date of the Unix timestamp `secs`, or `None` if it is out of
+    /// range.
+    fn from_timestamp(secs: i64, params: Self::Params) -> Option<Self> {
+        Some(Self::from_date(
+            DateTime::from_timestamp(secs, 0)?.date_naive(),
+            params,
+        ))
+    }
+
+    /// The period containing the UTC date of the Unix timestamp `millis`, or `None` if it is out
+    /// of range.
+    fn from_timestamp_millis(millis: i64, params: Self::Params) -> Option<Self> {
+        Some(Self::from_date(
+            DateTime::from_timestamp_millis(millis)?.date_naive(),
+            params,
+        ))
+    }
+
+    /// The period containing the UTC date of the Unix timestamp `nanos`. Every `i64` is in range.
+    fn from_timestamp_nanos(nanos: i64, params: Self::Params) -> Self {
+        Self::from_date(DateTime::from_timestamp_nanos(nanos).date_naive(), params)
+    }
+
     fn end(&self) -> chrono::NaiveDate {
         self.succ().start() - chrono::Duration::days(1)
     }
 
+    /// Whether `date` is one of the dates of this period.
+    fn contains_date(&self, date: NaiveDate) -> bool {
+        self.start() <= date && date <= self.end()
+    }
+
     fn num_days(&self) -> i64 {
         (self.end() - self.start()).num_days() + 1
     }
 
+    /// The number of periods of `S` within this period, without creating the periods themselves.
+    /// For example `year.num_sub_periods::<FiveMinute>()` is `366 * 288` in a leap year.
+    fn num_sub_periods<S: SubDateResolution>(&self) -> u64 {
+        u64::try_from(self.num_days()).expect("Periods have at least one day")
+            * u64::from(S::PERIODS_PER_DAY)
+    }
+
     fn to_sub_date_resolution<R>(&self) -> range::TimeRange<R>
     where
         R: SubDateResolution<Params = Self::Params>,
     {
-        range::TimeRange::from_bounds(
+        range::TimeRange::from_ordered_bounds(
             R::first_on_day(self.start(), self.params()),
             R::last_on_day(self.end(), self.params()),
         )
@@ -346,7 +1168,7 @@ pub trait DateResolutionExt: DateResolution {
         Out: DateResolution<Params = Self::Params>,
         Self: LongerThan<Out>,
     {
-        range::TimeRange::from_bounds(
+        range::TimeRange::from_ordered_bounds(
             Out::from_date(self.start(), self.params()),
             Out::from_date(self.end(), self.params()),
         )
@@ -354,3 +1176,1059 @@ pub trait DateResolutionExt: DateResolution {
 }
 
 impl<T> DateResolutionExt for T where T: DateResolution {}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+    use alloc::format;
+
+    #[test]
+    fn test_name() {
+        fn borrowed<R: TimeResolution>(period: R, name: &str) {
+            assert!(matches!(period.name(), borrow::Cow::Borrowed(n) if n == name));
+        }
+        borrowed(Day::from_monotonic(0), "Day");
+        borrowed(Month::from_monotonic(0), "Month");
+        borrowed(Week::<Sunday>::from_monotonic(0), "Week[StartDay:Sunday]");
+        borrowed(Hour::from_monotonic(0), "Minutes[Length:60]");
+        borrowed(FiveMinute::from_monotonic(0), "Minutes[Length:5]");
+        assert_eq!(
+            Minutes::<45>::from_monotonic(0).name(),
+            "Minutes[Length:45]"
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        use alloc::string::ToString;
+
+        fn round_trip<R: TimeResolution + fmt::Debug>(period: R) {
+            assert_eq!(period.to_string().parse::<R>().unwrap(), period);
+        }
+        fn bounds<R: TimeResolution + Bounded + fmt::Debug>() {
+            round_trip(R::MIN);
+            round_trip(R::MAX);
+            round_trip(R::from_monotonic(0));
+            round_trip(R::from_monotonic(-1));
+        }
+        bounds::<Day>();
+        bounds::<Week<Monday>>();
+        bounds::<Week<Sunday>>();
+        bounds::<Month>();
+        bounds::<Quarter>();
+        bounds::<Year>();
+        bounds::<Minute>();
+        bounds::<FiveMinute>();
+        bounds::<Minutes<45>>();
+
+        let datetime = "2021-06-30T23:30:00+10:00"
+            .parse::<DateTime<chrono::FixedOffset>>()
+            .unwrap();
+        let fixed = Zoned::<HalfHour, _>::from(datetime);
+        assert_eq!(
+            fixed.to_string(),
+            "2021-06-30 23:30 => 2021-07-01 00:00 +10:00"
+        );
+        round_trip(fixed);
+
+        type Aest = FixedEast<{ 10 * 60 * 60 }>;
+        let aest = Zoned::<Day, Aest>::from_date(
+            NaiveDate::from_ymd_opt(2021, 7, 1).unwrap(),
+            Aest::new(),
+        );
+        assert_eq!(aest.to_string(), "2021-07-01 FixedEast[+10:00]");
+        round_trip(aest);
+        round_trip(Zoned::<Hour, Utc>::from_monotonic(1000));
+
+        assert!("2021-07-01 FixedEast[+11:00]"
+            .parse::<Zoned<Day, Aest>>()
+            .is_err());
+    }
+
+    #[cfg(feature = "tz")]
+    #[test]
+    fn test_round_trip_tz() {
+        use alloc::string::ToString;
+
+        // 02:00 to 03:00 is repeated when daylight saving ends in Sydney
+        let first = Zoned::<Hour, chrono_tz::Tz>::from_utc_in_tz(
+            "2021-04-03T15:00:00Z".parse().unwrap(),
+            "Australia/Sydney",
+        )
+        .unwrap();
+        let second = first.succ();
+        assert_eq!(
+            first.to_string(),
+            "2021-04-04 02:00 => 2021-04-04 03:00 +11:00 Australia/Sydney"
+        );
+        assert_eq!(
+            second.to_string(),
+            "2021-04-04 02:00 => 2021-04-04 03:00 +10:00 Australia/Sydney"
+        );
+        assert_eq!(
+            first
+                .to_string()
+                .parse::<Zoned<Hour, chrono_tz::Tz>>()
+                .unwrap(),
+            first
+        );
+        assert_eq!(
+            second
+                .to_string()
+                .parse::<Zoned<Hour, chrono_tz::Tz>>()
+                .unwrap(),
+            second
+        );
+
+        assert!(
+            "2021-04-04 05:00 => 2021-04-04 06:00 +11:00 Australia/Sydney"
+                .parse::<Zoned<Hour, chrono_tz::Tz>>()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_write_name() {
+        let mut name = String::new();
+        Minutes::<45>::from_monotonic(0)
+            .write_name(&mut name)
+            .unwrap();
+        assert_eq!(name, "Minutes[Length:45]");
+
+        let mut name = String::new();
+        Week::<Monday>::from_monotonic(0)
+            .write_name(&mut name)
+            .unwrap();
+        assert_eq!(name, "Week[StartDay:Monday]");
+    }
+
+    #[test]
+    fn test_write_erased_resolution() {
+        use fmt::Write;
+
+        // a fixed capacity buffer, to check nothing is allocated
+        struct Buf {
+            bytes: [u8; 64],
+            len: usize,
+        }
+        impl fmt::Write for Buf {
+            fn write_str(&mut self, s: &str) -> fmt::Result {
+                let end = self.len + s.len();
+                self.bytes
+                    .get_mut(self.len..end)
+                    .ok_or(fmt::Error)?
+                    .copy_from_slice(s.as_bytes());
+                self.len = end;
+                Ok(())
+            }
+        }
+        impl Buf {
+            fn as_str(&self) -> &str {
+                str::from_utf8(&self.bytes[..self.len]).unwrap()
+            }
+        }
+
+        let mut buf = Buf {
+            bytes: [0; 64],
+            len: 0,
+        };
+        let day = "2021-12-06".parse::<Day>().unwrap();
+        write_erased_resolution(
+            &mut buf,
+            |_, _, _| unreachable!(),
+            any::TypeId::of::<Day>(),
+            day.to_monotonic(),
+        )
+        .unwrap();
+        assert_eq!(buf.as_str(), "Day:2021-12-06");
+
+        let mut buf = Buf {
+            bytes: [0; 64],
+            len: 0,
+        };
+        write_erased_resolution(
+            &mut buf,
+            |out, _, val| write!(out, "Unknown:{val}"),
+            any::TypeId::of::<u8>(),
+            5,
+        )
+        .unwrap();
+        assert_eq!(buf.as_str(), "Unknown:5");
+
+        let mut out = String::new();
+        write_erased_resolution(
+            &mut out,
+            |_, _, _| unreachable!(),
+            any::TypeId::of::<Week<Sunday>>(),
+            0,
+        )
+        .unwrap();
+        assert_eq!(out, "Week[Sunday]:Week starting 2021-01-10");
+    }
+
+    #[test]
+    fn test_parse_erased_resolution() {
+        fn unknown(kind: &str, _: &str) -> core::result::Result<(any::TypeId, i64), Error> {
+            Err(Error::ParseCustom {
+                ty_name: "resolution",
+                input: kind.into(),
+            })
+        }
+        fn no_unknown(_: any::TypeId, _: i64) -> String {
+            unreachable!()
+        }
+
+        let week = "Week starting 2021-12-06".parse::<Week<Monday>>().unwrap();
+        assert_eq!(
+            parse_erased_resolution(unknown, "Week[StartDay:Monday]", "Week starting 2021-12-06")
+                .unwrap(),
+            (any::TypeId::of::<Week<Monday>>(), week.to_monotonic())
+        );
+
+        // round trips the output of `format_erased_resolution`
+        let hour = "2021-12-06 10:00 => 2021-12-06 11:00"
+            .parse::<Hour>()
+            .unwrap();
+        let values = [
+            (any::TypeId::of::<Week<Monday>>(), week.to_monotonic()),
+            (any::TypeId::of::<Week<Sunday>>(), week.to_monotonic()),
+            (any::TypeId::of::<Hour>(), hour.to_monotonic()),
+            (any::TypeId::of::<Minute>(), hour.to_monotonic()),
+            (any::TypeId::of::<Day>(), -5),
+            (any::TypeId::of::<Month>(), 24_000),
+            (any::TypeId::of::<Quarter>(), 8_000),
+            (any::TypeId::of::<Year>(), 2021),
+        ];
+        for (tid, val) in values {
+            let formatted = format_erased_resolution(no_unknown, tid, val);
+            let (kind, value) = formatted.split_once(':').unwrap();
+            assert_eq!(
+                parse_erased_resolution(unknown, kind, value).unwrap(),
+                (tid, val),
+                "{formatted}"
+            );
+        }
+
+        assert_eq!(
+            parse_erased_resolution(unknown, &hour.name(), &format!("{hour}")).unwrap(),
+            (any::TypeId::of::<Hour>(), hour.to_monotonic())
+        );
+        assert!(matches!(
+            parse_erased_resolution(unknown, "Fortnight", "2021-12-06"),
+            Err(Error::ParseCustom { input, .. }) if input == "Fortnight"
+        ));
+        assert!(parse_erased_resolution(unknown, "Day", "Dec-2021").is_err());
+    }
+
+    #[test]
+    fn test_format_erased_resolution() {
+        fn unknown(_: any::TypeId, val: i64) -> String {
+            format!("Unknown:{val}")
+        }
+
+        let week = "Week starting 2021-12-06".parse::<Week<Monday>>().unwrap();
+        assert_eq!(
+            format_erased_resolution(
+                unknown,
+                any::TypeId::of::<Week<Monday>>(),
+                week.to_monotonic()
+            ),
+            "Week[Monday]:Week starting 2021-12-06"
+        );
+
+        let week = "Week starting 2021-12-05".parse::<Week<Sunday>>().unwrap();
+        assert_eq!(
+            format_erased_resolution(
+                unknown,
+                any::TypeId::of::<Week<Sunday>>(),
+                week.to_monotonic()
+            ),
+            "Week[Sunday]:Week starting 2021-12-05"
+        );
+
+        let day = "2021-12-06".parse::<Day>().unwrap();
+        assert_eq!(
+            format_erased_resolution(unknown, any::TypeId::of::<Day>(), day.to_monotonic()),
+            "Day:2021-12-06"
+        );
+        assert_eq!(
+            format_erased_resolution(unknown, any::TypeId::of::<u8>(), 5),
+            "Unknown:5"
+        );
+    }
+
+    #[test]
+    fn test_count_periods() {
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+
+        // 2021-12-06 is a Monday
+        let (start, end) = (date(2021, 12, 6), date(2021, 12, 26));
+        assert_eq!(
+            count_periods::<Week<Monday>>(start, end, CountPolicy::Intersecting),
+            3
+        );
+        assert_eq!(
+            count_periods::<Week<Monday>>(start, end, CountPolicy::Contained),
+            3
+        );
+        assert_eq!(
+            count_periods::<Week<Sunday>>(start, end, CountPolicy::Intersecting),
+            4
+        );
+        assert_eq!(
+            count_periods::<Week<Sunday>>(start, end, CountPolicy::Contained),
+            2
+        );
+        assert_eq!(count_periods::<Day>(start, end, CountPolicy::Contained), 21);
+        assert_eq!(
+            count_periods::<Month>(start, end, CountPolicy::Intersecting),
+            1
+        );
+        assert_eq!(
+            count_periods::<Month>(start, end, CountPolicy::Contained),
+            0
+        );
+
+        let (start, end) = (date(1900, 1, 1), date(2099, 12, 31));
+        assert_eq!(
+            count_periods::<Year>(start, end, CountPolicy::Contained),
+            200
+        );
+        assert_eq!(
+            count_periods::<Quarter>(start, end, CountPolicy::Contained),
+            800
+        );
+        assert_eq!(
+            count_periods::<Day>(start, end, CountPolicy::Contained),
+            73049
+        );
+
+        assert_eq!(
+            count_periods::<Day>(end, start, CountPolicy::Intersecting),
+            0
+        );
+    }
+
+    #[test]
+    fn test_periods_in_duration() {
+        let sla = chrono::Duration::hours(4) + chrono::Duration::minutes(10);
+        assert_eq!(periods_in_duration::<HalfHour>(sla), Some(8));
+        assert_eq!(periods_in_duration::<FiveMinute>(sla), Some(50));
+        assert_eq!(periods_in_duration::<Day>(sla), Some(0));
+        assert_eq!(
+            periods_in_duration::<Week<Monday>>(chrono::Duration::days(15)),
+            Some(2)
+        );
+        assert_eq!(
+            periods_in_duration::<Zoned<Hour, Utc>>(chrono::Duration::days(1)),
+            Some(24)
+        );
+        assert_eq!(
+            periods_in_duration::<Month>(chrono::Duration::days(365)),
+            None
+        );
+        assert_eq!(
+            periods_in_duration::<Quarter>(chrono::Duration::days(365)),
+            None
+        );
+        assert_eq!(
+            periods_in_duration::<Year>(chrono::Duration::days(365)),
+            None
+        );
+        assert_eq!(
+            periods_in_duration::<Hour>(-chrono::Duration::hours(2)),
+            None
+        );
+        assert_eq!(
+            periods_in_duration::<Hour>(-chrono::Duration::minutes(1)),
+            None
+        );
+        assert_eq!(
+            periods_in_duration::<Hour>(chrono::Duration::zero()),
+            Some(0)
+        );
+    }
+
+    #[test]
+    fn test_alternate_display() {
+        let date = NaiveDate::from_ymd_opt(2021, 2, 1).unwrap();
+        let minute = "2021-01-01 10:05".parse::<Minute>().unwrap();
+        assert_eq!(format!("{minute:#}"), "2021-01-01T10:05Z");
+        assert_eq!(
+            format!("{:#}", FiveMinute::from(minute.start_datetime())),
+            "2021-01-01T10:05Z/2021-01-01T10:10Z"
+        );
+        assert_eq!(format!("{:#}", Day::from(date)), "2021-02-01");
+        assert_eq!(format!("{:#}", Week::<Monday>::from(date)), "2021-W05");
+        assert_eq!(
+            format!("{:#}", Week::<week::Sunday>::from(date)),
+            "2021-W04-7"
+        );
+        assert_eq!(format!("{:#}", Month::from(date)), "2021-02");
+        assert_eq!(format!("{:#}", Quarter::from(date)), "2021-Q1");
+        assert_eq!(format!("{:#}", Year::from(date)), "2021");
+        assert_eq!(
+            format!(
+                "{:#}",
+                Month::from(NaiveDate::from_ymd_opt(-1, 12, 1).unwrap())
+            ),
+            "-0001-12"
+        );
+        assert_eq!(
+            format!(
+                "{:#}",
+                Year::from(NaiveDate::from_ymd_opt(21, 1, 1).unwrap())
+            ),
+            "0021"
+        );
+    }
+
+    #[cfg(feature = "bytemuck")]
+    #[test]
+    fn test_bytemuck() {
+        fn check<P: TimeResolution + FromMonotonic + bytemuck::Pod + fmt::Debug>() {
+            let periods = [
+                P::from_monotonic(-3),
+                P::from_monotonic(0),
+                P::from_monotonic(7),
+            ];
+            let indexes: &[i64] = bytemuck::cast_slice(&periods);
+            assert_eq!(indexes, [-3, 0, 7]);
+            assert_eq!(bytemuck::cast_slice::<i64, P>(indexes), periods);
+            assert_eq!(P::zeroed(), P::from_monotonic(0));
+        }
+        check::<Minute>();
+        check::<FiveMinute>();
+        check::<Day>();
+        check::<Week<Sunday>>();
+        check::<Month>();
+        check::<Quarter>();
+        check::<Year>();
+    }
+
+    #[test]
+    fn test_checked_succ_pred() {
+        fn check<P: TimeResolution + FromMonotonic + fmt::Debug>() {
+            let zero = P::from_monotonic(0);
+            let max = P::from_monotonic(i64::MAX);
+            let min = P::from_monotonic(i64::MIN);
+            assert_eq!(zero.checked_succ_n(5), Some(P::from_monotonic(5)));
+            assert_eq!(zero.checked_pred_n(5), Some(P::from_monotonic(-5)));
+            assert_eq!(max.checked_succ_n(1), None);
+            assert_eq!(min.checked_pred_n(1), None);
+            assert_eq!(max.checked_pred_n(u64::MAX), Some(min));
+            assert_eq!(min.checked_succ_n(u64::MAX), Some(max));
+            assert_eq!(zero.checked_succ_n(u64::MAX), None);
+        }
+        check::<Minute>();
+        check::<Hour>();
+        check::<Day>();
+        check::<Week<Sunday>>();
+        check::<Month>();
+        check::<Quarter>();
+        check::<Year>();
+
+        let zoned = Zoned::<Hour, FixedEast<3600>>::from_monotonic(0);
+        assert_eq!(zoned.checked_succ_n(2), Some(zoned.succ().succ()));
+        assert_eq!(zoned.checked_succ_n(u64::MAX), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Day out of range")]
+    fn test_succ_out_of_range() {
+        Day::from_monotonic(i64::MAX).succ();
+    }
+
+    #[test]
+    fn test_offset_ops() {
+        let day = "2021-01-01".parse::<Day>().unwrap();
+        assert_eq!(day + 7, day.succ_n(7));
+        assert_eq!(day - 7, day.pred_n(7));
+        assert_eq!(day + -7, day - 7);
+        assert_eq!(day - -7, day + 7);
+        assert_eq!(day + 0, day);
+
+        let mut month = "Jan-2021".parse::<Month>().unwrap();
+        month += 13;
+        assert_eq!(month, "Feb-2022".parse().unwrap());
+        month -= 2;
+        assert_eq!(month, "Dec-2021".parse().unwrap());
+
+        let minute = Minute::from_monotonic(0);
+        assert_eq!(minute + i64::MIN, Minute::from_monotonic(i64::MIN));
+        assert_eq!(
+            Minute::from_monotonic(-1) - i64::MIN,
+            Minute::from_monotonic(i64::MAX)
+        );
+
+        let week = Week::<Sunday>::from_monotonic(10);
+        assert_eq!((week + 2).to_monotonic(), 12);
+        let zoned = Zoned::<Hour, FixedEast<3600>>::from_monotonic(0);
+        assert_eq!(zoned + 2, zoned.succ().succ());
+        assert_eq!((zoned + 2) - 2, zoned);
+    }
+
+    #[test]
+    fn test_sub_periods() {
+        let day = "2021-01-01".parse::<Day>().unwrap();
+        let later = day + 10;
+        assert_eq!(later - day, 10);
+        assert_eq!(day - later, -10);
+        assert_eq!(day + (later - day), later);
+        assert_eq!(later - day, day.between(later));
+        assert_eq!(day - day, 0);
+
+        let jan = "Jan-2021".parse::<Month>().unwrap();
+        assert_eq!("Mar-2022".parse::<Month>().unwrap() - jan, 14);
+        let q1 = "Q1-2021".parse::<Quarter>().unwrap();
+        assert_eq!(q1 - (q1 + 3), -3);
+        let hour = Hour::from_monotonic(100);
+        assert_eq!(hour - Hour::from_monotonic(40), 60);
+        let zoned = Zoned::<Hour, FixedEast<3600>>::from_monotonic(0);
+        assert_eq!((zoned + 5) - zoned, 5);
+    }
+
+    #[cfg(resolution_nightly)]
+    #[test]
+    fn test_step() {
+        use alloc::vec::Vec;
+
+        let start = "2021-01-01".parse::<Day>().unwrap();
+        let end = start + 6;
+        assert_eq!(
+            (start..=end).collect::<Vec<_>>(),
+            TimeRange::from_unordered_bounds(start, end)
+                .iter()
+                .collect::<Vec<_>>()
+        );
+        assert_eq!((start..end).count(), 6);
+        assert_eq!((end..start).count(), 0);
+        assert_eq!((start..).nth(31), Some(start + 31));
+        assert_eq!((start..=end).next_back(), Some(end));
+
+        let jan = "Jan-2021".parse::<Month>().unwrap();
+        assert_eq!((jan..jan + 12).step_by(3).count(), 4);
+    }
+
+    #[test]
+    fn test_bounded() {
+        assert_eq!(i64::from(chrono::Datelike::year(&NaiveDate::MIN)), MIN_YEAR);
+        assert_eq!(i64::from(chrono::Datelike::year(&NaiveDate::MAX)), MAX_YEAR);
+        assert_eq!(Day::from(NaiveDate::MIN).to_monotonic(), MIN_DAY);
+        assert_eq!(Day::from(NaiveDate::MAX).to_monotonic(), MAX_DAY);
+
+        assert_eq!(Day::MIN.start(), NaiveDate::MIN);
+        assert_eq!(Day::MAX.succ().start(), NaiveDate::MAX);
+        // the period after `MAX` is the last period supported
+        let last = |month, day| NaiveDate::from_ymd_opt(MAX_YEAR as i32, month, day).unwrap();
+        assert_eq!(Month::MIN.start(), NaiveDate::MIN);
+        assert_eq!(Month::MAX.succ().start(), last(12, 1));
+        assert_eq!(Quarter::MIN.start(), NaiveDate::MIN);
+        assert_eq!(Quarter::MAX.succ().start(), last(10, 1));
+        assert_eq!(Year::MIN.start(), NaiveDate::MIN);
+        assert_eq!(Year::MAX.succ().start(), last(1, 1));
+        assert_eq!(Year::MAX.end(), last(1, 1).pred_opt().unwrap());
+
+        fn check_week<D: StartDay>() {
+            let min = Week::<D>::MIN.start();
+            assert!((0..7).contains(&(min - NaiveDate::MIN).num_days()));
+            let max = Week::<D>::MAX.succ().start();
+            assert!((0..7).contains(&(NaiveDate::MAX - max).num_days()));
+            assert_eq!(Week::<D>::MAX.end(), max.pred_opt().unwrap());
+        }
+        check_week::<Monday>();
+        check_week::<Wednesday>();
+        check_week::<Sunday>();
+
+        fn check_minutes<const N: u32>() {
+            let length = chrono::Duration::minutes(N.into());
+            let min = Minutes::<N>::MIN.start_datetime();
+            assert_eq!(min.checked_sub_signed(length), None);
+            let max = Minutes::<N>::MAX.succ().start_datetime();
+            assert_eq!(max.checked_add_signed(length), None);
+        }
+        check_minutes::<1>();
+        check_minutes::<45>();
+        check_minutes::<60>();
+
+        assert_eq!(Month::from_monotonic_saturating(i64::MAX), Month::MAX);
+        assert_eq!(Month::from_monotonic_saturating(i64::MIN), Month::MIN);
+        assert_eq!(
+            Month::from_monotonic_saturating(5),
+            Month::from_monotonic(5)
+        );
+        assert_eq!(Hour::from_monotonic_saturating(i64::MAX), Hour::MAX);
+
+        assert_eq!(Month::from_monotonic_checked(i64::MAX), None);
+        assert_eq!(
+            Month::from_monotonic_checked(Month::MIN.to_monotonic()),
+            Some(Month::MIN)
+        );
+        assert_eq!(
+            Month::from_monotonic_checked(Month::MIN.to_monotonic() - 1),
+            None
+        );
+        assert_eq!(Year::from_monotonic_checked(5), Some(Year::new(5)));
+        assert_eq!(Hour::from_monotonic_checked(i64::MIN), None);
+    }
+
+    #[test]
+    fn test_epochs() {
+        assert_eq!(Day::from_monotonic(0).start(), Day::EPOCH);
+        assert_eq!(Month::from_monotonic(0).start(), Month::EPOCH);
+        assert_eq!(Quarter::from_monotonic(0).start(), Quarter::EPOCH);
+        assert_eq!(Year::from_monotonic(0).start(), Year::EPOCH);
+        assert_eq!(
+            Week::<Monday>::from_monotonic(0).start(),
+            Week::<Monday>::EPOCH
+        );
+        assert_eq!(
+            Week::<Sunday>::from_monotonic(0).start(),
+            Week::<Sunday>::EPOCH
+        );
+        assert_eq!(
+            chrono::Datelike::weekday(&Week::<Sunday>::EPOCH),
+            chrono::Weekday::Sun
+        );
+        assert_eq!(Hour::from_monotonic(0).start_datetime(), Hour::EPOCH);
+
+        // days since the Unix epoch
+        let unix = Day::from(Hour::EPOCH.date_naive());
+        let day = "2021-12-06".parse::<Day>().unwrap();
+        assert_eq!(day.to_monotonic_since(&unix), day.to_unix_days());
+        assert_eq!(Day::from_monotonic_since(day.to_unix_days(), &unix), day);
+        assert_eq!(
+            Month::from_monotonic_since(-1, &"Jan-2021".parse().unwrap()),
+            "Dec-2020".parse().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_try_start() {
+        for idx in [i64::MAX, i64::MIN, MAX_YEAR * 12 + 12, MIN_YEAR * 12 - 1] {
+            assert!(matches!(
+                Month::from_monotonic(idx).try_start(),
+                Err(Error::YearOutOfRange { .. })
+            ));
+            assert!(Quarter::from_monotonic(idx).try_start().is_err());
+            assert!(Year::from_monotonic(idx).try_start().is_err());
+        }
+        assert!(matches!(
+            Year::from_monotonic(i64::MAX).try_year_num(),
+            Err(Error::YearOutOfRange { year: i64::MAX })
+        ));
+        assert_eq!(
+            Year::from_monotonic(MAX_YEAR + 1).try_year_num().unwrap(),
+            262_143
+        );
+        assert!(Year::from_monotonic(MAX_YEAR + 1).try_start().is_err());
+
+        assert_eq!(
+            Month::MAX.succ().try_start().unwrap(),
+            Month::MAX.succ().start()
+        );
+        assert_eq!(Quarter::MIN.try_start().unwrap(), NaiveDate::MIN);
+        assert_eq!(Year::MAX.try_start().unwrap(), Year::MAX.start());
+    }
+
+    #[test]
+    fn test_from_timestamp() {
+        // 2021-06-30T23:59:59Z
+        let secs = 1_625_097_599;
+        assert_eq!(
+            Minute::from_timestamp(secs, ()).unwrap().start_datetime(),
+            DateTime::from_timestamp(secs - 59, 0).unwrap()
+        );
+        assert_eq!(
+            HalfHour::from_timestamp_millis(secs * 1000 + 999, ()),
+            HalfHour::from_timestamp(secs, ())
+        );
+        assert_eq!(
+            HalfHour::from_timestamp_millis(secs * 1000 + 1000, ()),
+            HalfHour::from_timestamp(secs, ()).map(|p| p.succ())
+        );
+        assert_eq!(
+            Day::from_timestamp(secs, ()),
+            Some("2021-06-30".parse().unwrap())
+        );
+        assert_eq!(
+            Quarter::from_timestamp_millis(secs * 1000 + 1000, ()),
+            Some("Q3-2021".parse().unwrap())
+        );
+        assert_eq!(
+            Month::from_timestamp_nanos(secs * 1_000_000_000, ()),
+            "Jun-2021".parse().unwrap()
+        );
+        assert_eq!(
+            Day::from_timestamp_nanos(-1, ()),
+            "1969-12-31".parse().unwrap()
+        );
+
+        assert_eq!(Day::from_timestamp(i64::MAX, ()), None);
+        assert_eq!(Minute::from_timestamp_millis(i64::MIN, ()), None);
+    }
+
+    #[test]
+    fn test_timestamp_range() {
+        // 2021-02-01T00:00:00Z
+        let feb = 1_612_137_600;
+        assert_eq!(
+            "Feb-2021".parse::<Month>().unwrap().timestamp_range(),
+            (feb, feb + 28 * 86_400)
+        );
+        assert_eq!(
+            "2021-02-01".parse::<Day>().unwrap().timestamp_range(),
+            (feb, feb + 86_400)
+        );
+        assert_eq!(
+            HalfHour::from_timestamp(feb + 1799, ())
+                .unwrap()
+                .timestamp_range(),
+            (feb, feb + 1800)
+        );
+
+        let zoned = Zoned::<Day, FixedEast<{ 10 * 60 * 60 }>>::from_date(
+            NaiveDate::from_ymd_opt(2021, 2, 1).unwrap(),
+            FixedEast,
+        );
+        assert_eq!(zoned.timestamp_range(), (feb - 10 * 3600, feb + 14 * 3600));
+
+        let (start, end) = Year::MAX.timestamp_range();
+        assert_eq!(start, Year::MAX.start_datetime().timestamp());
+        assert!(end > start);
+    }
+
+    #[test]
+    fn test_contains_date() {
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        let q1 = "Q1-2024".parse::<Quarter>().unwrap();
+        assert!(q1.contains_date(date(2024, 1, 1)));
+        assert!(q1.contains_date(date(2024, 2, 29)));
+        assert!(q1.contains_date(date(2024, 3, 31)));
+        assert!(!q1.contains_date(date(2023, 12, 31)));
+        assert!(!q1.contains_date(date(2024, 4, 1)));
+
+        let week = "Week starting 2024-01-01".parse::<Week<Monday>>().unwrap();
+        assert!(week.contains_date(date(2024, 1, 7)));
+        assert!(!week.contains_date(date(2024, 1, 8)));
+
+        let day = Day::from(date(2024, 1, 1));
+        assert!(day.contains_date(date(2024, 1, 1)));
+        assert!(!day.contains_date(date(2024, 1, 2)));
+    }
+
+    #[test]
+    fn test_contains_datetime() {
+        let hour = "2024-01-01 10:00 => 2024-01-01 11:00"
+            .parse::<Hour>()
+            .unwrap();
+        let start = hour.start_datetime();
+        let end = hour.succ().start_datetime();
+        let ns = chrono::Duration::nanoseconds(1);
+
+        assert!(hour.contains_datetime(start));
+        assert!(hour.contains_datetime(start + ns));
+        assert!(hour.contains_datetime(end - ns));
+        assert!(!hour.contains_datetime(start - ns));
+        assert!(!hour.contains_datetime(end));
+        assert!(hour.succ().contains_datetime(end));
+
+        let zoned = Zoned::<Hour, FixedEast<{ 10 * 60 * 60 }>>::from_utc_datetime(start, FixedEast);
+        assert!(zoned.contains_datetime(start));
+        assert!(!zoned.contains_datetime(end));
+    }
+
+    #[test]
+    fn test_contains() {
+        let month = "Feb-2024".parse::<Month>().unwrap();
+        assert!(month.contains("2024-02-01".parse::<Day>().unwrap()));
+        assert!(month.contains("2024-02-29".parse::<Day>().unwrap()));
+        assert!(!month.contains("2024-03-01".parse::<Day>().unwrap()));
+        assert!(!month.contains("2024-01-31".parse::<Day>().unwrap()));
+        assert!(month.contains(month));
+        assert!(!month.contains(month.succ()));
+        assert!(month.year().contains(month));
+        assert!(month.quarter().contains(month));
+
+        let day = "2024-02-29".parse::<Day>().unwrap();
+        let first = FiveMinute::first_on_day(day.start(), ());
+        assert!(day.contains(first));
+        assert!(day.contains(first.succ_n(287)));
+        assert!(!day.contains(first.pred()));
+        assert!(!day.contains(first.succ_n(288)));
+        assert!(day.week::<Monday>().contains(day));
+        assert!(!day.succ().week::<Friday>().contains(day));
+
+        let zoned = Zoned::<Day, FixedEast<{ 10 * 60 * 60 }>>::from_date(day.start(), FixedEast);
+        let hour = Zoned::<Hour, _>::first_on_day(day.start(), FixedEast);
+        assert!(zoned.contains(hour));
+        assert!(!zoned.contains(hour.pred()));
+    }
+
+    #[test]
+    fn test_cmp_start() {
+        let month = "Feb-2024".parse::<Month>().unwrap();
+        let day = "2024-02-01".parse::<Day>().unwrap();
+        let hour = Hour::first_on_day(day.start(), ());
+
+        assert_eq!(month.cmp_start(&day), cmp::Ordering::Equal);
+        assert_eq!(month.cmp_start(&hour), cmp::Ordering::Equal);
+        assert_eq!(month.cmp_start(&day.succ()), cmp::Ordering::Less);
+        assert!(hour.pred().is_before(&month));
+        assert!(!hour.is_before(&month));
+        assert!(day.succ().is_after(&month));
+        assert!(!month.is_after(&day));
+    }
+
+    #[test]
+    fn test_count_in() {
+        let month = "Jan-2024".parse::<Month>().unwrap();
+        assert_eq!(month.count_in::<Day>(), 31);
+        assert_eq!(month.succ().count_in::<Day>(), 29);
+        assert_eq!(month.count_in::<Hour>(), 31 * 24);
+        assert_eq!(month.count_in::<Month>(), 1);
+        assert_eq!(month.year().count_in::<Day>(), 366);
+        assert_eq!(month.year().count_in::<Quarter>(), 4);
+        assert_eq!(month.quarter().count_in::<Month>(), 3);
+
+        let day = "2024-01-01".parse::<Day>().unwrap();
+        assert_eq!(day.count_in::<FiveMinute>(), 288);
+        assert_eq!(day.count_in::<Minute>(), 1440);
+        assert_eq!(day.week::<Monday>().count_in::<HalfHour>(), 7 * 48);
+        assert_eq!(
+            Hour::first_on_day(day.start(), ()).count_in::<FiveMinute>(),
+            12
+        );
+
+        for month in TimeRange::from_unordered_bounds(month, month.succ_n(24)).iter() {
+            assert_eq!(month.count_in::<Day>(), month.rescale::<Day>().len().get());
+        }
+    }
+
+    #[test]
+    fn test_convert_with() {
+        fn to_week<P: TimeResolution, D: StartDay>(period: P) -> Week<D> {
+            period.convert_with(())
+        }
+
+        let hour = Hour::from_timestamp(1_709_164_800, ()).unwrap();
+        let date = NaiveDate::from_ymd_opt(2024, 2, 29).unwrap();
+        assert_eq!(hour.start_datetime().date_naive(), date);
+        assert_eq!(
+            to_week::<_, Monday>(hour),
+            "Week starting 2024-02-26".parse().unwrap()
+        );
+        assert_eq!(hour.convert_with::<Month>(()), "Feb-2024".parse().unwrap());
+
+        let zoned = hour.convert_with::<Zoned<Day, FixedEast<3600>>>(FixedEast);
+        assert_eq!(zoned.start(), date);
+
+        // 23:00 UTC is already the next day at +10:00
+        let late = hour.pred();
+        assert_eq!(late.start_datetime().date_naive(), date.pred_opt().unwrap());
+        let zoned = late.convert_with::<Zoned<Day, FixedEast<36000>>>(FixedEast);
+        assert_eq!(zoned.start(), date);
+        assert!(zoned.utc_start_datetime() <= late.start_datetime());
+        assert!(zoned.succ().utc_start_datetime() > late.start_datetime());
+    }
+
+    #[test]
+    fn test_quarter_and_week() {
+        fn quarter_and_week<P: TimeResolution>(period: P) -> (Quarter, Week<Sunday>) {
+            (period.quarter(), period.week())
+        }
+
+        let day = "2024-03-31".parse::<Day>().unwrap();
+        let expected = (
+            "Q1-2024".parse().unwrap(),
+            "Week starting 2024-03-31".parse().unwrap(),
+        );
+        assert_eq!(quarter_and_week(day), expected);
+        assert_eq!(
+            quarter_and_week(Hour::last_on_day(day.start(), ())),
+            expected
+        );
+        assert_eq!(
+            quarter_and_week(day.month()),
+            (expected.0, day.month().week())
+        );
+        assert_eq!(day.year().quarter(), expected.0);
+        assert_eq!(
+            TimeResolution::week::<Monday>(&day.month()),
+            "Week starting 2024-02-26".parse().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_rescale_to() {
+        let day = "2024-02-29".parse::<Day>().unwrap();
+        assert_eq!(
+            day.rescale_to::<FiveMinute>(),
+            day.to_sub_date_resolution::<FiveMinute>()
+        );
+        assert_eq!(
+            day.rescale_to::<Day>(),
+            TimeRange::from_unordered_bounds(day, day)
+        );
+
+        let year = day.year();
+        assert_eq!(year.rescale_to::<Month>(), year.rescale::<Month>());
+        assert_eq!(year.rescale_to::<Day>().len().get(), 366);
+        assert_eq!(
+            year.rescale_to::<Hour>().end(),
+            Hour::last_on_day(year.end(), ())
+        );
+
+        let hour = Hour::first_on_day(day.start(), ());
+        let halves = hour.rescale_to::<HalfHour>();
+        assert_eq!(halves.len().get(), 2);
+        assert_eq!(halves.start().start_datetime(), hour.start_datetime());
+    }
+}
+
+// parsing and formatting without allocating, so these also run without the `alloc` feature
+#[cfg(test)]
+mod core_tests {
+    use super::*;
+
+    // a fixed size buffer to write into
+    struct Buf {
+        bytes: [u8; 128],
+        len: usize,
+    }
+
+    impl Buf {
+        fn new() -> Buf {
+            Buf {
+                bytes: [0; 128],
+                len: 0,
+            }
+        }
+        fn as_str(&self) -> &str {
+            str::from_utf8(&self.bytes[..self.len]).unwrap()
+        }
+    }
+
+    impl fmt::Write for Buf {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            let end = self.len + s.len();
+            self.bytes
+                .get_mut(self.len..end)
+                .ok_or(fmt::Error)?
+                .copy_from_slice(s.as_bytes());
+            self.len = end;
+            Ok(())
+        }
+    }
+
+    fn round_trip<R>(input: &str)
+    where
+        R: str::FromStr<Err = Error> + fmt::Display + fmt::Debug + PartialEq,
+    {
+        let parsed = input.parse::<R>().unwrap();
+        let mut out = Buf::new();
+        fmt::write(&mut out, format_args!("{parsed}")).unwrap();
+        assert_eq!(out.as_str(), input);
+        assert_eq!(out.as_str().parse::<R>().unwrap(), parsed);
+    }
+
+    #[test]
+    fn test_parse_without_alloc() {
+        round_trip::<Minute>("2021-01-01 10:07");
+        round_trip::<FiveMinute>("2021-01-01 10:05 => 2021-01-01 10:10");
+        round_trip::<DaySubdivison<30>>("period 21 of 48");
+        round_trip::<Day>("2021-01-01");
+        round_trip::<Week<Monday>>("Week starting 2021-01-04");
+        round_trip::<Month>("Jan-2021");
+        round_trip::<Quarter>("Q1-2021");
+        round_trip::<Year>("2021");
+        round_trip::<Zoned<Day, FixedEast<{ 10 * 60 * 60 }>>>("2021-01-01 FixedEast[+10:00]");
+        round_trip::<Zoned<Hour, chrono::FixedOffset>>(
+            "2021-01-01 10:00 => 2021-01-01 11:00 +10:00",
+        );
+        round_trip::<TimeRange<FiveMinute>>("2021-01-01T10:00Z/2021-01-01T11:00Z");
+
+        assert_eq!(
+            parse_erased_resolution(|_, _| Err(Error::EmptyRange), "Month", "Jan-2021").unwrap(),
+            (
+                any::TypeId::of::<Month>(),
+                "Jan-2021".parse::<Month>().unwrap().to_monotonic()
+            )
+        );
+    }
+
+    #[test]
+    fn test_parse_errors_without_alloc() {
+        // the position is within the whole input, even when the error is in the end of a period
+        let err = "2021-01-01 10:00 => 2021-01-01 1x:05"
+            .parse::<FiveMinute>()
+            .unwrap_err();
+        #[cfg(feature = "alloc")]
+        assert!(matches!(
+            err,
+            Error::ParseIntDetailed {
+                field: "hour",
+                position: 31,
+                ..
+            }
+        ));
+        #[cfg(not(feature = "alloc"))]
+        assert!(matches!(
+            err,
+            Error::ParseIntAt {
+                field: "hour",
+                position: 31,
+                ..
+            }
+        ));
+
+        let err = "Janu-2021".parse::<Month>().unwrap_err();
+        #[cfg(feature = "alloc")]
+        assert!(matches!(
+            err,
+            Error::ParseDateInternal {
+                position: 0,
+                format: "%b-%Y",
+                ..
+            }
+        ));
+        #[cfg(not(feature = "alloc"))]
+        assert!(matches!(
+            err,
+            Error::ParseDateAt {
+                position: 0,
+                format: "%b-%Y",
+            }
+        ));
+
+        let err = "Q5-2021".parse::<Quarter>().unwrap_err();
+        #[cfg(feature = "alloc")]
+        assert!(matches!(
+            err,
+            Error::ParseCustom {
+                ty_name: "Quarter",
+                ..
+            }
+        ));
+        #[cfg(not(feature = "alloc"))]
+        assert!(matches!(err, Error::InvalidInput { ty_name: "Quarter" }));
+
+        // errors can be written without allocating
+        let mut out = Buf::new();
+        fmt::write(
+            &mut out,
+            format_args!("{}", Error::InvalidInput { ty_name: "Quarter" }),
+        )
+        .unwrap();
+        assert_eq!(out.as_str(), "Error parsing Quarter");
+    }
+
+    #[test]
+    fn test_write_name_without_alloc() {
+        let mut out = Buf::new();
+        Minutes::<45>::from_monotonic(0)
+            .write_name(&mut out)
+            .unwrap();
+        assert_eq!(out.as_str(), "Minutes[Length:45]");
+    }
+}
@@ -2,36 +2,57 @@ use core::fmt::Debug;
 use core::num::NonZeroU64;
 
 use crate::{Error, FromMonotonic, Monotonic, SubDateResolution, TimeResolution};
-use alloc::{
-    fmt, format, str,
-    string::{String, ToString},
-};
-use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveTime, Timelike, Utc};
+#[cfg(feature = "alloc")]
+use alloc::{borrow, format, string::String};
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Timelike, Utc};
+use core::{fmt, str};
 
 const NUM_SECS: i64 = 60;
 
-/// Note that for sensible behaviour, the N chosen should be a number that either:
+/// The N chosen must divide a day with no remainder, for example a number that either:
 /// 1. divides into an hour with no remainder (1, 2, 3, 4, 5, 6, 10, 12, 15, 20, 30, 60)
-/// 2. is exactly a whole number of hours that divides into a day with no remainder (60, 120, 180, 240, 360, 480, 1800)
-/// Any other choice will result in unexpected / unuseful behaviour (eg the `Minutes` not cleanly fitting into parts of a day)
+/// 2. is exactly a whole number of hours that divides into a day with no remainder (60, 120, 180, 240, 360, 480, 720, 1440)
+///
+/// This is checked at compile time wherever a `Minutes` is created, so for example this fails to
+/// compile, as otherwise the periods wouldn't cleanly fit into a day:
+///
+/// ```compile_fail
+/// let period = resolution::Minutes::<7>::from_rfc3339("2021-01-01T10:00:00Z");
+/// ```
+///
+/// The monotonic index of a `Minutes` is the number of periods since the Unix epoch,
+/// 1970-01-01T00:00:00Z, unlike the date resolutions, which count from 0000-01-01. Use
+/// `Minutes::from_unix_seconds` and `Minutes::to_unix_seconds` to exchange seconds since the Unix
+/// epoch with other systems.
+///
+/// With `serde`, `Minutes` are serialized as a string, as written by `Display`, for human readable
+/// formats, or otherwise as the monotonic `i64`. The struct form `{"index":..,"length":..}`,
+/// previously used for all formats, is still accepted for human readable formats. With
+/// `serde_with`, `serde_as::Rfc3339` instead uses the RFC 3339 timestamp of the start.
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
-#[cfg_attr(feature = "serde", serde(try_from = "Minutes_", into = "Minutes_"))]
+#[repr(transparent)]
 pub struct Minutes<const N: u32> {
     index: i64,
 }
 
+// SAFETY: `Minutes` is `repr(transparent)` over an `i64`, so every bit pattern is a valid `Minutes`
+#[cfg(feature = "bytemuck")]
+unsafe impl<const N: u32> bytemuck::Zeroable for Minutes<N> {}
+#[cfg(feature = "bytemuck")]
+unsafe impl<const N: u32> bytemuck::Pod for Minutes<N> {}
+
 // #[cfg(not(serde))]
 // #[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
 // pub struct Minutes<const N: u32> {
 //     index: i64,
 // }
 
+#[cfg(feature = "alloc")]
 impl<const N: u32> TryFrom<Minutes_> for Minutes<N> {
     type Error = String;
     fn try_from(value: Minutes_) -> Result<Self, Self::Error> {
         if value.length == N {
-            Ok(Minutes { index: value.index })
+            Ok(Minutes::from_monotonic(value.index))
         } else {
             Err(format!(
                 "To create a Minutes[Length:{}], the length field should be {} but was instead {}",
@@ -41,86 +62,136 @@ impl<const N: u32> TryFrom<Minutes_> for Minutes<N> {
     }
 }
 
-impl<const N: u32> From<Minutes<N>> for Minutes_ {
-    fn from(w: Minutes<N>) -> Self {
-        Minutes_ {
-            index: w.index,
-            length: N,
-        }
-    }
-}
-
-#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg(feature = "alloc")]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
 pub(crate) struct Minutes_ {
     index: i64,
     pub(crate) length: u32,
 }
 
+#[cfg(feature = "serde")]
+impl<'de, const N: u32> serde::Deserialize<'de> for Minutes<N> {
+    fn deserialize<D>(deserializer: D) -> Result<Minutes<N>, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        crate::compact::deserialize_readable_or_legacy::<_, Minutes_, _>(deserializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<const N: u32> serde::Serialize for Minutes<N> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        crate::compact::serialize_readable(self, serializer)
+    }
+}
+
 impl<const N: u32> From<DateTime<Utc>> for Minutes<N> {
     fn from(d: DateTime<Utc>) -> Self {
+        let () = Self::DIVIDES_DAY;
         Minutes {
             index: d.timestamp().div_euclid(60 * i64::from(N)),
         }
     }
 }
 
+impl<const N: u32> Minutes<N> {
+    /// Parse an RFC 3339 timestamp with any offset, eg `2021-01-01T10:07:30+10:00`, into the
+    /// period containing it. See `SubDateResolution::parse_rfc3339`.
+    pub fn from_rfc3339(input: &str) -> Result<Self, Error> {
+        Self::parse_rfc3339(input, ())
+    }
+}
+
+impl<const N: u32> Minutes<N> {
+    // check that `start` and `end` are the bounds of a single period
+    fn from_start_end(
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        input: &str,
+    ) -> Result<Self, Error> {
+        if (start.hour() * 60 + start.minute()).rem_euclid(N) != 0 {
+            return Err(crate::Error::custom(
+                "Minutes",
+                format_args!("Invalid start for Minutes[Length:{}]: {}", N, start),
+            ));
+        }
+        if start + Duration::minutes(i64::from(N)) != end {
+            return Err(crate::Error::custom(
+                "Minutes",
+                format_args!(
+                    "Invalid start-end combination for Minutes[Length:{}]: {}",
+                    N, input
+                ),
+            ));
+        }
+        Ok(start.into())
+    }
+}
+
 impl<const N: u32> str::FromStr for Minutes<N> {
     type Err = crate::Error;
+    /// As well as the form written by `Display`, this accepts the ISO 8601 interval written by
+    /// the alternate `Display`, eg `2021-01-01T10:00Z/2021-01-01T10:05Z`, and for `Minute`, the
+    /// ISO 8601 time `2021-01-01T10:00Z`.
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some((start, end)) = s.split_once('/') {
+            let start_len = start.len();
+            let start = parse_iso_datetime(start).map_err(|e| e.within(0, s))?;
+            let end = parse_iso_datetime(end).map_err(|e| e.within(start_len + 1, s))?;
+            return Self::from_start_end(start, end, s);
+        }
         if N == 1 {
-            let time = parse_datetime(s)?;
+            let time = if s.ends_with('Z') {
+                parse_iso_datetime(s)?
+            } else {
+                parse_datetime(s)?
+            };
             if time.second() != 0 {
-                Err(crate::Error::ParseCustom {
-                    ty_name: "Minutes",
-                    input: s.into(),
-                })
+                Err(crate::Error::custom("Minutes", format_args!("{s}")))
             } else {
                 Ok(time.into())
             }
         } else {
             let mut splits = s.split(" => ");
 
-            let start = splits.next().ok_or_else(|| crate::Error::ParseCustom {
-                ty_name: "Minutes",
-                input: s.into(),
-            })?;
-
-            let end = splits.next().ok_or_else(|| crate::Error::ParseCustom {
-                ty_name: "Minutes",
-                input: s.into(),
-            })?;
+            let start = splits
+                .next()
+                .ok_or_else(|| crate::Error::custom("Minutes", format_args!("{s}")))?;
+            let start_len = start.len();
 
-            let start = parse_datetime(start)?;
+            let end = splits
+                .next()
+                .ok_or_else(|| crate::Error::custom("Minutes", format_args!("{s}")))?;
 
-            if (start.hour() * 60 + start.minute()).rem_euclid(N) != 0 {
-                return Err(crate::Error::ParseCustom {
-                    ty_name: "Minutes",
-                    input: format!("Invalid start for Minutes[Length:{}]: {}", N, start,),
-                });
-            }
-            let end = parse_datetime(end)?;
-
-            if start + Duration::minutes(i64::from(N)) != end {
-                return Err(crate::Error::ParseCustom {
-                    ty_name: "Minutes",
-                    input: format!(
-                        "Invalid start-end combination for Minutes[Length:{}]: {}",
-                        N, s
-                    ),
-                });
-            }
-
-            Ok(start.into())
+            let start = parse_datetime(start).map_err(|e| e.within(0, s))?;
+            let end = parse_datetime(end).map_err(|e| e.within(start_len + 4, s))?;
+            Self::from_start_end(start, end, s)
         }
     }
 }
 
 // TODO: make this more efficient
 fn format_datetime(n: DateTime<Utc>, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    crate::write_iso_year(f, n.year().into())?;
+    write!(
+        f,
+        "-{:02}-{:02} {:02}:{:02}",
+        n.month(),
+        n.day(),
+        n.hour(),
+        n.minute()
+    )
+}
+
+pub(crate) fn format_iso_datetime(n: DateTime<Utc>, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    crate::write_iso_year(f, n.year().into())?;
     write!(
         f,
-        "{}-{:02}-{:02} {:02}:{:02}",
-        n.year(),
+        "-{:02}-{:02}T{:02}:{:02}Z",
         n.month(),
         n.day(),
         n.hour(),
@@ -128,43 +199,105 @@ fn format_datetime(n: DateTime<Utc>, f: &mut fmt::Formatter<'_>) -> fmt::Result
     )
 }
 
+const DATETIME_FORMAT: &str = "%Y-%m-%d %H:%M";
+const ISO_DATETIME_FORMAT: &str = "%Y-%m-%dT%H:%MZ";
+
 fn parse_datetime(input: &str) -> Result<DateTime<Utc>, Error> {
-    let year = input[0..=3]
-        .parse()
-        .map_err(|e| Error::ParseIntDetailed(e, input[0..=3].to_string()))?;
-    let month = input[5..=6]
-        .parse()
-        .map_err(|e| Error::ParseIntDetailed(e, input[5..=6].to_string()))?;
-    let day = input[8..=9]
-        .parse()
-        .map_err(|e| Error::ParseIntDetailed(e, input[8..=9].to_string()))?;
-    let hour = input[11..=12]
-        .parse()
-        .map_err(|e| Error::ParseIntDetailed(e, input[10..=12].to_string()))?;
-    let minute = input[14..=15]
+    parse_fields(input, b' ', DATETIME_FORMAT)
+}
+
+// parse the ISO 8601 form written by the alternate `Display`, eg `2021-01-01T10:05Z`
+pub(crate) fn parse_iso_datetime(input: &str) -> Result<DateTime<Utc>, Error> {
+    let Some(fields) = input.strip_suffix('Z') else {
+        return Err(Error::parse_date(
+            format_args!("Expected a trailing `Z`"),
+            input.len(),
+            input,
+            ISO_DATETIME_FORMAT,
+        ));
+    };
+    parse_fields(fields, b'T', ISO_DATETIME_FORMAT).map_err(|e| e.within(0, input))
+}
+
+fn parse_fields(input: &str, separator: u8, format: &'static str) -> Result<DateTime<Utc>, Error> {
+    // validate up front so that the fields below can be found by byte offset
+    // without panicking on short input or in the middle of a multi-byte character
+    if input.len() < 16 {
+        return Err(Error::UnexpectedInputLength {
+            required: 16,
+            actual: input.len(),
+            format,
+        });
+    }
+    if let Some(position) = input.bytes().position(|b| !b.is_ascii()) {
+        return Err(Error::parse_date(
+            format_args!("Unexpected non-ASCII character"),
+            position,
+            input,
+            format,
+        ));
+    }
+
+    // the year may be signed and have more than 4 digits, so the other fields
+    // are found relative to the end of the input
+    let (year, rest) = input.split_at(input.len() - 12);
+    for (offset, expected) in [(0, b'-'), (3, b'-'), (6, separator), (9, b':')] {
+        if rest.as_bytes()[offset] != expected {
+            return Err(Error::parse_date(
+                format_args!("Expected `{}`", char::from(expected)),
+                year.len() + offset,
+                input,
+                format,
+            ));
+        }
+    }
+    let field = |name: &'static str, start: usize, len: usize| {
+        let value = &rest[start..start + len];
+        value
+            .parse()
+            .map_err(|source| Error::parse_int(source, name, year.len() + start, input, format))
+    };
+    let year = year
         .parse()
-        .map_err(|e| Error::ParseIntDetailed(e, input[14..=15].to_string()))?;
-
-    let date =
-        NaiveDate::from_ymd_opt(year, month, day).ok_or_else(|| Error::ParseDateInternal {
-            message: alloc::format!("Invalid values for ymd: {year}-{month}-{day}"),
-            input: input.to_string(),
-            format: "%Y/%m/%d %H:%M",
-        })?;
-
-    let time =
-        NaiveTime::from_hms_opt(hour, minute, 0).ok_or_else(|| Error::ParseDateInternal {
-            message: alloc::format!("Invalid values for hm: {hour}:{minute}"),
-            input: input.to_string(),
-            format: "%Y/%m/%d %H:%M",
-        })?;
+        .map_err(|source| Error::parse_int(source, "year", 0, input, format))?;
+    let month = field("month", 1, 2)?;
+    let day = field("day", 4, 2)?;
+    let hour = field("hour", 7, 2)?;
+    let minute = field("minute", 10, 2)?;
+
+    let date = NaiveDate::from_ymd_opt(year, month, day).ok_or_else(|| {
+        Error::parse_date(
+            format_args!("Invalid values for ymd: {year}-{month}-{day}"),
+            0,
+            input,
+            format,
+        )
+    })?;
+
+    let time = chrono::NaiveTime::from_hms_opt(hour, minute, 0).ok_or_else(|| {
+        Error::parse_date(
+            format_args!("Invalid values for hm: {hour}:{minute}"),
+            input.len() - 5,
+            input,
+            format,
+        )
+    })?;
 
     Ok(date.and_time(time).and_utc())
 }
 
 impl<const N: u32> fmt::Display for Minutes<N> {
+    /// The alternate form, `{:#}`, is ISO 8601, eg `2021-01-01T10:05Z`, or for periods longer
+    /// than one minute, the interval `2021-01-01T10:05Z/2021-01-01T10:10Z`.
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        if N == 1 {
+        if f.alternate() {
+            format_iso_datetime(self.start_datetime(), f)?;
+            if N != 1 {
+                f.write_str("/")?;
+                format_iso_datetime(self.succ().start_datetime(), f)?;
+            }
+            Ok(())
+        } else if N == 1 {
             format_datetime(self.start_datetime(), f)
         } else {
             format_datetime(self.start_datetime(), f)?;
@@ -177,21 +310,41 @@ impl<const N: u32> fmt::Display for Minutes<N> {
 
 impl<const N: u32> crate::TimeResolution for Minutes<N> {
     fn succ_n(&self, n: u64) -> Minutes<N> {
-        Minutes {
-            index: self.index + i64::try_from(n).unwrap(),
-        }
+        self.checked_succ_n(n).expect("Minutes out of range")
     }
     fn pred_n(&self, n: u64) -> Minutes<N> {
-        Minutes {
-            index: self.index - i64::try_from(n).unwrap(),
-        }
+        self.checked_pred_n(n).expect("Minutes out of range")
+    }
+    fn checked_succ_n(&self, n: u64) -> Option<Minutes<N>> {
+        let index = self.index.checked_add_unsigned(n)?;
+        Some(Minutes { index })
+    }
+    fn checked_pred_n(&self, n: u64) -> Option<Minutes<N>> {
+        let index = self.index.checked_sub_unsigned(n)?;
+        Some(Minutes { index })
     }
     fn start_datetime(&self) -> DateTime<Utc> {
         DateTime::<Utc>::from_timestamp(self.index * NUM_SECS * i64::from(N), 0)
             .expect("valid timestamp")
     }
-    fn name(&self) -> String {
-        format!("Minutes[Length:{}]", N)
+    fn write_name(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        write!(out, "Minutes[Length:{}]", N)
+    }
+    #[cfg(feature = "alloc")]
+    fn name(&self) -> borrow::Cow<'static, str> {
+        // avoid allocating for the common lengths
+        match N {
+            1 => "Minutes[Length:1]".into(),
+            5 => "Minutes[Length:5]".into(),
+            10 => "Minutes[Length:10]".into(),
+            15 => "Minutes[Length:15]".into(),
+            30 => "Minutes[Length:30]".into(),
+            60 => "Minutes[Length:60]".into(),
+            _ => format!("Minutes[Length:{}]", N).into(),
+        }
+    }
+    fn fixed_duration() -> Option<Duration> {
+        Some(Duration::minutes(i64::from(N)))
     }
 }
 
@@ -206,13 +359,51 @@ impl<const N: u32> Monotonic for Minutes<N> {
 
 impl<const N: u32> FromMonotonic for Minutes<N> {
     fn from_monotonic(index: i64) -> Self {
+        let () = Self::DIVIDES_DAY;
         Minutes { index }
     }
 }
 
-impl<const N: u32> Minutes<N> {}
+impl<const N: u32> crate::Bounded for Minutes<N> {
+    // the first period starting on or after the first supported datetime
+    const MIN: Minutes<N> = {
+        let () = Self::DIVIDES_DAY;
+        Minutes {
+            index: -((-(crate::MIN_DAY - crate::EPOCH_DAY) * 24 * 60).div_euclid(N as i64)),
+        }
+    };
+    // the last period where the following period starts on or before the last supported datetime
+    const MAX: Minutes<N> = Minutes {
+        index: ((crate::MAX_DAY - crate::EPOCH_DAY + 1) * 24 * 60 - 1).div_euclid(N as i64) - 1,
+    };
+}
+
+impl<const N: u32> Minutes<N> {
+    // evaluated at compile time wherever it is used, so using an `N` which doesn't evenly divide
+    // a day is a compile error
+    pub(crate) const DIVIDES_DAY: () = assert!(
+        N > 0 && 1440 % N == 0,
+        "Minutes length must evenly divide a day"
+    );
+}
+
+// `Minutes<N>` can be subdivided into `Minutes<M>`
+struct Subdivides<const N: u32, const M: u32>;
+
+impl<const N: u32, const M: u32> Subdivides<N, M> {
+    // evaluated at compile time wherever it is used, so subdividing into an `M` which doesn't
+    // evenly divide `N` is a compile error
+    const CHECK: () = assert!(
+        M > 0 && N.is_multiple_of(M),
+        "Minutes length must divide the length being subdivided"
+    );
+}
 
 impl<const N: u32> SubDateResolution for Minutes<N> {
+    const PERIODS_PER_DAY: u32 = DaySubdivison::<N>::PERIODS;
+    fn exact_duration() -> Duration {
+        Duration::minutes(i64::from(N))
+    }
     fn occurs_on_date(&self) -> chrono::NaiveDate {
         self.start_datetime().date_naive()
     }
@@ -222,7 +413,7 @@ impl<const N: u32> SubDateResolution for Minutes<N> {
                 .expect("valid time")
                 .and_utc()
                 .timestamp()
-                / (i64::from(N) * NUM_SECS),
+                .div_euclid(i64::from(N) * NUM_SECS),
         )
     }
 
@@ -235,98 +426,205 @@ impl<const N: u32> SubDateResolution for Minutes<N> {
     }
 }
 
-macro_rules! minutes_impl {
-    ($i:literal) => {
-        impl Minutes<$i> {
-            pub fn relative(&self) -> DaySubdivison<$i> {
-                DaySubdivison {
-                    index: Minutes::<$i>::first_on_day(self.occurs_on_date(), ()).between(*self),
-                }
-            }
+impl<const N: u32> Minutes<N> {
+    pub fn relative(&self) -> DaySubdivison<N> {
+        let () = DaySubdivison::<N>::DIVIDES_DAY;
+        DaySubdivison {
+            index: Minutes::<N>::first_on_day(self.occurs_on_date(), ()).between(*self),
         }
+    }
+    /// The start of the `Minutes` with a monotonic index of `0`, the Unix epoch.
+    pub const EPOCH: DateTime<Utc> = DateTime::UNIX_EPOCH;
+    /// The period containing the time `secs` seconds after the Unix epoch. Unlike
+    /// `SubDateResolution::from_timestamp` this doesn't check the time is supported by `chrono`.
+    pub fn from_unix_seconds(secs: i64) -> Self {
+        Self::from_monotonic(secs.div_euclid(i64::from(N) * NUM_SECS))
+    }
+    /// The number of seconds from the Unix epoch to the start of this period.
+    pub fn to_unix_seconds(&self) -> i64 {
+        self.index * i64::from(N) * NUM_SECS
+    }
+    /// The periods of `Minutes<M>` which make up this period, for example the six five minute
+    /// periods of a half hour. `M` must divide `N`, which is checked at compile time.
+    pub fn subdivide<const M: u32>(&self) -> crate::TimeRange<Minutes<M>> {
+        let () = Subdivides::<N, M>::CHECK;
+        let ratio = N / M;
+        crate::TimeRange::new(
+            Minutes::<M>::from_monotonic(self.index * i64::from(ratio)),
+            NonZeroU64::new(u64::from(ratio)).expect("M divides N so is at most N"),
+        )
+    }
+    /// All the periods on `date`, in order, from the first to the `DaySubdivison::PERIODS`th.
+    pub fn iter_on_date(date: NaiveDate) -> crate::TimeRangeIter<Minutes<N>> {
+        crate::TimeRange::new(
+            Minutes::<N>::first_on_day(date, ()),
+            NonZeroU64::new(u64::from(DaySubdivison::<N>::PERIODS))
+                .expect("A day has at least one period"),
+        )
+        .iter()
+    }
+}
+
+impl<const N: u32> Debug for DaySubdivison<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DaySubdivison")
+            .field("index", &self.index())
+            .field("length_minutes", &N)
+            .field("periods", &Self::PERIODS)
+            .finish()
+    }
+}
+
+impl<const N: u32> DaySubdivison<N> {
+    const DIVIDES_DAY: () = Minutes::<N>::DIVIDES_DAY;
+    pub const PERIODS: u32 = {
+        let () = Self::DIVIDES_DAY;
+        1440 / N
     };
+    /// Every subdivision of the day in order, from `1` to `PERIODS`.
+    pub fn all() -> impl DoubleEndedIterator<Item = DaySubdivison<N>> + ExactSizeIterator {
+        (0..Self::PERIODS).map(|index| DaySubdivison {
+            index: i64::from(index),
+        })
+    }
+    pub fn on_date(&self, date: NaiveDate) -> Minutes<N> {
+        Minutes::<N>::from_monotonic(
+            self.index + Minutes::<N>::first_on_day(date, ()).to_monotonic(),
+        )
+    }
+    pub fn new(period_no: NonZeroU64) -> Option<DaySubdivison<N>> {
+        let () = Self::DIVIDES_DAY;
+        if i64::try_from(period_no.get()).ok()? > i64::from(Self::PERIODS) {
+            return None;
+        }
+
+        Some(DaySubdivison {
+            index: i64::try_from(period_no.get()).ok()? - 1,
+        })
+    }
+    pub fn index(&self) -> NonZeroU64 {
+        NonZeroU64::new(u64::try_from(self.index).unwrap() + 1).unwrap()
+    }
+    /// The period number, as with `index`, counting from 1.
+    pub fn number(&self) -> u32 {
+        u32::try_from(self.index + 1).expect("Not possible to have more than PERIODS periods")
+    }
+}
+
+impl<const N: u32> fmt::Display for DaySubdivison<N> {
+    /// For example `period 37 of 48`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "period {} of {}", self.index(), Self::PERIODS)
+    }
 }
 
-macro_rules! day_subdivision_impl {
-    ($i:literal) => {
-        // 1
-        impl Debug for DaySubdivison<$i> {
-            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-                f.debug_struct("DaySubdivison")
-                    .field("index", &self.index())
-                    .field("length_minutes", &$i)
-                    .field("periods", &Self::PERIODS)
-                    .finish()
+impl<const N: u32> TryFrom<u32> for DaySubdivison<N> {
+    type Error = Error;
+    fn try_from(number: u32) -> Result<Self, Self::Error> {
+        NonZeroU64::new(u64::from(number))
+            .and_then(DaySubdivison::new)
+            .ok_or(Error::InvalidPeriodNumber {
+                number,
+                periods: Self::PERIODS,
+            })
+    }
+}
+
+impl<const N: u32> str::FromStr for DaySubdivison<N> {
+    type Err = Error;
+    /// Parses either the `Display` form, eg `period 37 of 48`, or just the period number, eg `37`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let number = match s.strip_prefix("period ") {
+            Some(rest) => {
+                let Some((number, periods)) = rest.split_once(" of ") else {
+                    return Err(Error::custom("DaySubdivison", format_args!("{s}")));
+                };
+                if periods.parse::<u32>()? != Self::PERIODS {
+                    return Err(Error::custom(
+                        "DaySubdivison",
+                        format_args!("{s}, expected {} periods in a day", Self::PERIODS),
+                    ));
+                }
+                number
             }
+            None => s,
+        };
+        DaySubdivison::try_from(number.parse::<u32>()?)
+    }
+}
+
+/// Serialized as a string for human readable formats, or otherwise as the period number. For
+/// human readable formats the period number on its own is also accepted.
+#[cfg(feature = "serde")]
+impl<const N: u32> serde::Serialize for DaySubdivison<N> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        if serializer.is_human_readable() {
+            serializer.collect_str(self)
+        } else {
+            serializer.serialize_u32(self.number())
         }
+    }
+}
 
-        // 2
-        impl DaySubdivison<$i> {
-            pub const PERIODS: u32 = 1440 / $i;
-            pub fn on_date(&self, date: NaiveDate) -> Minutes<$i> {
-                Minutes::<$i>::from_monotonic(
-                    self.index + Minutes::<$i>::first_on_day(date, ()).to_monotonic(),
+#[cfg(feature = "serde")]
+impl<'de, const N: u32> serde::Deserialize<'de> for DaySubdivison<N> {
+    fn deserialize<D>(deserializer: D) -> Result<DaySubdivison<N>, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        use serde::de;
+
+        struct Visitor<const N: u32>;
+
+        impl<const N: u32> de::Visitor<'_> for Visitor<N> {
+            type Value = DaySubdivison<N>;
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(
+                    f,
+                    "a period number or string such as `period 1 of {}`",
+                    DaySubdivison::<N>::PERIODS
                 )
             }
-            pub fn new(period_no: NonZeroU64) -> Option<DaySubdivison<$i>> {
-                if i64::try_from(period_no.get()).ok()? > i64::from(Self::PERIODS) {
-                    return None;
-                }
-
-                Some(DaySubdivison {
-                    index: i64::try_from(period_no.get()).ok()? - 1,
-                })
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+                v.parse().map_err(E::custom)
             }
-            pub fn index(&self) -> NonZeroU64 {
-                NonZeroU64::new(u64::try_from(self.index).unwrap() + 1).unwrap()
+            fn visit_u64<E: de::Error>(self, v: u64) -> Result<Self::Value, E> {
+                let number = u32::try_from(v).map_err(E::custom)?;
+                DaySubdivison::try_from(number).map_err(E::custom)
+            }
+            fn visit_i64<E: de::Error>(self, v: i64) -> Result<Self::Value, E> {
+                let number = u32::try_from(v).map_err(E::custom)?;
+                DaySubdivison::try_from(number).map_err(E::custom)
             }
         }
-    };
-}
 
-minutes_impl!(1);
-minutes_impl!(2);
-minutes_impl!(3);
-minutes_impl!(4);
-minutes_impl!(5);
-minutes_impl!(6);
-minutes_impl!(10);
-minutes_impl!(15);
-minutes_impl!(20);
-minutes_impl!(30);
-minutes_impl!(60);
-minutes_impl!(120);
-minutes_impl!(180);
-minutes_impl!(240);
-minutes_impl!(360);
-minutes_impl!(720);
-
-day_subdivision_impl!(1);
-day_subdivision_impl!(2);
-day_subdivision_impl!(3);
-day_subdivision_impl!(4);
-day_subdivision_impl!(5);
-day_subdivision_impl!(6);
-day_subdivision_impl!(10);
-day_subdivision_impl!(15);
-day_subdivision_impl!(20);
-day_subdivision_impl!(30);
-day_subdivision_impl!(60);
-day_subdivision_impl!(120);
-day_subdivision_impl!(180);
-day_subdivision_impl!(240);
-day_subdivision_impl!(360);
-day_subdivision_impl!(720);
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(Visitor)
+        } else {
+            deserializer.deserialize_u32(Visitor)
+        }
+    }
+}
 
+/// `DaySubdivison` is the position of a `Minutes<N>` within its day, counting from 1, for example
+/// the 37th of the 48 half hours. `N` must evenly divide a day, so this fails to compile:
+///
+/// ```compile_fail
+/// let periods = resolution::DaySubdivison::<7>::PERIODS;
+/// ```
 #[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub struct DaySubdivison<const N: u32> {
     index: i64,
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "alloc"))]
 mod tests {
     use super::*;
     use crate::TimeResolution;
+    use alloc::string::ToString;
 
     #[test]
     fn test_relative() {
@@ -402,13 +700,149 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_relative_any_divisor() {
+        // not one of the common lengths, but still evenly divides a day
+        assert_eq!(DaySubdivison::<12>::PERIODS, 120);
+        assert_eq!(DaySubdivison::<1440>::PERIODS, 1);
+
+        let date = chrono::NaiveDate::from_ymd_opt(2021, 1, 1).unwrap();
+        let base = Minutes::<12>::first_on_day(date, ());
+        for i in 0..240 {
+            let relative = base.succ_n(i).relative();
+            assert_eq!(relative.index().get(), i % 120 + 1);
+            assert_eq!(relative.on_date(date), base.succ_n(i % 120));
+        }
+        assert!(DaySubdivison::<12>::new(NonZeroU64::new(121).unwrap()).is_none());
+    }
+
+    #[test]
+    fn test_iter_on_date() {
+        let date = chrono::NaiveDate::from_ymd_opt(2021, 1, 1).unwrap();
+
+        let periods = DaySubdivison::<30>::all();
+        assert_eq!(periods.len(), 48);
+        let half_hours = Minutes::<30>::iter_on_date(date);
+        assert_eq!(half_hours.len(), 48);
+        for (sub, half_hour) in periods.zip(half_hours) {
+            assert_eq!(sub.on_date(date), half_hour);
+            assert_eq!(half_hour.relative(), sub);
+        }
+        assert_eq!(DaySubdivison::<30>::all().last().unwrap().index().get(), 48);
+        assert_eq!(
+            Minutes::<30>::iter_on_date(date)
+                .next_back()
+                .unwrap()
+                .succ(),
+            Minutes::<30>::first_on_day(date.succ_opt().unwrap(), ())
+        );
+    }
+
+    #[test]
+    fn test_subdivide() {
+        let half_hour = "2021-01-01 10:30 => 2021-01-01 11:00"
+            .parse::<Minutes<30>>()
+            .unwrap();
+        let five_minutes = half_hour.subdivide::<5>();
+        assert_eq!(five_minutes.len().get(), 6);
+        assert_eq!(
+            five_minutes.start().start_datetime(),
+            half_hour.start_datetime()
+        );
+        assert_eq!(
+            five_minutes.end().succ(),
+            half_hour.succ().subdivide().start()
+        );
+        assert_eq!(
+            half_hour.subdivide::<30>().start().start_datetime(),
+            half_hour.start_datetime()
+        );
+        assert_eq!(half_hour.subdivide::<1>().len().get(), 30);
+
+        // before the epoch the indexes are negative
+        let half_hour = "1969-12-31 23:30 => 1970-01-01 00:00"
+            .parse::<Minutes<30>>()
+            .unwrap();
+        assert_eq!(
+            half_hour.subdivide::<5>().start().start_datetime(),
+            half_hour.start_datetime()
+        );
+        assert_eq!(half_hour.subdivide::<5>().end().succ().to_monotonic(), 0);
+    }
+
+    #[test]
+    fn test_unix_seconds() {
+        let period = "2021-01-01 10:30 => 2021-01-01 11:00"
+            .parse::<Minutes<30>>()
+            .unwrap();
+        assert_eq!(
+            period.to_unix_seconds(),
+            period.start_datetime().timestamp()
+        );
+        assert_eq!(
+            Minutes::<30>::from_unix_seconds(period.to_unix_seconds() + 1799),
+            period
+        );
+        assert_eq!(Minutes::<5>::from_unix_seconds(0).to_monotonic(), 0);
+        assert_eq!(Minutes::<5>::from_unix_seconds(-1).to_unix_seconds(), -300);
+    }
+
+    #[test]
+    fn test_periods_per_day() {
+        fn check<R: SubDateResolution<Params = ()>>(expected: u32) {
+            assert_eq!(R::PERIODS_PER_DAY, expected);
+            assert_eq!(
+                R::exact_duration() * i32::try_from(R::PERIODS_PER_DAY).unwrap(),
+                Duration::days(1)
+            );
+            let date = NaiveDate::from_ymd_opt(2021, 3, 1).unwrap();
+            assert_eq!(
+                R::first_on_day(date, ()).between(R::last_on_day(date, ())) + 1,
+                i64::from(expected)
+            );
+        }
+        check::<Minutes<1>>(1440);
+        check::<Minutes<5>>(288);
+        check::<Minutes<30>>(48);
+        check::<Minutes<1440>>(1);
+        assert_eq!(Minutes::<30>::exact_duration(), Duration::minutes(30));
+    }
+
+    #[test]
+    fn test_day_subdivision_parse() {
+        let period = DaySubdivison::<30>::try_from(37).unwrap();
+        assert_eq!(period.number(), 37);
+        assert_eq!(period.to_string(), "period 37 of 48");
+        assert_eq!(period, "period 37 of 48".parse().unwrap());
+        assert_eq!(period, "37".parse().unwrap());
+
+        assert!(DaySubdivison::<30>::try_from(0).is_err());
+        assert!(DaySubdivison::<30>::try_from(49).is_err());
+        assert!(DaySubdivison::<30>::try_from(48).is_ok());
+        assert!("period 37 of 24".parse::<DaySubdivison<30>>().is_err());
+        assert!("period 37".parse::<DaySubdivison<30>>().is_err());
+        assert!("period 49 of 48".parse::<DaySubdivison<30>>().is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_day_subdivision_serde() {
+        let period = DaySubdivison::<5>::try_from(288).unwrap();
+        let json = serde_json::to_string(&period).unwrap();
+        assert_eq!(json, "\"period 288 of 288\"");
+        assert_eq!(period, serde_json::from_str(&json).unwrap());
+        assert_eq!(period, serde_json::from_str("288").unwrap());
+        assert!(serde_json::from_str::<DaySubdivison<5>>("289").is_err());
+        assert!(serde_json::from_str::<DaySubdivison<5>>("-1").is_err());
+    }
+
     #[cfg(feature = "serde")]
     #[test]
     fn test_roundtrip() {
         use crate::SubDateResolution;
 
         let dt = chrono::NaiveDate::from_ymd_opt(2021, 12, 6).unwrap();
-        let tm = dt.and_time(NaiveTime::MIN).and_utc();
+        let tm = dt.and_time(chrono::NaiveTime::MIN).and_utc();
 
         let min = Minutes::<1>::from(tm);
         assert!(min.occurs_on_date() == dt);
@@ -521,5 +955,188 @@ mod tests {
                 .and_utc()
                 .into(),
         );
+
+        assert!(matches!(
+            "2021-01-01 1x:05".parse::<Minutes<1>>(),
+            Err(Error::ParseIntDetailed {
+                field: "hour",
+                position: 11,
+                ..
+            })
+        ));
+        assert!(matches!(
+            "2021-01-01 10:00 => 2021-01-01 10:6x".parse::<Minutes<5>>(),
+            Err(Error::ParseIntDetailed {
+                field: "minute",
+                position: 34,
+                ..
+            })
+        ));
+        assert!(matches!(
+            "2021-02-30 10:00".parse::<Minutes<1>>(),
+            Err(Error::ParseDateInternal { position: 0, .. })
+        ));
+        assert!(matches!(
+            "2021-01-01T10:00".parse::<Minutes<1>>(),
+            Err(Error::ParseDateInternal { position: 10, .. })
+        ));
+        assert!(matches!(
+            "2021-01-01".parse::<Minutes<1>>(),
+            Err(Error::UnexpectedInputLength {
+                required: 16,
+                actual: 10,
+                ..
+            })
+        ));
+        assert_eq!(
+            "2021-01-01 10:6x".parse::<Minutes<1>>().unwrap_err().to_string(),
+            "Error parsing minute field at position 14 of 2021-01-01 10:6x as integer using format %Y-%m-%d %H:%M: invalid digit found in string"
+        );
+    }
+
+    #[test]
+    fn test_from_rfc3339() {
+        let expected = "2021-01-01 00:05 => 2021-01-01 00:10"
+            .parse::<Minutes<5>>()
+            .unwrap();
+        for input in [
+            "2021-01-01T00:05:00Z",
+            "2021-01-01T00:09:59.999Z",
+            "2021-01-01T10:07:30+10:00",
+            "2020-12-31t19:35:00-04:30",
+        ] {
+            assert_eq!(
+                Minutes::<5>::from_rfc3339(input).unwrap(),
+                expected,
+                "{input}"
+            );
+        }
+        assert_eq!(
+            Minutes::<1>::from_rfc3339("2021-01-01T10:07:30+10:00").unwrap(),
+            "2021-01-01 00:07".parse().unwrap()
+        );
+        for input in [
+            "2021-01-01 00:05",
+            "2021-01-01T00:05:00",
+            "2021-01-01T25:00:00Z",
+        ] {
+            assert!(Minutes::<5>::from_rfc3339(input).is_err(), "{input}");
+        }
+    }
+
+    #[test]
+    fn test_pre_epoch() {
+        let base = "1969-12-31 23:00".parse::<Minutes<1>>().unwrap();
+        assert_eq!(base.to_monotonic(), -60);
+        assert_eq!(base.to_string(), "1969-12-31 23:00");
+        assert_eq!(base.relative().index().get(), 1381);
+        assert_eq!(base.relative().on_date(base.occurs_on_date()), base);
+
+        let base = "1969-12-31 23:30 => 1970-01-01 00:00"
+            .parse::<Minutes<30>>()
+            .unwrap();
+        assert_eq!(base.to_monotonic(), -1);
+        assert_eq!(base.relative().index().get(), 48);
+        assert_eq!(base.succ().relative().index().get(), 1);
+
+        for year in [-1, 0, 500, 1969, 10000] {
+            let min = Minutes::<5>::from(
+                chrono::NaiveDate::from_ymd_opt(year, 6, 15)
+                    .unwrap()
+                    .and_hms_opt(10, 5, 0)
+                    .unwrap()
+                    .and_utc(),
+            );
+            assert_eq!(min, min.to_string().parse().unwrap());
+            assert_eq!(min.relative().index().get(), 122);
+        }
+        assert_eq!(
+            "-0001-06-15 10:05"
+                .parse::<Minutes<1>>()
+                .unwrap()
+                .start_datetime(),
+            chrono::NaiveDate::from_ymd_opt(-1, 6, 15)
+                .unwrap()
+                .and_hms_opt(10, 5, 0)
+                .unwrap()
+                .and_utc()
+        );
+    }
+
+    #[test]
+    fn test_parse_no_panic() {
+        let valid = ["2021-01-01 10:05", "2021-01-01 10:05 => 2021-01-01 10:10"];
+        let mut inputs = alloc::vec::Vec::new();
+        for input in valid {
+            for i in 0..=input.len() {
+                inputs.push(input[..i].to_string());
+                inputs.push(input[i..].to_string());
+                for c in ['é', '€', '😀', '-', ' ', '0'] {
+                    let mut replaced = input.to_string();
+                    replaced.insert(i, c);
+                    inputs.push(replaced.clone());
+                    if i < input.len() {
+                        replaced.remove(i + c.len_utf8());
+                        inputs.push(replaced);
+                    }
+                }
+            }
+        }
+        // a simple pseudo-random generator, to avoid adding a dependency for fuzzing
+        let mut state = 0x2545_f491_4f6c_dd1d_u64;
+        let alphabet = ['0', '1', '9', '-', '+', ' ', ':', '=', '>', 'é', '😀'];
+        for _ in 0..10_000 {
+            let len = (state % 40) as usize;
+            let input = (0..len)
+                .map(|_| {
+                    state ^= state << 13;
+                    state ^= state >> 7;
+                    state ^= state << 17;
+                    alphabet[(state % alphabet.len() as u64) as usize]
+                })
+                .collect::<String>();
+            inputs.push(input);
+        }
+
+        for input in inputs {
+            // only checking that these don't panic
+            let _ = input.parse::<Minutes<1>>();
+            let _ = input.parse::<Minutes<5>>();
+        }
+    }
+
+    #[test]
+    fn test_parse_iso_interval() {
+        let five = "2021-01-01 10:00 => 2021-01-01 10:05"
+            .parse::<Minutes<5>>()
+            .unwrap();
+        assert_eq!(format!("{five:#}"), "2021-01-01T10:00Z/2021-01-01T10:05Z");
+        assert_eq!(format!("{five:#}").parse::<Minutes<5>>().unwrap(), five);
+
+        let minute = "2021-01-01 10:00".parse::<Minutes<1>>().unwrap();
+        assert_eq!("2021-01-01T10:00Z".parse::<Minutes<1>>().unwrap(), minute);
+        assert_eq!(
+            "2021-01-01T10:00Z/2021-01-01T10:01Z"
+                .parse::<Minutes<1>>()
+                .unwrap(),
+            minute
+        );
+
+        for input in [
+            "2021-01-01T10:00Z/2021-01-01T10:10Z",
+            "2021-01-01T10:01Z/2021-01-01T10:06Z",
+            "2021-01-01T10:00/2021-01-01T10:05Z",
+            "2021-01-01 10:00Z/2021-01-01T10:05Z",
+        ] {
+            assert!(input.parse::<Minutes<5>>().is_err(), "{input}");
+        }
+        assert!(matches!(
+            "2021-01-01T10:00Z/2021-01-01T1x:05Z".parse::<Minutes<5>>(),
+            Err(Error::ParseIntDetailed {
+                field: "hour",
+                position: 29,
+                ..
+            })
+        ));
     }
 }
@@ -1,22 +1,19 @@
 use crate::DateResolution;
-use alloc::{
-    fmt, format, str,
-    string::{String, ToString},
-};
+#[cfg(feature = "alloc")]
+use alloc::{borrow, format, string::String};
 use chrono::{DateTime, Datelike, NaiveDate, NaiveTime, Utc};
-use core::convert::TryFrom;
+use core::{convert::TryFrom, fmt, str};
 #[cfg(feature = "serde")]
 use serde::de;
 
+/// Serialized as a string for human readable formats, or otherwise as its monotonic `i64`.
 #[cfg(feature = "serde")]
 impl<'de> de::Deserialize<'de> for Month {
-    fn deserialize<D>(deserializer: D) -> std::result::Result<Month, D::Error>
+    fn deserialize<D>(deserializer: D) -> Result<Month, D::Error>
     where
         D: de::Deserializer<'de>,
     {
-        let s = String::deserialize(deserializer)?;
-        let date = s.parse::<Month>().map_err(serde::de::Error::custom)?;
-        Ok(date)
+        crate::compact::deserialize_readable(deserializer)
     }
 }
 
@@ -26,33 +23,42 @@ impl serde::Serialize for Month {
     where
         S: serde::Serializer,
     {
-        let s = self.to_string();
-        serializer.serialize_str(&s)
+        crate::compact::serialize_readable(self, serializer)
     }
 }
 
-fn month_num_from_name(name: &str) -> Result<u32, crate::Error> {
-    let num = match name {
-        "Jan" => 1,
-        "Feb" => 2,
-        "Mar" => 3,
-        "Apr" => 4,
-        "May" => 5,
-        "Jun" => 6,
-        "Jul" => 7,
-        "Aug" => 8,
-        "Sep" => 9,
-        "Oct" => 10,
-        "Nov" => 11,
-        "Dec" => 12,
-        n => {
-            return Err(crate::Error::ParseCustom {
-                ty_name: "Month",
-                input: format!("Unknown month name `{}`", n),
-            })
-        }
-    };
-    Ok(num)
+const MONTH_NAMES: [(&str, &str); 12] = [
+    ("Jan", "January"),
+    ("Feb", "February"),
+    ("Mar", "March"),
+    ("Apr", "April"),
+    ("May", "May"),
+    ("Jun", "June"),
+    ("Jul", "July"),
+    ("Aug", "August"),
+    ("Sep", "September"),
+    ("Oct", "October"),
+    ("Nov", "November"),
+    ("Dec", "December"),
+];
+
+// accepts abbreviated or full month names, ignoring case. The name is always at the start of the
+// `input`, which is only used for the error
+fn month_num_from_name(name: &str, input: &str, format: &'static str) -> Result<u32, crate::Error> {
+    MONTH_NAMES
+        .iter()
+        .position(|(short, long)| {
+            name.eq_ignore_ascii_case(short) || name.eq_ignore_ascii_case(long)
+        })
+        .map(|idx| idx as u32 + 1)
+        .ok_or_else(|| {
+            crate::Error::parse_date(
+                format_args!("unknown month name `{}`", name),
+                0,
+                input,
+                format,
+            )
+        })
 }
 
 fn month_name_from_num(month: chrono::Month) -> &'static str {
@@ -74,41 +80,81 @@ fn month_name_from_num(month: chrono::Month) -> &'static str {
 
 impl str::FromStr for Month {
     type Err = crate::Error;
+    /// Parses `Jan-2021` (as written by `Display`), `2021-01` and `January 2021`. Month names
+    /// may be abbreviated or in full, and are case-insensitive.
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut split = s.split('-');
-        let month =
-            month_num_from_name(split.next().ok_or_else(|| crate::Error::ParseCustom {
-                ty_name: "Month",
-                input: s.to_string(),
-            })?)?;
-        let year = split
-            .next()
-            .ok_or_else(|| crate::Error::ParseCustom {
-                ty_name: "Month",
-                input: s.to_string(),
-            })?
-            .parse()?;
-        let date = chrono::NaiveDate::from_ymd_opt(year, month, 1).expect("valid datetime");
+        // split on the last `-` for `2021-01`, otherwise on the first `-` as the year may be negative
+        let (month, year, year_position, format) = match s.rsplit_once('-') {
+            Some((year, month))
+                if month.len() == 2
+                    && month.bytes().all(|b| b.is_ascii_digit())
+                    && year.bytes().last().is_some_and(|b| b.is_ascii_digit()) =>
+            {
+                let month = month.parse().expect("two ASCII digits");
+                (month, year, 0, "%Y-%m")
+            }
+            _ => {
+                let (month, year, format) = if let Some((month, year)) = s.split_once(' ') {
+                    (month, year, "%B %Y")
+                } else if let Some((month, year)) = s.split_once('-') {
+                    (month, year, "%b-%Y")
+                } else {
+                    return Err(crate::Error::custom("Month", format_args!("{s}")));
+                };
+                (
+                    month_num_from_name(month, s, format)?,
+                    year,
+                    month.len() + 1,
+                    format,
+                )
+            }
+        };
+        let year = year
+            .parse()
+            .map_err(|source| crate::Error::parse_int(source, "year", year_position, s, format))?;
+        let date = chrono::NaiveDate::from_ymd_opt(year, month, 1).ok_or_else(|| {
+            crate::Error::custom(
+                "Month",
+                format_args!("Month or year out of range in `{}`", s),
+            )
+        })?;
         Ok(date.into())
     }
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[repr(transparent)]
 pub struct Month(i64); // number of months +- since 0AD
 
+// SAFETY: `Month` is `repr(transparent)` over an `i64`, so every bit pattern is a valid `Month`
+#[cfg(feature = "bytemuck")]
+unsafe impl bytemuck::Zeroable for Month {}
+#[cfg(feature = "bytemuck")]
+unsafe impl bytemuck::Pod for Month {}
+
 impl crate::TimeResolution for Month {
     fn succ_n(&self, n: u64) -> Self {
-        Month(self.0 + i64::try_from(n).unwrap())
+        self.checked_succ_n(n).expect("Month out of range")
     }
     fn pred_n(&self, n: u64) -> Self {
-        Month(self.0 - i64::try_from(n).unwrap())
+        self.checked_pred_n(n).expect("Month out of range")
+    }
+    fn checked_succ_n(&self, n: u64) -> Option<Self> {
+        self.0.checked_add_unsigned(n).map(Month)
+    }
+    fn checked_pred_n(&self, n: u64) -> Option<Self> {
+        self.0.checked_sub_unsigned(n).map(Month)
     }
     fn start_datetime(&self) -> DateTime<Utc> {
         self.start().and_time(NaiveTime::MIN).and_utc()
     }
 
-    fn name(&self) -> String {
-        "Month".to_string()
+    fn write_name(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        out.write_str("Month")
+    }
+    #[cfg(feature = "alloc")]
+    fn name(&self) -> borrow::Cow<'static, str> {
+        "Month".into()
     }
 }
 
@@ -127,11 +173,15 @@ impl crate::FromMonotonic for Month {
     }
 }
 
+impl crate::Bounded for Month {
+    const MIN: Month = Month(crate::MIN_YEAR * 12);
+    // November, as December is the last month supported
+    const MAX: Month = Month(crate::MAX_YEAR * 12 + 10);
+}
+
 impl crate::DateResolution for Month {
     fn start(&self) -> chrono::NaiveDate {
-        let years = i32::try_from(self.0.div_euclid(12)).expect("Not pre/post historic");
-        let months = u32::try_from(1 + self.0.rem_euclid(12)).expect("valid datetime");
-        chrono::NaiveDate::from_ymd_opt(years, months, 1).expect("valid datetime")
+        self.try_start().expect("Not pre/post historic")
     }
 
     type Params = ();
@@ -156,6 +206,8 @@ impl From<DateTime<Utc>> for Month {
 }
 
 impl Month {
+    /// The start date of the `Month` with a monotonic index of `0`, January of the year 0.
+    pub const EPOCH: NaiveDate = NaiveDate::from_ymd_opt(0, 1, 1).expect("valid date");
     pub fn year(&self) -> super::Year {
         self.start().into()
     }
@@ -168,6 +220,9 @@ impl Month {
     pub fn month_num(&self) -> u32 {
         self.start().month()
     }
+    pub fn quarter_num(&self) -> u32 {
+        self.start().month0() / 3 + 1
+    }
     pub fn month(&self) -> chrono::Month {
         match self.month_num() {
             1 => chrono::Month::January,
@@ -188,15 +243,53 @@ impl Month {
     pub fn new(date: NaiveDate) -> Self {
         date.into()
     }
-    pub fn from_parts(year: i32, month: chrono::Month) -> Self {
-        crate::FromMonotonic::from_monotonic(
-            i64::from(year) + (i64::from(month.number_from_month()) - 1),
-        )
+    /// As `start`, but returning an error rather than panicking if the month is outside the
+    /// range supported by `chrono`.
+    pub fn try_start(&self) -> Result<NaiveDate, crate::Error> {
+        let year = self.0.div_euclid(12);
+        let months = u32::try_from(1 + self.0.rem_euclid(12)).expect("valid datetime");
+        i32::try_from(year)
+            .ok()
+            .and_then(|years| chrono::NaiveDate::from_ymd_opt(years, months, 1))
+            .ok_or(crate::Error::YearOutOfRange { year })
+    }
+    /// The ISO 8601 month, eg `2021-01`, as written by the alternate `Display`.
+    #[cfg(feature = "alloc")]
+    pub fn to_iso_string(&self) -> String {
+        format!("{self:#}")
+    }
+    /// The number of days in this month, from `28` to `31`.
+    pub fn num_days(&self) -> i64 {
+        match self.month() {
+            chrono::Month::February if self.year().is_leap() => 29,
+            chrono::Month::February => 28,
+            chrono::Month::April
+            | chrono::Month::June
+            | chrono::Month::September
+            | chrono::Month::November => 30,
+            _ => 31,
+        }
+    }
+    /// Every day of this month, in order.
+    pub fn days(&self) -> crate::TimeRange<super::Day> {
+        crate::DateResolutionExt::rescale(self)
+    }
+    /// The given `month` of `year`, or an error if the year is outside the range supported by
+    /// `chrono`.
+    pub fn from_parts(year: i32, month: chrono::Month) -> Result<Self, crate::Error> {
+        NaiveDate::from_ymd_opt(year, month.number_from_month(), 1)
+            .map(Month::from)
+            .ok_or(crate::Error::YearOutOfRange { year: year.into() })
     }
 }
 
 impl fmt::Display for Month {
+    /// The alternate form, `{:#}`, is ISO 8601, eg `2021-01`.
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            crate::write_iso_year(f, self.start().year().into())?;
+            return write!(f, "-{:02}", self.start().month());
+        }
         write!(
             f,
             "{}-{}",
@@ -206,10 +299,77 @@ impl fmt::Display for Month {
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "alloc"))]
 mod tests {
     use super::Month;
     use crate::{DateResolution, TimeResolution};
+    use alloc::string::ToString;
+
+    #[test]
+    fn test_iso_string() {
+        let month = "Jan-2021".parse::<Month>().unwrap();
+        assert_eq!(month.to_iso_string(), "2021-01");
+        assert_eq!(alloc::format!("{month:#}"), "2021-01");
+        assert_eq!(month.to_iso_string().parse::<Month>().unwrap(), month);
+        assert_eq!(
+            "Dec-21".parse::<Month>().unwrap().to_iso_string(),
+            "0021-12"
+        );
+        let month = "Oct--5".parse::<Month>().unwrap();
+        assert_eq!(month.to_iso_string(), "-0005-10");
+        assert_eq!(month.to_iso_string().parse::<Month>().unwrap(), month);
+    }
+
+    #[test]
+    fn test_from_parts() {
+        // previously the year wasn't multiplied by the number of months in a year
+        assert_eq!(
+            Month::from_parts(2021, chrono::Month::January)
+                .unwrap()
+                .to_string(),
+            "Jan-2021"
+        );
+        assert_eq!(
+            Month::from_parts(2021, chrono::Month::December)
+                .unwrap()
+                .to_string(),
+            "Dec-2021"
+        );
+        assert_eq!(
+            Month::from_parts(-1, chrono::Month::March).unwrap().start(),
+            chrono::NaiveDate::from_ymd_opt(-1, 3, 1).unwrap()
+        );
+        assert!(matches!(
+            Month::from_parts(i32::MIN, chrono::Month::June),
+            Err(crate::Error::YearOutOfRange { .. })
+        ));
+    }
+
+    #[test]
+    fn test_num_days() {
+        let mut month = "Jan-1999".parse::<Month>().unwrap();
+        for _ in 0..36 {
+            assert_eq!(month.num_days(), crate::DateResolutionExt::num_days(&month));
+            month = month.succ();
+        }
+        assert_eq!("Feb-2000".parse::<Month>().unwrap().num_days(), 29);
+        assert_eq!("Feb-1900".parse::<Month>().unwrap().num_days(), 28);
+    }
+
+    #[test]
+    fn test_days() {
+        let month = "Feb-2020".parse::<Month>().unwrap();
+        let days = month.days();
+        assert_eq!(days.len().get(), 29);
+        assert_eq!(days.start().start(), month.start());
+        assert_eq!(days.end().succ().start(), month.succ().start());
+        assert_eq!(
+            days.iter()
+                .map(|day| day.day_num())
+                .collect::<alloc::vec::Vec<_>>(),
+            (1..=29).collect::<alloc::vec::Vec<_>>()
+        );
+    }
 
     #[test]
     #[cfg(feature = "serde")]
@@ -247,6 +407,61 @@ mod tests {
             "Jan-2021".parse::<Month>().unwrap().succ().pred().start(),
             chrono::NaiveDate::from_ymd_opt(2021, 1, 1).unwrap(),
         );
+
+        let jan = "Jan-2021".parse::<Month>().unwrap();
+        for input in [
+            "2021-01",
+            "January 2021",
+            "january 2021",
+            "JAN 2021",
+            "jan-2021",
+            "JANUARY-2021",
+        ] {
+            assert_eq!(input.parse::<Month>().unwrap(), jan, "{input}");
+        }
+        assert_eq!(
+            "-0001-12".parse::<Month>().unwrap(),
+            "Dec--0001".parse::<Month>().unwrap()
+        );
+        for input in [
+            "2021-13",
+            "2021-00",
+            "Janu-2021",
+            "Jan2021",
+            "Jan-999999999",
+            "",
+        ] {
+            assert!(input.parse::<Month>().is_err(), "{input}");
+        }
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = "Janu-2021".parse::<Month>().unwrap_err();
+        assert!(matches!(
+            &err,
+            crate::Error::ParseDateInternal {
+                position: 0,
+                format: "%b-%Y",
+                message,
+                input,
+            } if message.contains("`Janu`") && input == "Janu-2021"
+        ));
+        assert!(matches!(
+            "Sept 2021".parse::<Month>(),
+            Err(crate::Error::ParseDateInternal {
+                format: "%B %Y",
+                ..
+            })
+        ));
+        assert!(matches!(
+            "Sep-20x1".parse::<Month>(),
+            Err(crate::Error::ParseIntDetailed {
+                field: "year",
+                position: 4,
+                ..
+            })
+        ));
     }
 
     #[test]
@@ -288,4 +503,20 @@ mod tests {
             chrono::NaiveDate::from_ymd_opt(-2, 10, 1).unwrap()
         );
     }
+
+    #[test]
+    fn test_pre_epoch() {
+        for (year, mon) in [(-1, 12), (-1, 1), (0, 1), (500, 6), (1969, 12)] {
+            let m = Month::from(chrono::NaiveDate::from_ymd_opt(year, mon, 1).unwrap());
+            assert_eq!(m.year_num(), year);
+            assert_eq!(m.month_num(), mon);
+            assert_eq!(m.quarter_num(), m.quarter().quarter_num());
+            assert_eq!(m, m.to_string().parse().unwrap());
+        }
+        assert_eq!(
+            "Dec--1".parse::<Month>().unwrap().start(),
+            chrono::NaiveDate::from_ymd_opt(-1, 12, 1).unwrap()
+        );
+        assert_eq!("Dec--1".parse::<Month>().unwrap().succ(), Month(0));
+    }
 }
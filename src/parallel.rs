@@ -0,0 +1,126 @@
+use crate::{TimeRange, TimeRangeIter, TimeResolution};
+use rayon::iter::{
+    plumbing::{bridge, Consumer, Producer, ProducerCallback, UnindexedConsumer},
+    IndexedParallelIterator, IntoParallelIterator, ParallelIterator,
+};
+
+/// A parallel iterator over the periods of a `TimeRange`, which is split between threads by the
+/// offset of the periods within the range, without creating the periods in between.
+#[derive(Debug, Clone)]
+pub struct TimeRangeParIter<P: TimeResolution> {
+    range: TimeRange<P>,
+}
+
+impl<P: TimeResolution + Send> IntoParallelIterator for TimeRange<P> {
+    type Item = P;
+    type Iter = TimeRangeParIter<P>;
+    fn into_par_iter(self) -> Self::Iter {
+        TimeRangeParIter { range: self }
+    }
+}
+
+impl<P: TimeResolution + Send> ParallelIterator for TimeRangeParIter<P> {
+    type Item = P;
+    fn drive_unindexed<C>(self, consumer: C) -> C::Result
+    where
+        C: UnindexedConsumer<Self::Item>,
+    {
+        bridge(self, consumer)
+    }
+    fn opt_len(&self) -> Option<usize> {
+        usize::try_from(self.range.len().get()).ok()
+    }
+}
+
+impl<P: TimeResolution + Send> IndexedParallelIterator for TimeRangeParIter<P> {
+    /// Panics if the range has more than `usize::MAX` periods.
+    fn len(&self) -> usize {
+        usize::try_from(self.range.len().get()).expect("Range length fits in usize")
+    }
+    fn drive<C: Consumer<Self::Item>>(self, consumer: C) -> C::Result {
+        bridge(self, consumer)
+    }
+    fn with_producer<CB: ProducerCallback<Self::Item>>(self, callback: CB) -> CB::Output {
+        let end = self.range.len().get();
+        callback.callback(TimeRangeProducer {
+            range: self.range,
+            start: 0,
+            end,
+        })
+    }
+}
+
+// the periods from offset `start` up to but not including offset `end` of `range`. Unlike a
+// `TimeRange`, this can be empty, as rayon may split at either end
+struct TimeRangeProducer<P: TimeResolution> {
+    range: TimeRange<P>,
+    start: u64,
+    end: u64,
+}
+
+impl<P: TimeResolution + Send> Producer for TimeRangeProducer<P> {
+    type Item = P;
+    type IntoIter = TimeRangeIter<P>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.range.iter_offsets(self.start, self.end)
+    }
+    fn split_at(self, index: usize) -> (Self, Self) {
+        let mid = self.start + index as u64;
+        (
+            TimeRangeProducer {
+                range: self.range,
+                start: self.start,
+                end: mid,
+            },
+            TimeRangeProducer {
+                range: self.range,
+                start: mid,
+                end: self.end,
+            },
+        )
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+    use crate::{Day, FromMonotonic, Minute, Monotonic};
+    use alloc::vec::Vec;
+    use core::num::NonZeroU64;
+
+    #[test]
+    fn test_par_iter() {
+        let range = TimeRange::new(
+            Minute::from_monotonic(-500),
+            NonZeroU64::new(10_000).unwrap(),
+        );
+        let sequential = range.iter().collect::<Vec<_>>();
+        assert_eq!(range.into_par_iter().collect::<Vec<_>>(), sequential);
+        assert_eq!(range.into_par_iter().len(), 10_000);
+        assert_eq!(
+            range.into_par_iter().map(|p| p.to_monotonic()).sum::<i64>(),
+            sequential.iter().map(|p| p.to_monotonic()).sum::<i64>()
+        );
+
+        // adaptors which split at either end of the range
+        assert_eq!(range.into_par_iter().skip(10_000).count(), 0);
+        assert_eq!(range.into_par_iter().take(0).count(), 0);
+        assert_eq!(
+            range.into_par_iter().skip(9_999).collect::<Vec<_>>(),
+            [range.end()]
+        );
+        assert_eq!(
+            range.into_par_iter().rev().collect::<Vec<_>>(),
+            sequential.iter().rev().copied().collect::<Vec<_>>()
+        );
+
+        let days = TimeRange::new(Day::from_monotonic(0), NonZeroU64::new(3).unwrap());
+        assert_eq!(
+            days.into_par_iter()
+                .zip(range.into_par_iter())
+                .map(|(d, m)| d.to_monotonic() + m.to_monotonic())
+                .collect::<Vec<_>>(),
+            [-500, -498, -496]
+        );
+    }
+}
@@ -0,0 +1,145 @@
+//! `sqlx` support for Postgres, where date resolutions are stored as the `DATE` of the start of
+//! the period, and `Minutes` as the `TIMESTAMPTZ` of the start of the period.
+//!
+//! Decoding fails if the value is not the start of a period, for example a `Month` from a
+//! `DATE` which is not the first of the month.
+use crate::{DateResolution, Day, Minutes, Month, Quarter, StartDay, TimeResolution, Week, Year};
+use alloc::format;
+use chrono::{DateTime, NaiveDate, Utc};
+use sqlx::{
+    encode::IsNull,
+    error::BoxDynError,
+    postgres::{PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueRef},
+    Decode, Encode, Postgres, Type,
+};
+
+fn date_period<P: DateResolution + From<NaiveDate>>(
+    date: NaiveDate,
+    ty_name: &'static str,
+) -> Result<P, crate::Error> {
+    let period = P::from(date);
+    if period.start() != date {
+        return Err(crate::Error::ParseCustom {
+            ty_name,
+            input: format!("{} is not the start of a {}", date, period.name()),
+        });
+    }
+    Ok(period)
+}
+
+fn minutes_period<const N: u32>(datetime: DateTime<Utc>) -> Result<Minutes<N>, crate::Error> {
+    let period = Minutes::<N>::from(datetime);
+    if period.start_datetime() != datetime {
+        return Err(crate::Error::ParseCustom {
+            ty_name: "Minutes",
+            input: format!("{} is not the start of a {}", datetime, period.name()),
+        });
+    }
+    Ok(period)
+}
+
+macro_rules! impl_date {
+    ([$($generics:tt)*] $ty:ty, $name:literal) => {
+        impl<$($generics)*> Type<Postgres> for $ty {
+            fn type_info() -> PgTypeInfo {
+                <NaiveDate as Type<Postgres>>::type_info()
+            }
+        }
+
+        impl<$($generics)*> PgHasArrayType for $ty {
+            fn array_type_info() -> PgTypeInfo {
+                <NaiveDate as PgHasArrayType>::array_type_info()
+            }
+        }
+
+        impl<$($generics)*> Encode<'_, Postgres> for $ty {
+            fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+                Encode::<Postgres>::encode(self.start(), buf)
+            }
+            fn size_hint(&self) -> usize {
+                Encode::<Postgres>::size_hint(&self.start())
+            }
+        }
+
+        impl<'r, $($generics)*> Decode<'r, Postgres> for $ty {
+            fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+                let date = <NaiveDate as Decode<Postgres>>::decode(value)?;
+                Ok(date_period(date, $name)?)
+            }
+        }
+    };
+}
+
+impl_date!([] Day, "Day");
+impl_date!([D: StartDay] Week<D>, "Week");
+impl_date!([] Month, "Month");
+impl_date!([] Quarter, "Quarter");
+impl_date!([] Year, "Year");
+
+impl<const N: u32> Type<Postgres> for Minutes<N> {
+    fn type_info() -> PgTypeInfo {
+        <DateTime<Utc> as Type<Postgres>>::type_info()
+    }
+}
+
+impl<const N: u32> PgHasArrayType for Minutes<N> {
+    fn array_type_info() -> PgTypeInfo {
+        <DateTime<Utc> as PgHasArrayType>::array_type_info()
+    }
+}
+
+impl<const N: u32> Encode<'_, Postgres> for Minutes<N> {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+        Encode::<Postgres>::encode(self.start_datetime(), buf)
+    }
+    fn size_hint(&self) -> usize {
+        Encode::<Postgres>::size_hint(&self.start_datetime())
+    }
+}
+
+impl<'r, const N: u32> Decode<'r, Postgres> for Minutes<N> {
+    fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+        let datetime = <DateTime<Utc> as Decode<Postgres>>::decode(value)?;
+        Ok(minutes_period(datetime)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Hour, Monday, Sunday};
+
+    #[test]
+    fn test_postgres_types() {
+        assert!(Day::compatible(&PgTypeInfo::with_name("DATE")));
+        assert!(Week::<Monday>::compatible(&PgTypeInfo::with_name("DATE")));
+        assert!(Quarter::compatible(&PgTypeInfo::with_name("DATE")));
+        assert!(Hour::compatible(&PgTypeInfo::with_name("TIMESTAMPTZ")));
+        assert!(!Hour::compatible(&PgTypeInfo::with_name("DATE")));
+    }
+
+    #[test]
+    fn test_decode_alignment() {
+        let first = NaiveDate::from_ymd_opt(2021, 4, 1).unwrap();
+        let second = first.succ_opt().unwrap();
+        assert_eq!(
+            date_period::<Quarter>(first, "Quarter").unwrap(),
+            Quarter::from(first)
+        );
+        assert!(date_period::<Quarter>(second, "Quarter").is_err());
+        assert!(date_period::<Month>(second, "Month").is_err());
+        assert_eq!(
+            date_period::<Day>(second, "Day").unwrap(),
+            Day::from(second)
+        );
+        // 2021-04-04 is a Sunday
+        let sunday = NaiveDate::from_ymd_opt(2021, 4, 4).unwrap();
+        assert!(date_period::<Week<Sunday>>(sunday, "Week").is_ok());
+        assert!(date_period::<Week<Monday>>(sunday, "Week").is_err());
+
+        let start = first.and_hms_opt(10, 0, 0).unwrap().and_utc();
+        assert_eq!(minutes_period::<60>(start).unwrap(), Hour::from(start));
+        assert!(minutes_period::<60>(start + chrono::Duration::minutes(5)).is_err());
+        assert!(minutes_period::<5>(start + chrono::Duration::minutes(5)).is_ok());
+    }
+}
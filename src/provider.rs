@@ -0,0 +1,116 @@
+use crate::{Cache, CacheResponse, TimeRange, TimeResolution};
+use alloc::collections;
+use core::{fmt, future::Future};
+
+/// `CachedProvider` wraps a `Cache` and an async function which fetches the data for a
+/// `TimeRange`, so that only the parts of a request which are not already cached are fetched.
+pub struct CachedProvider<P, T, F>
+where
+    P: TimeResolution + fmt::Debug,
+    T: Send + fmt::Debug + Eq + Copy,
+{
+    cache: Cache<P, T>,
+    fetch: F,
+}
+
+impl<P, T, F, Fut, E> CachedProvider<P, T, F>
+where
+    P: TimeResolution + fmt::Debug,
+    T: Send + fmt::Debug + Eq + Copy,
+    F: FnMut(TimeRange<P>) -> Fut,
+    Fut: Future<Output = Result<collections::BTreeMap<P, T>, E>>,
+{
+    pub fn new(cache: Cache<P, T>, fetch: F) -> CachedProvider<P, T, F> {
+        CachedProvider { cache, fetch }
+    }
+    pub fn cache(&self) -> &Cache<P, T> {
+        &self.cache
+    }
+    pub fn into_cache(self) -> Cache<P, T> {
+        self.cache
+    }
+    /// Get all the data for `range`, fetching the missing ranges in order and adding them to
+    /// the cache.
+    ///
+    /// If any fetch fails, the error is returned, but the data from fetches which have already
+    /// succeeded is kept in the cache.
+    pub async fn get_range(
+        &mut self,
+        range: TimeRange<P>,
+    ) -> Result<collections::BTreeMap<P, T>, E> {
+        let (mut data, missing) = match self.cache.get_range(range) {
+            CacheResponse::Hit(data) => return Ok(data),
+            CacheResponse::Partial { data, missing } => (data, missing),
+            CacheResponse::Miss(missing) => (collections::BTreeMap::new(), missing),
+        };
+        for missing in missing {
+            let fetched = (self.fetch)(missing).await?;
+            // kept in the result directly, as it may be evicted from the cache straight away
+            data.extend(
+                fetched
+                    .iter()
+                    .filter(|(k, _)| range.contains(**k))
+                    .map(|(k, v)| (*k, *v)),
+            );
+            self.cache.add_range(missing, fetched);
+        }
+        Ok(data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Day, Monotonic};
+    use alloc::vec::Vec;
+    use core::{pin, task};
+
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let mut future = pin::pin!(future);
+        let mut cx = task::Context::from_waker(task::Waker::noop());
+        loop {
+            if let task::Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+                return output;
+            }
+        }
+    }
+
+    #[test]
+    fn test_cached_provider() {
+        let base = "2021-01-01".parse::<Day>().unwrap();
+        let first = TimeRange::from_unordered_bounds(base, base.succ_n(4));
+        let second = TimeRange::from_unordered_bounds(base.succ_n(2), base.succ_n(7));
+
+        let mut fetched = Vec::new();
+        let mut provider = CachedProvider::new(Cache::<Day, u32>::empty(), |range| {
+            fetched.push(range);
+            async move {
+                Ok::<_, ()>(
+                    range
+                        .iter()
+                        .map(|d| (d, (d.to_monotonic() - base.to_monotonic()) as u32))
+                        .collect(),
+                )
+            }
+        });
+
+        let data = block_on(provider.get_range(first)).unwrap();
+        assert_eq!(
+            data.keys().copied().collect::<Vec<_>>(),
+            first.iter().collect::<Vec<_>>()
+        );
+        let data = block_on(provider.get_range(second)).unwrap();
+        assert_eq!(data.len(), 6);
+        assert_eq!(data[&base.succ_n(7)], 7);
+        drop(provider);
+
+        // only the missing days are fetched the second time
+        assert_eq!(
+            fetched,
+            [
+                first,
+                TimeRange::from_unordered_bounds(base.succ_n(5), base.succ_n(7))
+            ]
+        );
+    }
+}
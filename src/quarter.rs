@@ -1,30 +1,45 @@
 use crate::{month, year, DateResolution, DateResolutionExt};
-use alloc::{
-    fmt, str,
-    string::{String, ToString},
-    vec::Vec,
-};
+#[cfg(feature = "alloc")]
+use alloc::borrow;
 use chrono::{DateTime, Datelike, NaiveDate, NaiveTime, Utc};
 use core::convert::TryFrom;
+use core::{fmt, str};
 #[cfg(feature = "serde")]
 use serde::de;
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[repr(transparent)]
 pub struct Quarter(i64);
 
+// SAFETY: `Quarter` is `repr(transparent)` over an `i64`, so every bit pattern is a valid `Quarter`
+#[cfg(feature = "bytemuck")]
+unsafe impl bytemuck::Zeroable for Quarter {}
+#[cfg(feature = "bytemuck")]
+unsafe impl bytemuck::Pod for Quarter {}
+
 impl crate::TimeResolution for Quarter {
     fn succ_n(&self, n: u64) -> Self {
-        Quarter(self.0 + i64::try_from(n).unwrap())
+        self.checked_succ_n(n).expect("Quarter out of range")
     }
     fn pred_n(&self, n: u64) -> Self {
-        Quarter(self.0 - i64::try_from(n).unwrap())
+        self.checked_pred_n(n).expect("Quarter out of range")
+    }
+    fn checked_succ_n(&self, n: u64) -> Option<Self> {
+        self.0.checked_add_unsigned(n).map(Quarter)
+    }
+    fn checked_pred_n(&self, n: u64) -> Option<Self> {
+        self.0.checked_sub_unsigned(n).map(Quarter)
     }
     fn start_datetime(&self) -> DateTime<Utc> {
         self.start().and_time(NaiveTime::MIN).and_utc()
     }
 
-    fn name(&self) -> String {
-        "Quarter".to_string()
+    fn write_name(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        out.write_str("Quarter")
+    }
+    #[cfg(feature = "alloc")]
+    fn name(&self) -> borrow::Cow<'static, str> {
+        "Quarter".into()
     }
 }
 
@@ -43,11 +58,15 @@ impl crate::FromMonotonic for Quarter {
     }
 }
 
+impl crate::Bounded for Quarter {
+    const MIN: Quarter = Quarter(crate::MIN_YEAR * 4);
+    // Q3, as Q4 is the last quarter supported
+    const MAX: Quarter = Quarter(crate::MAX_YEAR * 4 + 2);
+}
+
 impl crate::DateResolution for Quarter {
     fn start(&self) -> chrono::NaiveDate {
-        let years = i32::try_from(self.0.div_euclid(4)).expect("Not pre/post historic");
-        let qtr = self.quarter_num();
-        chrono::NaiveDate::from_ymd_opt(years, qtr * 3 - 2, 1).expect("valid time")
+        self.try_start().expect("Not pre/post historic")
     }
 
     type Params = ();
@@ -64,6 +83,12 @@ impl From<NaiveDate> for Quarter {
     }
 }
 
+impl From<DateTime<Utc>> for Quarter {
+    fn from(d: DateTime<Utc>) -> Self {
+        d.date_naive().into()
+    }
+}
+
 fn quarter_num(d: chrono::NaiveDate) -> i64 {
     match d.month() {
         1..=3 => 1,
@@ -75,6 +100,9 @@ fn quarter_num(d: chrono::NaiveDate) -> i64 {
 }
 
 impl Quarter {
+    /// The start date of the `Quarter` with a monotonic index of `0`, the first quarter of the
+    /// year 0.
+    pub const EPOCH: NaiveDate = NaiveDate::from_ymd_opt(0, 1, 1).expect("valid date");
     pub fn first_month(&self) -> month::Month {
         self.start().into()
     }
@@ -93,39 +121,164 @@ impl Quarter {
     pub fn new(date: NaiveDate) -> Self {
         date.into()
     }
-    pub fn from_parts(year: i32, quarter: QuarterNumber) -> Self {
-        crate::FromMonotonic::from_monotonic(i64::from(year) + quarter.offset())
+    /// As `start`, but returning an error rather than panicking if the quarter is outside the
+    /// range supported by `chrono`.
+    pub fn try_start(&self) -> Result<NaiveDate, crate::Error> {
+        let year = self.0.div_euclid(4);
+        let qtr = self.quarter_num();
+        i32::try_from(year)
+            .ok()
+            .and_then(|years| chrono::NaiveDate::from_ymd_opt(years, qtr * 3 - 2, 1))
+            .ok_or(crate::Error::YearOutOfRange { year })
+    }
+    /// The number of days in this quarter, from `90` to `92`.
+    pub fn num_days(&self) -> i64 {
+        self.months().iter().map(|month| month.num_days()).sum()
+    }
+    /// Every day of this quarter, in order.
+    pub fn days(&self) -> crate::TimeRange<super::Day> {
+        self.rescale()
+    }
+    /// The three months of this quarter, in order.
+    pub fn months(&self) -> crate::TimeRange<month::Month> {
+        self.rescale()
+    }
+    /// The given `quarter`, from `1` to `4`, of `year`, or an error if either is out of range.
+    pub fn from_parts(year: i32, quarter: u8) -> Result<Self, crate::Error> {
+        if !(1..=4).contains(&quarter) {
+            return Err(crate::Error::InvalidQuarterNumber { number: quarter });
+        }
+        NaiveDate::from_ymd_opt(year, u32::from(quarter) * 3 - 2, 1)
+            .map(Quarter::from)
+            .ok_or(crate::Error::YearOutOfRange { year: year.into() })
     }
 }
 
-pub enum QuarterNumber {
-    Q1,
-    Q2,
-    Q3,
-    Q4,
+impl fmt::Display for Quarter {
+    /// The alternate form, `{:#}`, is `2021-Q1`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            crate::write_iso_year(f, self.year_num().into())?;
+            write!(f, "-Q{}", self.quarter_num())
+        } else {
+            write!(f, "Q{}-{}", self.quarter_num(), self.year_num())
+        }
+    }
 }
 
-impl QuarterNumber {
-    fn offset(&self) -> i64 {
-        match self {
-            QuarterNumber::Q1 => 0,
-            QuarterNumber::Q2 => 1,
-            QuarterNumber::Q3 => 2,
-            QuarterNumber::Q4 => 3,
-        }
+/// Serialized as a string for human readable formats, or otherwise as its monotonic `i64`.
+#[cfg(feature = "serde")]
+impl<'de> de::Deserialize<'de> for Quarter {
+    fn deserialize<D>(deserializer: D) -> Result<Quarter, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        crate::compact::deserialize_readable(deserializer)
     }
 }
 
-impl fmt::Display for Quarter {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "Q{}-{}", self.quarter_num(), self.year_num())
+#[cfg(feature = "serde")]
+impl serde::Serialize for Quarter {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        crate::compact::serialize_readable(self, serializer)
+    }
+}
+
+impl str::FromStr for Quarter {
+    type Err = crate::Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Ok(parsed) = chrono::NaiveDate::parse_from_str(s, "%Y-%m-%d") {
+            return Ok(parsed.into());
+        }
+        // split on the first `-` for `Q1-2021`, or the last `-` for `2021-Q1`,
+        // as the year may be negative
+        let (qtr, qtr_position, year, year_position, format) =
+            match (s.split_once('-'), s.rsplit_once('-')) {
+                (Some((qtr, year)), _) if qtr.starts_with('Q') => {
+                    (&qtr[1..], 1, year, qtr.len() + 1, "Q%q-%Y")
+                }
+                (_, Some((year, qtr))) if qtr.starts_with('Q') => {
+                    (&qtr[1..], year.len() + 2, year, 0, "%Y-Q%q")
+                }
+                _ => return Err(crate::Error::custom("Quarter", format_args!("{s}"))),
+            };
+        let qtr = qtr.parse::<u32>().map_err(|source| {
+            crate::Error::parse_int(source, "quarter", qtr_position, s, format)
+        })?;
+        if !(1..=4).contains(&qtr) {
+            return Err(crate::Error::custom(
+                "Quarter",
+                format_args!("Quarter number must be from 1 to 4, got {} in `{}`", qtr, s),
+            ));
+        }
+        let year = year
+            .parse()
+            .map_err(|source| crate::Error::parse_int(source, "year", year_position, s, format))?;
+        let date = chrono::NaiveDate::from_ymd_opt(year, qtr * 3 - 2, 1).ok_or_else(|| {
+            crate::Error::custom("Quarter", format_args!("Year out of range in `{}`", s))
+        })?;
+        Ok(date.into())
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "alloc"))]
 mod tests {
     use super::*;
     use crate::{DateResolution, TimeResolution};
+    use alloc::string::ToString;
+
+    #[test]
+    fn test_days() {
+        let days = "Q1-2020".parse::<Quarter>().unwrap().days();
+        assert_eq!(days.len().get(), 91);
+        assert_eq!(days.start().to_string(), "2020-01-01");
+        assert_eq!(days.end().to_string(), "2020-03-31");
+        assert_eq!("Q4-2021".parse::<Quarter>().unwrap().days().len().get(), 92);
+    }
+
+    #[test]
+    fn test_from_parts() {
+        // previously the year wasn't multiplied by the number of quarters in a year
+        assert_eq!(Quarter::from_parts(2021, 1).unwrap().to_string(), "Q1-2021");
+        assert_eq!(Quarter::from_parts(2021, 4).unwrap().to_string(), "Q4-2021");
+        assert_eq!(
+            Quarter::from_parts(-1, 2).unwrap().start(),
+            NaiveDate::from_ymd_opt(-1, 4, 1).unwrap()
+        );
+        for number in [0, 5, u8::MAX] {
+            assert!(matches!(
+                Quarter::from_parts(2021, number),
+                Err(crate::Error::InvalidQuarterNumber { number: n }) if n == number
+            ));
+        }
+        assert!(matches!(
+            Quarter::from_parts(i32::MAX, 1),
+            Err(crate::Error::YearOutOfRange { .. })
+        ));
+    }
+
+    #[test]
+    fn test_num_days() {
+        let mut quarter = "Q1-2019".parse::<Quarter>().unwrap();
+        for _ in 0..12 {
+            assert_eq!(quarter.num_days(), DateResolutionExt::num_days(&quarter));
+            quarter = quarter.succ();
+        }
+        assert_eq!("Q1-2020".parse::<Quarter>().unwrap().num_days(), 91);
+    }
+
+    #[test]
+    fn test_months() {
+        let quarter = "Q3-2021".parse::<Quarter>().unwrap();
+        let months = quarter.months();
+        assert_eq!(months.len().get(), 3);
+        assert_eq!(months.start(), quarter.first_month());
+        assert_eq!(months.end(), quarter.last_month());
+        assert_eq!(months.start().to_string(), "Jul-2021");
+    }
 
     #[test]
     #[cfg(feature = "serde")]
@@ -156,6 +309,59 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            "2021-Q3".parse::<Quarter>().unwrap(),
+            "Q3-2021".parse::<Quarter>().unwrap()
+        );
+        assert_eq!(
+            "-0001-Q4".parse::<Quarter>().unwrap(),
+            "Q4--0001".parse::<Quarter>().unwrap()
+        );
+        assert_eq!("-0001-Q4".parse::<Quarter>().unwrap(), Quarter(-1));
+
+        assert!(matches!(
+            "Q-2021".parse::<Quarter>(),
+            Err(crate::Error::ParseIntDetailed {
+                field: "quarter",
+                position: 1,
+                ..
+            })
+        ));
+        assert!(matches!(
+            "2021-Qx".parse::<Quarter>(),
+            Err(crate::Error::ParseIntDetailed {
+                field: "quarter",
+                position: 6,
+                ..
+            })
+        ));
+        assert!(matches!(
+            "Q1-20x1".parse::<Quarter>(),
+            Err(crate::Error::ParseIntDetailed {
+                field: "year",
+                position: 3,
+                ..
+            })
+        ));
+        for input in [
+            "Q9-2021",
+            "Q0-2021",
+            "2021-Q5",
+            "Q1-999999999",
+            "2021",
+            "",
+            "Q",
+        ] {
+            assert!(input.parse::<Quarter>().is_err(), "{input}");
+        }
+        assert_eq!(
+            "Q9-2021".parse::<Quarter>().unwrap_err().to_string(),
+            "Error parsing Quarter from input: Quarter number must be from 1 to 4, got 9 in `Q9-2021`"
+        );
+    }
+
     #[test]
     fn test_parse_date_syntax() {
         assert_eq!(
@@ -200,58 +406,24 @@ mod tests {
             chrono::NaiveDate::from_ymd_opt(-1, 7, 1).unwrap()
         );
     }
-}
 
-#[cfg(feature = "serde")]
-impl<'de> de::Deserialize<'de> for Quarter {
-    fn deserialize<D>(deserializer: D) -> std::result::Result<Quarter, D::Error>
-    where
-        D: de::Deserializer<'de>,
-    {
-        let s = String::deserialize(deserializer)?;
-        let date = s.parse::<Quarter>().map_err(serde::de::Error::custom)?;
-        Ok(date)
-    }
-}
-
-#[cfg(feature = "serde")]
-impl serde::Serialize for Quarter {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: serde::Serializer,
-    {
-        let s = self.to_string();
-        serializer.serialize_str(&s)
-    }
-}
-
-impl str::FromStr for Quarter {
-    type Err = crate::Error;
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if let Ok(parsed) = chrono::NaiveDate::parse_from_str(s, "%Y-%m-%d") {
-            Ok(parsed.into())
-        } else {
-            let split = s
-                .split('-')
-                .map(ToString::to_string)
-                .collect::<Vec<String>>();
-            if split.len() == 2 {
-                let qtr = split[0]
-                    .chars()
-                    .nth(1)
-                    .unwrap()
-                    .to_string()
-                    .parse::<u32>()?;
-                let year = split[1].parse()?;
-                let date =
-                    chrono::NaiveDate::from_ymd_opt(year, qtr * 3 - 2, 1).expect("valid date");
-                Ok(date.into())
-            } else {
-                Err(crate::Error::ParseCustom {
-                    ty_name: "Quarter",
-                    input: s.to_string(),
-                })
-            }
+    #[test]
+    fn test_pre_epoch() {
+        for (year, mon, qtr) in [
+            (-1, 12, 4),
+            (-1, 1, 1),
+            (0, 4, 2),
+            (500, 7, 3),
+            (1969, 12, 4),
+        ] {
+            let q = Quarter::from(chrono::NaiveDate::from_ymd_opt(year, mon, 1).unwrap());
+            assert_eq!(q.year_num(), year);
+            assert_eq!(q.quarter_num(), qtr);
+            assert_eq!(q, q.to_string().parse().unwrap());
         }
+        assert_eq!(
+            "Q4--1".parse::<Quarter>().unwrap().start(),
+            chrono::NaiveDate::from_ymd_opt(-1, 10, 1).unwrap()
+        );
     }
 }
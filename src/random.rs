@@ -0,0 +1,70 @@
+use crate::{TimeRange, TimeResolution};
+use alloc::vec::Vec;
+use core::num;
+use rand::{distributions::Distribution, seq::SliceRandom, Rng};
+
+/// Sample a period uniformly from the range
+impl<P: TimeResolution> Distribution<P> for TimeRange<P> {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> P {
+        self.start().succ_n(rng.gen_range(0..self.len().get()))
+    }
+}
+
+/// `SubRange` samples a range of one or more periods contained within a `TimeRange`, with the
+/// first and last periods each sampled uniformly from the range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SubRange<P: TimeResolution>(pub TimeRange<P>);
+
+impl<P: TimeResolution> Distribution<TimeRange<P>> for SubRange<P> {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> TimeRange<P> {
+        let len = self.0.len().get();
+        let a = rng.gen_range(0..len);
+        let b = rng.gen_range(0..len);
+        let start = self.0.start().succ_n(a.min(b));
+        TimeRange::new(
+            start,
+            num::NonZeroU64::new(a.abs_diff(b) + 1).expect("At least 1"),
+        )
+    }
+}
+
+impl<P: TimeResolution> TimeRange<P> {
+    /// All the periods in the range, in a random order.
+    pub fn shuffled<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec<P> {
+        let mut periods = self.iter().collect::<Vec<_>>();
+        periods.shuffle(rng);
+        periods
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Day;
+    use alloc::collections::BTreeSet;
+    use rand::{rngs::SmallRng, SeedableRng};
+
+    #[test]
+    fn test_sample() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let start = "2021-01-01".parse::<Day>().unwrap();
+        let range = TimeRange::from_unordered_bounds(start, start.succ_n(9));
+
+        let sampled = range
+            .sample_iter(&mut rng)
+            .take(1000)
+            .collect::<BTreeSet<_>>();
+        // every day is sampled, and nothing outside the range
+        assert_eq!(sampled, range.set());
+
+        for sub_range in SubRange(range).sample_iter(&mut rng).take(1000) {
+            assert!(range.contains_range(&sub_range));
+        }
+        let single = TimeRange::from_unordered_bounds(start, start);
+        assert_eq!(SubRange(single).sample(&mut rng), single);
+
+        let shuffled = range.shuffled(&mut rng);
+        assert_ne!(shuffled, range.iter().collect::<Vec<_>>());
+        assert_eq!(shuffled.into_iter().collect::<BTreeSet<_>>(), range.set());
+    }
+}
@@ -1,15 +1,23 @@
 use crate::{DateResolution, DateResolutionExt, FromMonotonic, SubDateResolution, TimeResolution};
-use alloc::{collections, fmt, vec::Vec};
-use core::{mem, num};
+#[cfg(feature = "alloc")]
+use alloc::{boxed::Box, collections, string::ToString, vec::Vec};
+use chrono::{DateTime, Utc};
+use core::str;
+#[cfg(feature = "alloc")]
+use core::sync::atomic;
+use core::{fmt, iter, num};
 #[cfg(feature = "serde")]
 use serde::de;
 
 /// `TimeRange` stores a contigious sequence of underlying periods of a given `TimeResolution`.
 ///
 /// This is useful to represent the time axis of a timeseries.
+///
+/// With `serde`, `Compact` writes a `TimeRange` as a fixed 16 bytes, for example to use ranges as
+/// keys in a key-value store.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
-pub struct TimeRange<P: TimeResolution> {
+pub struct TimeRange<P> {
     #[cfg_attr(
         feature = "serde",
         serde(bound(deserialize = "P: de::DeserializeOwned"))
@@ -38,65 +46,95 @@ impl<P: DateResolution> TimeRange<P> {
         // get last end
         let last_end = S::last_on_day(self.end().end(), self.end().params());
         // do from_start_end and expect it
-        TimeRange::from_bounds(first_start, last_end)
+        TimeRange::from_ordered_bounds(first_start, last_end)
     }
 }
 
 impl<P: TimeResolution + FromMonotonic> TimeRange<P> {
+    #[cfg(feature = "alloc")]
+    #[deprecated(note = "use `ranges_from_sorted` instead")]
     pub fn from_map(map: collections::BTreeSet<i64>) -> Vec<TimeRange<P>> {
-        let mut ranges = Vec::new();
-        if map.is_empty() {
-            return ranges;
-        }
+        TimeRange::ranges_from_sorted(map)
+    }
 
-        let mut iter = map.into_iter();
+    #[cfg(feature = "alloc")]
+    /// Group sorted monotonic indexes into the minimal set of contigious ranges, in order.
+    /// Repeated indexes are ignored.
+    pub fn ranges_from_sorted<I>(indexes: I) -> Vec<TimeRange<P>>
+    where
+        I: IntoIterator<Item = i64>,
+    {
+        let mut ranges = Vec::new();
+        // the first and last index of the range currently being built
+        let mut current: Option<(i64, i64)> = None;
 
-        let mut prev = match iter.next() {
-            Some(n) => n,
-            None => return ranges,
-        };
-        let mut current_range = TimeRange {
-            start: P::from_monotonic(prev),
-            len: num::NonZeroU64::new(1).unwrap(),
-        };
-        for val in iter {
-            if val == prev + 1 {
-                current_range.len =
-                    num::NonZeroU64::new(current_range.len.get().saturating_add(1)).unwrap();
-            } else {
-                let mut old_range = TimeRange {
-                    start: P::from_monotonic(val),
-                    len: num::NonZeroU64::new(1).unwrap(),
-                };
-                mem::swap(&mut current_range, &mut old_range);
-                if !ranges.contains(&old_range) {
-                    ranges.push(old_range);
+        for idx in indexes {
+            current = match current {
+                Some((start, end)) if end.checked_add(1) == Some(idx) => Some((start, idx)),
+                Some((start, end)) if (start..=end).contains(&idx) => Some((start, end)),
+                Some((start, end)) => {
+                    ranges.push(TimeRange::from_index_bounds(start, end));
+                    Some((idx, idx))
                 }
-            }
+                None => Some((idx, idx)),
+            };
+        }
 
-            prev = val;
+        if let Some((start, end)) = current {
+            ranges.push(TimeRange::from_index_bounds(start, end));
         }
 
         ranges
     }
+
+    #[cfg(feature = "alloc")]
+    fn from_index_bounds(start: i64, end: i64) -> TimeRange<P> {
+        TimeRange {
+            start: P::from_monotonic(start),
+            len: num::NonZeroU64::new(end.abs_diff(start) + 1).unwrap(),
+        }
+    }
 }
 
 impl<P: TimeResolution> TimeRange<P> {
+    #[cfg(feature = "alloc")]
+    /// Note that this allocates a node for every period in the range, prefer `indexes` where possible
     pub fn to_indexes(&self) -> collections::BTreeSet<i64> {
-        self.iter().map(|p| p.to_monotonic()).collect()
+        self.indexes().collect()
+    }
+
+    /// The monotonic index of each period in the range, in order
+    pub fn indexes(&self) -> impl DoubleEndedIterator<Item = i64> + ExactSizeIterator {
+        self.iter().map(|p| p.to_monotonic())
     }
 
+    #[cfg(feature = "alloc")]
+    /// Create a range from the periods in the set, returning `None` if the set is empty or
+    /// has any gaps between its periods.
     pub fn from_set(set: &collections::BTreeSet<P>) -> Option<TimeRange<P>> {
-        if u32::try_from(set.len()).is_err() {
-            return None;
-        }
-        if set.is_empty() {
-            return None;
+        TimeRange::try_from_iter(set.iter().copied()).ok()
+    }
+
+    /// Create a range from an iterator of periods, which must be sorted and have no gaps
+    /// between its periods (or repeated periods).
+    pub fn try_from_iter<I>(iter: I) -> Result<TimeRange<P>, crate::Error>
+    where
+        I: IntoIterator<Item = P>,
+    {
+        let mut iter = iter.into_iter();
+        let start = iter.next().ok_or(crate::Error::EmptyRange)?;
+        let mut end = start;
+        for period in iter {
+            let expected = end.succ();
+            if period != expected {
+                return Err(crate::Error::NonContiguous {
+                    expected: expected.start_datetime(),
+                    actual: period.start_datetime(),
+                });
+            }
+            end = period;
         }
-        Some(TimeRange {
-            start: set.iter().next().copied()?,
-            len: num::NonZeroU64::new(u64::try_from(set.len()).ok()?)?,
-        })
+        Ok(TimeRange::from_ordered_bounds(start, end))
     }
 
     pub fn maybe_new(start: P, len: u64) -> Option<TimeRange<P>> {
@@ -118,6 +156,37 @@ impl<P: TimeResolution> TimeRange<P> {
             )
         }
     }
+    /// Create the range from `start` to `end` inclusive, returning an error if `end` is before `start`
+    pub fn try_from_bounds(start: P, end: P) -> Result<TimeRange<P>, crate::Error> {
+        if start <= end {
+            Ok(TimeRange::from_ordered_bounds(start, end))
+        } else {
+            Err(crate::Error::ReversedBounds {
+                start: start.start_datetime(),
+                end: end.start_datetime(),
+            })
+        }
+    }
+    /// Create the range between the earlier and the later of `a` and `b`, inclusive
+    pub fn from_unordered_bounds(a: P, b: P) -> TimeRange<P> {
+        let (start, end) = if a <= b { (a, b) } else { (b, a) };
+        TimeRange::from_ordered_bounds(start, end)
+    }
+    // for bounds which are known to be ordered, so that a bug which reverses them panics rather
+    // than being hidden by swapping them
+    pub(crate) fn from_ordered_bounds(start: P, end: P) -> TimeRange<P> {
+        assert!(
+            start <= end,
+            "The start of a time range can't be after its end"
+        );
+        TimeRange {
+            start,
+            len: num::NonZeroU64::new(1 + u64::try_from(start.between(end)).unwrap()).unwrap(),
+        }
+    }
+    #[deprecated(
+        note = "when `b` is before `a` this creates a range starting at `a`, use `try_from_bounds` or `from_unordered_bounds` instead"
+    )]
     pub fn from_bounds(a: P, b: P) -> TimeRange<P> {
         if a <= b {
             TimeRange {
@@ -136,12 +205,23 @@ impl<P: TimeResolution> TimeRange<P> {
         self.len
     }
 
+    /// Divide the range into the periods before `idx` and the periods from `idx` onwards,
+    /// as per `slice::split_at`. As a `TimeRange` cannot be empty, this returns `None` unless
+    /// `idx` is strictly between zero and the length of the range.
+    pub fn split_at(&self, idx: u64) -> Option<(TimeRange<P>, TimeRange<P>)> {
+        let rest = self.len.get().checked_sub(idx)?;
+        Some((
+            TimeRange::maybe_new(self.start, idx)?,
+            TimeRange::maybe_new(self.start.succ_n(idx), rest)?,
+        ))
+    }
+
     pub fn intersection(&self, other: &TimeRange<P>) -> Option<TimeRange<P>> {
         let max_start = self.start().max(other.start());
         let min_end = self.end().min(other.end());
 
         if max_start <= min_end {
-            Some(TimeRange::from_bounds(max_start, min_end))
+            Some(TimeRange::from_ordered_bounds(max_start, min_end))
         } else {
             None
         }
@@ -150,12 +230,101 @@ impl<P: TimeResolution> TimeRange<P> {
         if self.intersection(other).is_some() {
             let min_start = self.start().min(other.start());
             let max_end = self.end().max(other.end());
-            Some(TimeRange::from_bounds(min_start, max_end))
+            Some(TimeRange::from_ordered_bounds(min_start, max_end))
         } else {
             None
         }
     }
 
+    /// Whether all of `other` is within this range, comparing by the UTC start and end of
+    /// each range, so that the ranges may be of different resolutions.
+    pub fn contains_range<O: TimeResolution>(&self, other: &TimeRange<O>) -> bool {
+        let (start, end) = self.utc_bounds();
+        let (other_start, other_end) = other.utc_bounds();
+        other_start >= start && other_end <= end
+    }
+    /// Whether any part of `other` is within this range, comparing by the UTC start and end of
+    /// each range, so that the ranges may be of different resolutions.
+    pub fn overlaps<O: TimeResolution>(&self, other: &TimeRange<O>) -> bool {
+        let (start, end) = self.utc_bounds();
+        let (other_start, other_end) = other.utc_bounds();
+        other_start < end && other_end > start
+    }
+    // start inclusive, end exclusive
+    fn utc_bounds(&self) -> (DateTime<Utc>, DateTime<Utc>) {
+        (
+            self.start.start_datetime(),
+            self.end().succ().start_datetime(),
+        )
+    }
+
+    /// The range of the same length, starting `n` periods later
+    pub fn shift_forward(&self, n: u64) -> TimeRange<P> {
+        TimeRange {
+            start: self.start.succ_n(n),
+            len: self.len,
+        }
+    }
+    /// The range of the same length, starting `n` periods earlier
+    pub fn shift_back(&self, n: u64) -> TimeRange<P> {
+        TimeRange {
+            start: self.start.pred_n(n),
+            len: self.len,
+        }
+    }
+    /// The range with the same start, and `n` more periods at the end
+    pub fn extend_by(&self, n: u64) -> TimeRange<P> {
+        TimeRange {
+            start: self.start,
+            len: self
+                .len
+                .checked_add(n)
+                .expect("Range length fits within u64"),
+        }
+    }
+    /// The range with the same start, and `n` fewer periods at the end, returning `None`
+    /// if that would leave the range empty
+    pub fn shrink_by(&self, n: u64) -> Option<TimeRange<P>> {
+        TimeRange::maybe_new(self.start, self.len.get().checked_sub(n)?)
+    }
+
+    /// Trim the range to the periods which are fully inside the window from `start` (inclusive)
+    /// to `end` (exclusive), returning `None` if there are no such periods.
+    pub fn clamp_to(&self, start: DateTime<Utc>, end: DateTime<Utc>) -> Option<TimeRange<P>> {
+        let first = self.partition_point(|i| self.start.succ_n(i).start_datetime() >= start);
+        let last = self.partition_point(|i| self.start.succ_n(i + 1).start_datetime() > end);
+        TimeRange::maybe_new(self.start.succ_n(first), last.checked_sub(first)?)
+    }
+
+    /// Trim the range to the periods which are at least partially inside the window from `start`
+    /// (inclusive) to `end` (exclusive), returning `None` if there are no such periods.
+    pub fn clamp_to_overlapping(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Option<TimeRange<P>> {
+        let first = self.partition_point(|i| self.start.succ_n(i + 1).start_datetime() > start);
+        let last = self.partition_point(|i| self.start.succ_n(i).start_datetime() >= end);
+        TimeRange::maybe_new(self.start.succ_n(first), last.checked_sub(first)?)
+    }
+
+    // find the index of the first period in the range for which `pred` holds, or the length
+    // of the range if there is no such period. `pred` must be false for all periods before
+    // the first period for which it is true.
+    fn partition_point(&self, pred: impl Fn(u64) -> bool) -> u64 {
+        let mut low = 0;
+        let mut high = self.len.get();
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if pred(mid) {
+                high = mid;
+            } else {
+                low = mid + 1;
+            }
+        }
+        low
+    }
+
     // pub fn subtract(&self, other: &TimeRange<P>) -> (Option<TimeRange<P>>, Option<TimeRange<P>>) {
     //     (
     //         {
@@ -184,138 +353,1643 @@ impl<P: TimeResolution> TimeRange<P> {
         self.start.succ_n(self.len.get() - 1)
     }
     pub fn contains(&self, rhs: P) -> bool {
-        rhs >= self.start && rhs <= self.end()
+        // only uses the monotonic indexes, rather than finding the end of the range
+        rhs.to_monotonic()
+            .checked_sub(self.start.to_monotonic())
+            .and_then(|offset| u64::try_from(offset).ok())
+            .is_some_and(|offset| offset < self.len.get())
     }
+    #[cfg(feature = "alloc")]
     pub fn set(&self) -> collections::BTreeSet<P> {
         self.iter().collect()
     }
     pub fn iter(&self) -> TimeRangeIter<P> {
         TimeRangeIter {
             current: self.start(),
-            end: self.end(),
+            remaining: self.len.get(),
+        }
+    }
+    // the periods from offset `start` up to but not including offset `end`, which may be empty
+    #[cfg(feature = "rayon")]
+    pub(crate) fn iter_offsets(&self, start: u64, end: u64) -> TimeRangeIter<P> {
+        TimeRangeIter {
+            current: if start < end {
+                self.start.succ_n(start)
+            } else {
+                self.start
+            },
+            remaining: end - start,
         }
     }
+    /// Split the range into consecutive sub-ranges of `chunk_len` periods, where only the last
+    /// chunk may be shorter. Each chunk can then be processed independently, eg on a thread pool.
+    pub fn chunks(&self, chunk_len: num::NonZeroU64) -> TimeRangeChunks<P> {
+        TimeRangeChunks {
+            remaining: Some(*self),
+            chunk_len,
+        }
+    }
+    /// Iterate over the range, provided it has no more than `max` periods.
+    ///
+    /// This is useful to reject unreasonably large requests before doing any work.
+    pub fn try_iter(&self, max: u64) -> Result<TimeRangeIter<P>, crate::Error> {
+        if self.len.get() > max {
+            Err(crate::Error::RangeTooLong {
+                max,
+                actual: self.len.get(),
+            })
+        } else {
+            Ok(self.iter())
+        }
+    }
+    /// The time between the start of the first period and the end of the last period, found
+    /// without iterating the range. This is exact in UTC, but note that for calendar based
+    /// resolutions the duration of the same number of periods can vary.
+    pub fn approximate_len_duration(&self) -> chrono::Duration {
+        self.end().succ().start_datetime() - self.start.start_datetime()
+    }
+}
+
+/// Written as an ISO 8601 interval from the start of the first period to the end of the last,
+/// eg `2021-01-01T10:00Z/2021-01-01T10:05Z` for five `Minute`s.
+impl<P: TimeResolution> fmt::Display for TimeRange<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        crate::minutes::format_iso_datetime(self.start.start_datetime(), f)?;
+        f.write_str("/")?;
+        crate::minutes::format_iso_datetime(self.end().succ().start_datetime(), f)
+    }
+}
+
+impl<P: SubDateResolution<Params = ()>> str::FromStr for TimeRange<P> {
+    type Err = crate::Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (start, end) = s
+            .split_once('/')
+            .ok_or_else(|| crate::Error::custom("TimeRange", format_args!("{s}")))?;
+        let start_len = start.len();
+        let start = crate::minutes::parse_iso_datetime(start).map_err(|e| e.within(0, s))?;
+        let end =
+            crate::minutes::parse_iso_datetime(end).map_err(|e| e.within(start_len + 1, s))?;
+
+        let first = P::from_utc_datetime(start, ());
+        let after_last = P::from_utc_datetime(end, ());
+        if first.start_datetime() != start || after_last.start_datetime() != end {
+            return Err(crate::Error::custom(
+                "TimeRange",
+                format_args!("Interval is not aligned to the start of periods: {}", s),
+            ));
+        }
+        TimeRange::try_from_bounds(first, after_last.pred())
+    }
 }
 
 pub struct TimeRangeIter<P: TimeResolution> {
+    // the next period to be returned from the front, only valid while `remaining` is non-zero
     current: P,
-    end: P,
+    remaining: u64,
 }
 
 impl<P: TimeResolution> Iterator for TimeRangeIter<P> {
     type Item = P;
     fn next(&mut self) -> Option<Self::Item> {
-        if self.current <= self.end {
-            let ret = self.current;
+        if self.remaining == 0 {
+            return None;
+        }
+        let ret = self.current;
+        self.remaining -= 1;
+        if self.remaining > 0 {
+            // don't step past the end of the range, so that we never create a period
+            // which the range doesn't contain
             self.current = self.current.succ();
-            Some(ret)
-        } else {
-            None
+        }
+        Some(ret)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match usize::try_from(self.remaining) {
+            Ok(remaining) => (remaining, Some(remaining)),
+            Err(_) => (usize::MAX, None),
+        }
+    }
+
+    // saturates at `usize::MAX` when the remaining length doesn't fit, as it can on 32-bit targets
+    fn count(self) -> usize {
+        usize::try_from(self.remaining).unwrap_or(usize::MAX)
+    }
+
+    fn last(mut self) -> Option<Self::Item> {
+        self.next_back()
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        match u64::try_from(n) {
+            Ok(n) if n < self.remaining => {
+                self.current = self.current.succ_n(n);
+                self.remaining -= n;
+                self.next()
+            }
+            _ => {
+                self.remaining = 0;
+                None
+            }
         }
     }
 }
 
-pub struct Cache<K: Ord + fmt::Debug + Copy, T: Send + fmt::Debug + Eq + Copy> {
-    // The actual data in the cache
-    data: collections::BTreeMap<K, T>,
-    // The requests for data which has been cached
-    requests: collections::BTreeSet<K>,
+impl<P: TimeResolution> DoubleEndedIterator for TimeRangeIter<P> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        Some(self.current.succ_n(self.remaining))
+    }
+
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        match u64::try_from(n) {
+            Ok(n) if n < self.remaining => {
+                self.remaining -= n;
+                self.next_back()
+            }
+            _ => {
+                self.remaining = 0;
+                None
+            }
+        }
+    }
 }
 
-// merge a request into a set of requests, grouping contigious on the way
-fn missing_pieces<K: Ord + fmt::Debug + Copy>(
-    request: collections::BTreeSet<K>,
-    requests: &collections::BTreeSet<K>,
-) -> Vec<collections::BTreeSet<K>> {
-    let mut to_request = Vec::new();
-    let mut current_request = collections::BTreeSet::new();
-
-    // there is a fundamental assumption that `request` is contigious
-    // as long as `request` is contigious, each of the returned requests
-    // will also be contigious
-    // there is no need to worry about filling gaps to reduce the total number
-    // of requests - the consumer will handle this
-    for requested in request {
-        if !requests.contains(&requested) {
-            current_request.insert(requested);
-        } else if !current_request.is_empty() {
-            to_request.push(mem::take(&mut current_request));
+/// `len` saturates at `usize::MAX` for ranges with more periods than fit in a `usize`, which is
+/// only possible on targets where `usize` is narrower than `u64`.
+impl<P: TimeResolution> ExactSizeIterator for TimeRangeIter<P> {
+    fn len(&self) -> usize {
+        usize::try_from(self.remaining).unwrap_or(usize::MAX)
+    }
+}
+
+impl<P: TimeResolution> iter::FusedIterator for TimeRangeIter<P> {}
+
+/// An iterator over consecutive sub-ranges of a `TimeRange`, see `TimeRange::chunks`.
+#[derive(Debug, Clone)]
+pub struct TimeRangeChunks<P: TimeResolution> {
+    remaining: Option<TimeRange<P>>,
+    chunk_len: num::NonZeroU64,
+}
+
+impl<P: TimeResolution> Iterator for TimeRangeChunks<P> {
+    type Item = TimeRange<P>;
+    fn next(&mut self) -> Option<Self::Item> {
+        let remaining = self.remaining.take()?;
+        match remaining.split_at(self.chunk_len.get()) {
+            Some((chunk, rest)) => {
+                self.remaining = Some(rest);
+                Some(chunk)
+            }
+            None => Some(remaining),
         }
     }
 
-    if !current_request.is_empty() {
-        to_request.push(current_request);
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let chunks = self
+            .remaining
+            .map(|r| r.len().get().div_ceil(self.chunk_len.get()))
+            .unwrap_or(0);
+        match usize::try_from(chunks) {
+            Ok(chunks) => (chunks, Some(chunks)),
+            Err(_) => (usize::MAX, None),
+        }
     }
+}
 
-    to_request
+impl<P: TimeResolution> iter::FusedIterator for TimeRangeChunks<P> {}
+
+#[cfg(feature = "alloc")]
+/// `TimeRangeSet` stores an arbitrary set of periods as the minimal collection of `TimeRange`s,
+/// such that no two of the stored ranges overlap or are adjacent to each other.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimeRangeSet<P> {
+    // keyed by the start of each range
+    ranges: collections::BTreeMap<P, TimeRange<P>>,
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+#[cfg(feature = "alloc")]
+impl<P: TimeResolution> Default for TimeRangeSet<P> {
+    fn default() -> Self {
+        TimeRangeSet::empty()
+    }
+}
 
-    #[test]
-    fn test_missing_pieces() {
-        let pieces = missing_pieces(
-            collections::BTreeSet::from([1, 2, 3, 4, 5, 6, 7, 8, 9, 10]),
-            &collections::BTreeSet::from([2, 3, 7, 8]),
-        );
-        assert_eq!(
-            pieces,
-            Vec::from([
-                collections::BTreeSet::from([1]),
-                collections::BTreeSet::from([4, 5, 6]),
-                collections::BTreeSet::from([9, 10]),
-            ])
-        )
+#[cfg(feature = "alloc")]
+impl<P: TimeResolution> FromIterator<TimeRange<P>> for TimeRangeSet<P> {
+    fn from_iter<I: IntoIterator<Item = TimeRange<P>>>(iter: I) -> Self {
+        let mut set = TimeRangeSet::empty();
+        for range in iter {
+            set.insert(range);
+        }
+        set
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<P: TimeResolution> TimeRangeSet<P> {
+    pub fn empty() -> TimeRangeSet<P> {
+        TimeRangeSet {
+            ranges: collections::BTreeMap::new(),
+        }
+    }
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+    pub fn insert(&mut self, range: TimeRange<P>) {
+        let mut start = range.start();
+        let mut end = range.end();
+
+        // as the stored ranges are disjoint, those touching the new range form a contigious
+        // run, ending with the last range starting no later than the period after `end`
+        let touching = self
+            .ranges
+            .range(..=end.succ())
+            .rev()
+            .take_while(|(_, existing)| existing.end().succ() >= start)
+            .map(|(key, _)| *key)
+            .collect::<Vec<_>>();
+
+        for key in touching {
+            if let Some(existing) = self.ranges.remove(&key) {
+                start = start.min(existing.start());
+                end = end.max(existing.end());
+            }
+        }
+
+        self.ranges
+            .insert(start, TimeRange::from_ordered_bounds(start, end));
+    }
+    pub fn contains(&self, point: P) -> bool {
+        self.ranges
+            .range(..=point)
+            .next_back()
+            .is_some_and(|(_, range)| range.contains(point))
+    }
+    pub fn iter(&self) -> impl Iterator<Item = TimeRange<P>> + '_ {
+        self.ranges.values().copied()
+    }
+    /// Remove all periods before `cutoff` from the set, truncating any range which spans it.
+    pub fn remove_before(&mut self, cutoff: P) {
+        let mut kept = self.ranges.split_off(&cutoff);
+        // the last range starting before the cutoff may extend past it
+        if let Some((_, range)) = self.ranges.pop_last() {
+            if range.end() >= cutoff {
+                kept.insert(cutoff, TimeRange::from_ordered_bounds(cutoff, range.end()));
+            }
+        }
+        self.ranges = kept;
+    }
+}
+
+#[cfg(feature = "alloc")]
+/// Find the parts of the `requested` range which are not covered by `have`.
+///
+/// The returned ranges are in order and none of them are adjacent to each other.
+pub fn missing_ranges<P: TimeResolution>(
+    requested: TimeRange<P>,
+    have: &TimeRangeSet<P>,
+) -> Vec<TimeRange<P>> {
+    let mut missing = Vec::new();
+    let end = requested.end();
+
+    // the earliest period of `requested` that is not yet known to be covered or missing
+    let mut cursor = requested.start();
+
+    // the range containing the start of the request may begin before it
+    let first = have
+        .ranges
+        .range(..=cursor)
+        .next_back()
+        .map(|(key, _)| *key)
+        .unwrap_or(cursor);
+
+    for existing in have.ranges.range(first..=end).map(|(_, range)| range) {
+        if existing.end() < cursor {
+            continue;
+        }
+        if existing.start() > cursor {
+            missing.push(TimeRange::from_ordered_bounds(
+                cursor,
+                existing.start().pred(),
+            ));
+        }
+        if existing.end() >= end {
+            return missing;
+        }
+        cursor = existing.end().succ();
+    }
+
+    missing.push(TimeRange::from_ordered_bounds(cursor, end));
+    missing
+}
+
+#[cfg(feature = "alloc")]
+/// Any ordered key can be used to name a `Cache`, but requesting and adding data needs the keys
+/// to be a `TimeResolution`, so that contigious requests can be stored as a single `TimeRange`.
+pub struct Cache<K: Ord + fmt::Debug + Copy, T: Send + fmt::Debug + Eq + Copy> {
+    // The actual data in the cache
+    data: collections::BTreeMap<K, T>,
+    // Every version of the points added with `add_versioned`, keyed by the as-of time. The latest
+    // version of each point is also stored in `data`
+    versions: collections::BTreeMap<K, collections::BTreeMap<DateTime<Utc>, T>>,
+    // The requests for data which has been cached, stored as ranges so that the memory used
+    // depends on the number of separate requests rather than the number of periods requested
+    requests: TimeRangeSet<K>,
+    // When set, the oldest data is evicted after each `add` to keep at most this many points
+    max_entries: Option<num::NonZeroUsize>,
+    // When set, the missing ranges of a response are split to at most this many periods
+    max_chunk_len: Option<num::NonZeroU64>,
+    // atomic so that `get` can record hits and misses through a shared reference
+    counters: CacheCounters,
+    hook: Option<Box<dyn Fn(CacheEvent) + Send + Sync>>,
+    // How to handle new data for a point which already has data, see `MergeDecision`
+    merge: Option<fn(&T, &T) -> MergeDecision<T>>,
+}
+
+#[cfg(feature = "alloc")]
+/// What to do when data is added to a `Cache` for a point which already has data, see
+/// `Cache::with_merge`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MergeDecision<T> {
+    /// Keep the existing data
+    KeepOld,
+    /// Replace the existing data with the new data
+    Replace,
+    /// Treat the new data as a conflict. `Cache::try_add` returns `Error::GotNonMatchingNewData`,
+    /// while `Cache::add` keeps the existing data
+    Error,
+    /// Store a combination of the existing and new data
+    Combine(T),
+}
+
+#[cfg(feature = "alloc")]
+/// Counters describing how effective a `Cache` has been, see `Cache::stats`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    /// Requests which were entirely answered from the cache
+    pub hits: u64,
+    /// Requests where none of the periods were cached
+    pub misses: u64,
+    /// Requests where some, but not all, of the periods were cached
+    pub partial_misses: u64,
+    /// Data points added to the cache
+    pub inserted: u64,
+    /// Data points removed from the cache, either explicitly or due to `max_entries`
+    pub evicted: u64,
+}
+
+#[cfg(feature = "alloc")]
+/// An event reported to the hook set with `Cache::set_hook`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheEvent {
+    Hit,
+    Miss,
+    PartialMiss,
+    /// The number of data points added
+    Inserted(u64),
+    /// The number of data points evicted
+    Evicted(u64),
+}
+
+#[cfg(feature = "alloc")]
+#[derive(Default)]
+struct CacheCounters {
+    hits: atomic::AtomicU64,
+    misses: atomic::AtomicU64,
+    partial_misses: atomic::AtomicU64,
+    inserted: atomic::AtomicU64,
+    evicted: atomic::AtomicU64,
+}
+
+#[cfg(feature = "alloc")]
+// group sorted periods into the minimal set of contigious ranges
+fn contigious_ranges<K: TimeResolution>(sorted: impl IntoIterator<Item = K>) -> Vec<TimeRange<K>> {
+    let mut ranges = Vec::new();
+    let mut current: Option<TimeRange<K>> = None;
+    for period in sorted {
+        current = match current {
+            Some(range) if range.end().succ() == period => Some(range.extend_by(1)),
+            Some(range) => {
+                ranges.push(range);
+                TimeRange::maybe_new(period, 1)
+            }
+            None => TimeRange::maybe_new(period, 1),
+        };
     }
+    ranges.extend(current);
+    ranges
+}
+
+#[cfg(feature = "alloc")]
+// find the parts of a request which aren't cached, grouping contigious on the way
+fn missing_pieces<K: TimeResolution>(
+    request: collections::BTreeSet<K>,
+    is_cached: impl Fn(&K) -> bool,
+) -> Vec<TimeRange<K>> {
+    // there is no need to worry about filling gaps to reduce the total number
+    // of requests - the consumer will handle this
+    contigious_ranges(request.into_iter().filter(|k| !is_cached(k)))
 }
 
-// No concept of partial, becuse we will simply request the missing data, then ask the cache again.
+#[cfg(feature = "alloc")]
 pub enum CacheResponse<K: Ord + fmt::Debug + Copy, T: Send + fmt::Debug + Eq + Copy> {
     Hit(collections::BTreeMap<K, T>), // means the whole request as able to be replied, doesn't necessarily mean the whole range of data is filled
-    Miss(Vec<collections::BTreeSet<K>>), // will be a minimal reasonable set of time ranges to request from the provider
+    // some of the request was able to be replied, so the data can be used while fetching the rest
+    Partial {
+        data: collections::BTreeMap<K, T>,
+        missing: Vec<TimeRange<K>>,
+    },
+    Miss(Vec<TimeRange<K>>), // will be a minimal reasonable set of time ranges to request from the provider
 }
 
+#[cfg(feature = "alloc")]
 impl<K: Ord + fmt::Debug + Copy, T: Send + fmt::Debug + Eq + Copy> Cache<K, T> {
+    /// The number of hits, misses, insertions and evictions since the cache was created.
+    pub fn stats(&self) -> CacheStats {
+        let counters = &self.counters;
+        CacheStats {
+            hits: counters.hits.load(atomic::Ordering::Relaxed),
+            misses: counters.misses.load(atomic::Ordering::Relaxed),
+            partial_misses: counters.partial_misses.load(atomic::Ordering::Relaxed),
+            inserted: counters.inserted.load(atomic::Ordering::Relaxed),
+            evicted: counters.evicted.load(atomic::Ordering::Relaxed),
+        }
+    }
+    /// Split each missing range returned by `get` into ranges of at most `max_chunk_len` periods,
+    /// for example to match a provider which allows at most 7 days of data per request.
+    pub fn set_max_chunk_len(&mut self, max_chunk_len: num::NonZeroU64) {
+        self.max_chunk_len = Some(max_chunk_len);
+    }
+    /// Set a hook which is called with each `CacheEvent`, in addition to updating the `stats`.
+    pub fn set_hook(&mut self, hook: impl Fn(CacheEvent) + Send + Sync + 'static) {
+        self.hook = Some(Box::new(hook));
+    }
+    fn record(&self, event: CacheEvent) {
+        let (counter, amount) = match event {
+            CacheEvent::Hit => (&self.counters.hits, 1),
+            CacheEvent::Miss => (&self.counters.misses, 1),
+            CacheEvent::PartialMiss => (&self.counters.partial_misses, 1),
+            CacheEvent::Inserted(n) => (&self.counters.inserted, n),
+            CacheEvent::Evicted(n) => (&self.counters.evicted, n),
+        };
+        counter.fetch_add(amount, atomic::Ordering::Relaxed);
+        if let Some(hook) = &self.hook {
+            hook(event);
+        }
+    }
+    pub fn empty() -> Cache<K, T> {
+        Cache {
+            data: collections::BTreeMap::new(),
+            versions: collections::BTreeMap::new(),
+            requests: TimeRangeSet {
+                ranges: collections::BTreeMap::new(),
+            },
+            max_entries: None,
+            max_chunk_len: None,
+            counters: CacheCounters::default(),
+            hook: None,
+            merge: None,
+        }
+    }
+    /// Create an empty cache which holds at most `max_entries` data points. When adding data
+    /// would exceed this, the oldest points are evicted, along with the record of them having
+    /// been requested, so that a later `get` of them is a `Miss`.
+    pub fn with_max_entries(max_entries: num::NonZeroUsize) -> Cache<K, T> {
+        Cache {
+            max_entries: Some(max_entries),
+            ..Cache::empty()
+        }
+    }
+    /// Create an empty cache which uses `merge` to decide what to store when data is added for
+    /// a point which already has data, instead of replacing it.
+    pub fn with_merge(merge: fn(&T, &T) -> MergeDecision<T>) -> Cache<K, T> {
+        Cache {
+            merge: Some(merge),
+            ..Cache::empty()
+        }
+    }
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+    // decide the data to store from `data` given the existing data, or the first point at
+    // which `merge` returns `MergeDecision::Error`
+    fn resolve(
+        &self,
+        data: &collections::BTreeMap<K, T>,
+        merge: impl Fn(&T, &T) -> MergeDecision<T>,
+    ) -> Result<collections::BTreeMap<K, T>, K> {
+        let mut resolved = collections::BTreeMap::new();
+        for (point, new) in data {
+            let Some(old) = self.data.get(point) else {
+                resolved.insert(*point, *new);
+                continue;
+            };
+            match merge(old, new) {
+                MergeDecision::KeepOld => {}
+                MergeDecision::Replace => {
+                    resolved.insert(*point, *new);
+                }
+                MergeDecision::Error => return Err(*point),
+                MergeDecision::Combine(combined) => {
+                    resolved.insert(*point, combined);
+                }
+            }
+        }
+        Ok(resolved)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<K: TimeResolution + fmt::Debug, T: Send + fmt::Debug + Eq + Copy> Cache<K, T> {
     pub fn get(&self, request: collections::BTreeSet<K>) -> CacheResponse<K, T> {
-        if request.is_empty() {
-            CacheResponse::Hit(collections::BTreeMap::new())
-        } else if self.requests.is_superset(&request) {
-            CacheResponse::Hit(
+        let (Some(first), Some(last)) = (request.first().copied(), request.last().copied()) else {
+            return CacheResponse::Hit(collections::BTreeMap::new());
+        };
+        let requested = u64::try_from(request.len()).unwrap_or(u64::MAX);
+        let missing = missing_pieces(request, |k| self.requests.contains(*k));
+        self.respond(first, last, requested, missing)
+    }
+    /// The same as `get`, but for a contigious `range` of periods, which avoids needing to
+    /// create a set of every period in the range.
+    pub fn get_range(&self, range: TimeRange<K>) -> CacheResponse<K, T> {
+        let missing = missing_ranges(range, &self.requests);
+        self.respond(range.start(), range.end(), range.len().get(), missing)
+    }
+    // build the response to a request spanning `first..=last` of which `missing` isn't cached
+    fn respond(
+        &self,
+        first: K,
+        last: K,
+        requested: u64,
+        mut missing: Vec<TimeRange<K>>,
+    ) -> CacheResponse<K, T> {
+        if missing.is_empty() {
+            self.record(CacheEvent::Hit);
+            return CacheResponse::Hit(
                 self.data
-                    .iter()
-                    // mustn't be empty othewise we would have returned out of the first arm of the `if`
-                    .filter(|(k, _)| request.iter().next().unwrap() <= *k)
-                    .filter(|(k, _)| request.iter().next_back().unwrap() >= *k)
+                    .range(first..=last)
                     .map(|(k, v)| (*k, *v))
                     .collect(),
-            )
+            );
+        }
+        let all_missing = missing.iter().map(|r| r.len().get()).sum::<u64>() == requested;
+        if let Some(max_chunk_len) = self.max_chunk_len {
+            missing = missing
+                .into_iter()
+                .flat_map(|range| range.chunks(max_chunk_len))
+                .collect();
+        }
+        if all_missing {
+            self.record(CacheEvent::Miss);
+            CacheResponse::Miss(missing)
         } else {
-            CacheResponse::Miss(missing_pieces(request, &self.requests))
+            self.record(CacheEvent::PartialMiss);
+            CacheResponse::Partial {
+                data: self
+                    .data
+                    .range(first..=last)
+                    .filter(|(k, _)| self.requests.contains(**k))
+                    .map(|(k, v)| (*k, *v))
+                    .collect(),
+                missing,
+            }
         }
     }
-    pub fn empty() -> Cache<K, T> {
-        Cache {
-            data: collections::BTreeMap::new(),
-            requests: collections::BTreeSet::new(),
+    /// Evict all data, and the record of requests, for periods before `cutoff`.
+    ///
+    /// Returns the number of data points evicted.
+    pub fn evict_before(&mut self, cutoff: K) -> usize {
+        let before = self.data.len();
+        self.data = self.data.split_off(&cutoff);
+        self.versions = self.versions.split_off(&cutoff);
+        self.requests.remove_before(cutoff);
+        let evicted = before - self.data.len();
+        if evicted > 0 {
+            self.record(CacheEvent::Evicted(evicted as u64));
+        }
+        evicted
+    }
+    /// Keep only the `periods` most recent periods up to and including `current`, evicting
+    /// everything older.
+    ///
+    /// Periods after `current` are never evicted by this method.
+    ///
+    /// Returns the number of data points evicted.
+    pub fn retain_recent(&mut self, current: K, periods: u64) -> usize {
+        match periods.checked_sub(1) {
+            Some(back) => self.evict_before(current.pred_n(back)),
+            None => self.evict_before(current.succ()),
+        }
+    }
+    // evict the oldest data until there are at most `max_entries` points
+    fn enforce_max_entries(&mut self) -> usize {
+        let Some(max_entries) = self.max_entries else {
+            return 0;
+        };
+        let excess = self.data.len().saturating_sub(max_entries.get());
+        match self.data.keys().nth(excess).copied() {
+            Some(cutoff) if excess > 0 => self.evict_before(cutoff),
+            _ => 0,
         }
     }
-    // could also store versioned data, with a DateTIme<Utc> associated with each T at each P?
-    // or allow overwriting, etc
-    // but this default seems better for now
+    /// Add the data for a request to the cache, replacing any existing data for the same points,
+    /// including any versions added with `add_versioned`.
+    /// This is the same as `overwrite`, unless the cache was created `with_merge`, in which case
+    /// the merge function decides what is stored. See `try_add` to instead detect conflicting data.
     pub fn add(
         &mut self,
-        mut request_range: collections::BTreeSet<K>,
+        request_range: collections::BTreeSet<K>,
+        data: collections::BTreeMap<K, T>,
+    ) {
+        self.add_ranges(contigious_ranges(request_range), data);
+    }
+    /// The same as `add`, but for a contigious `range` of requested periods, which avoids needing
+    /// to create a set of every period in the range.
+    pub fn add_range(&mut self, range: TimeRange<K>, data: collections::BTreeMap<K, T>) {
+        self.add_ranges([range], data);
+    }
+    fn add_ranges(
+        &mut self,
+        ranges: impl IntoIterator<Item = TimeRange<K>>,
+        data: collections::BTreeMap<K, T>,
+    ) {
+        let Some(merge) = self.merge else {
+            return self.overwrite_ranges(ranges, data);
+        };
+        let resolved = self
+            .resolve(&data, |old, new| match merge(old, new) {
+                MergeDecision::Error => MergeDecision::KeepOld,
+                decision => decision,
+            })
+            .expect("MergeDecision::Error is mapped to KeepOld");
+        self.overwrite_ranges(ranges, resolved);
+    }
+    /// Add the data for a request to the cache, replacing any existing data for the same points.
+    pub fn overwrite(
+        &mut self,
+        request_range: collections::BTreeSet<K>,
         data: collections::BTreeMap<K, T>,
     ) {
-        self.requests.append(&mut request_range);
+        self.overwrite_ranges(contigious_ranges(request_range), data);
+    }
+    fn overwrite_ranges(
+        &mut self,
+        ranges: impl IntoIterator<Item = TimeRange<K>>,
+        data: collections::BTreeMap<K, T>,
+    ) {
+        for range in ranges {
+            self.requests.insert(range);
+        }
+        if !data.is_empty() {
+            self.record(CacheEvent::Inserted(data.len() as u64));
+        }
+        for point in data.keys() {
+            self.versions.remove(point);
+        }
+        self.data.extend(data);
+        self.enforce_max_entries();
+    }
+    /// Add the data for a request to the cache as a version known as of `as_of`, keeping any
+    /// other versions of the same points so that they can be queried with `get_as_of`.
+    ///
+    /// `get` returns the version with the latest `as_of` for each point.
+    pub fn add_versioned(
+        &mut self,
+        request_range: collections::BTreeSet<K>,
+        data: collections::BTreeMap<K, T>,
+        as_of: DateTime<Utc>,
+    ) {
+        for range in contigious_ranges(request_range) {
+            self.requests.insert(range);
+        }
+        if !data.is_empty() {
+            self.record(CacheEvent::Inserted(data.len() as u64));
+        }
         for (point, datum) in data {
-            // should we check if the data point already exists?
-            // if it does exist, what should we do?
-            // for now, ignoring, as otherwise
-            // this function would need to be fallible
-            self.data.insert(point, datum);
+            let versions = self.versions.entry(point).or_default();
+            versions.insert(as_of, datum);
+            if let Some((_, latest)) = versions.last_key_value() {
+                self.data.insert(point, *latest);
+            }
+        }
+        self.enforce_max_entries();
+    }
+    /// The same as `get`, but with the data as it was known at `as_of`.
+    ///
+    /// Points added with `add_versioned` are only included if they have a version no later than
+    /// `as_of`, while points added without a version are always included.
+    pub fn get_as_of(
+        &self,
+        request: collections::BTreeSet<K>,
+        as_of: DateTime<Utc>,
+    ) -> CacheResponse<K, T> {
+        match self.get(request) {
+            CacheResponse::Hit(data) => CacheResponse::Hit(self.versions_as_of(data, as_of)),
+            CacheResponse::Partial { data, missing } => CacheResponse::Partial {
+                data: self.versions_as_of(data, as_of),
+                missing,
+            },
+            CacheResponse::Miss(missing) => CacheResponse::Miss(missing),
         }
     }
+    fn versions_as_of(
+        &self,
+        data: collections::BTreeMap<K, T>,
+        as_of: DateTime<Utc>,
+    ) -> collections::BTreeMap<K, T> {
+        data.into_iter()
+            .filter_map(|(point, datum)| match self.versions.get(&point) {
+                Some(versions) => versions
+                    .range(..=as_of)
+                    .next_back()
+                    .map(|(_, datum)| (point, *datum)),
+                None => Some((point, datum)),
+            })
+            .collect()
+    }
+    /// Add the data for a request to the cache, unless any of the new data is different to the
+    /// data already in the cache for the same point. In that case, nothing is added and
+    /// `Error::GotNonMatchingNewData` is returned for the first conflicting point.
+    ///
+    /// If the cache was created `with_merge`, a conflict is instead any point for which the
+    /// merge function returns `MergeDecision::Error`.
+    pub fn try_add(
+        &mut self,
+        request_range: collections::BTreeSet<K>,
+        data: collections::BTreeMap<K, T>,
+    ) -> Result<(), crate::Error>
+    where
+        K: fmt::Display,
+        T: fmt::Display,
+    {
+        let merge = self.merge.unwrap_or(|old, new| {
+            if old == new {
+                MergeDecision::Replace
+            } else {
+                MergeDecision::Error
+            }
+        });
+        match self.resolve(&data, merge) {
+            Ok(resolved) => {
+                self.overwrite(request_range, resolved);
+                Ok(())
+            }
+            Err(point) => Err(crate::Error::GotNonMatchingNewData {
+                point: point.to_string(),
+                old: self.data[&point].to_string(),
+                new: data[&point].to_string(),
+            }),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+// `from_bounds` is deprecated, but still works for ordered bounds, so is used in the older tests
+#[allow(deprecated)]
+mod tests {
+    use super::*;
+    use crate::Monotonic;
+
+    #[test]
+    fn test_missing_pieces() {
+        let day = |i| crate::Day::from_monotonic(i);
+        let cached = collections::BTreeSet::from([day(2), day(3), day(7), day(8)]);
+        let pieces = missing_pieces((1..=10).map(day).collect(), |k| cached.contains(k));
+        assert_eq!(
+            pieces,
+            Vec::from([
+                TimeRange::from_bounds(day(1), day(1)),
+                TimeRange::from_bounds(day(4), day(6)),
+                TimeRange::from_bounds(day(9), day(10)),
+            ])
+        );
+
+        // gaps in the request are not included in the missing ranges
+        let pieces = missing_pieces(
+            collections::BTreeSet::from([day(1), day(4), day(5), day(7), day(9)]),
+            |k| cached.contains(k),
+        );
+        assert_eq!(
+            pieces,
+            Vec::from([
+                TimeRange::from_bounds(day(1), day(1)),
+                TimeRange::from_bounds(day(4), day(5)),
+                TimeRange::from_bounds(day(9), day(9)),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_time_range_set_insert() {
+        let base = "2021-01-01".parse::<crate::Day>().unwrap();
+
+        let set = TimeRangeSet::from_iter([
+            TimeRange::from_bounds(base.succ_n(5), base.succ_n(6)),
+            TimeRange::from_bounds(base, base.succ_n(1)),
+            TimeRange::from_bounds(base.succ_n(2), base.succ_n(3)),
+            TimeRange::from_bounds(base.succ_n(10), base.succ_n(12)),
+            TimeRange::from_bounds(base.succ_n(11), base.succ_n(15)),
+        ]);
+
+        assert_eq!(
+            set.iter().collect::<Vec<_>>(),
+            Vec::from([
+                TimeRange::from_bounds(base, base.succ_n(3)),
+                TimeRange::from_bounds(base.succ_n(5), base.succ_n(6)),
+                TimeRange::from_bounds(base.succ_n(10), base.succ_n(15)),
+            ])
+        );
+        assert!(set.contains(base.succ_n(3)));
+        assert!(!set.contains(base.succ_n(4)));
+        assert!(!set.contains(base.pred()));
+
+        let mut set = set;
+        set.insert(TimeRange::from_bounds(base.pred(), base.succ_n(20)));
+        assert_eq!(
+            set.iter().collect::<Vec<_>>(),
+            Vec::from([TimeRange::from_bounds(base.pred(), base.succ_n(20))])
+        );
+    }
+
+    #[test]
+    fn test_missing_ranges() {
+        let base = "2021-01-01".parse::<crate::Day>().unwrap();
+
+        let have = TimeRangeSet::from_iter([
+            TimeRange::from_bounds(base.succ_n(1), base.succ_n(2)),
+            TimeRange::from_bounds(base.succ_n(6), base.succ_n(7)),
+        ]);
+
+        assert_eq!(
+            missing_ranges(TimeRange::from_bounds(base, base.succ_n(9)), &have),
+            Vec::from([
+                TimeRange::from_bounds(base, base),
+                TimeRange::from_bounds(base.succ_n(3), base.succ_n(5)),
+                TimeRange::from_bounds(base.succ_n(8), base.succ_n(9)),
+            ])
+        );
+        assert_eq!(
+            missing_ranges(
+                TimeRange::from_bounds(base.succ_n(2), base.succ_n(6)),
+                &have
+            ),
+            Vec::from([TimeRange::from_bounds(base.succ_n(3), base.succ_n(5))])
+        );
+        assert_eq!(
+            missing_ranges(
+                TimeRange::from_bounds(base.succ_n(6), base.succ_n(7)),
+                &have
+            ),
+            Vec::new()
+        );
+        assert_eq!(
+            missing_ranges(
+                TimeRange::from_bounds(base, base.succ_n(4)),
+                &TimeRangeSet::empty()
+            ),
+            Vec::from([TimeRange::from_bounds(base, base.succ_n(4))])
+        );
+    }
+
+    #[test]
+    fn test_split_at() {
+        let base = "2021-01-01".parse::<crate::Day>().unwrap();
+        let range = TimeRange::from_bounds(base, base.succ_n(4));
+
+        assert_eq!(
+            range.split_at(2),
+            Some((
+                TimeRange::from_bounds(base, base.succ_n(1)),
+                TimeRange::from_bounds(base.succ_n(2), base.succ_n(4)),
+            ))
+        );
+        assert_eq!(
+            range.split_at(4),
+            Some((
+                TimeRange::from_bounds(base, base.succ_n(3)),
+                TimeRange::from_bounds(base.succ_n(4), base.succ_n(4)),
+            ))
+        );
+        assert_eq!(range.split_at(0), None);
+        assert_eq!(range.split_at(5), None);
+        assert_eq!(range.split_at(u64::MAX), None);
+    }
+
+    #[test]
+    fn test_iter() {
+        let base = "2021-01-01".parse::<crate::Day>().unwrap();
+        let range = TimeRange::from_bounds(base, base.succ_n(4));
+
+        assert_eq!(range.iter().len(), 5);
+        assert_eq!(range.iter().size_hint(), (5, Some(5)));
+        assert_eq!(range.iter().last(), Some(base.succ_n(4)));
+        assert_eq!(
+            range.iter().rev().collect::<Vec<_>>(),
+            (0..5).rev().map(|i| base.succ_n(i)).collect::<Vec<_>>()
+        );
+
+        let mut iter = range.iter();
+        assert_eq!(iter.next(), Some(base));
+        assert_eq!(iter.next_back(), Some(base.succ_n(4)));
+        assert_eq!(iter.len(), 3);
+        assert_eq!(iter.nth(1), Some(base.succ_n(2)));
+        assert_eq!(iter.next_back(), Some(base.succ_n(3)));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next_back(), None);
+        assert_eq!(iter.len(), 0);
+
+        let mut iter = range.iter();
+        assert_eq!(iter.nth(5), None);
+        assert_eq!(iter.next(), None);
+
+        let mut iter = range.iter();
+        assert_eq!(iter.nth_back(1), Some(base.succ_n(3)));
+        assert_eq!(iter.nth(2), Some(base.succ_n(2)));
+        assert_eq!(iter.next(), None);
+
+        // longer than fits in a 32-bit usize, where the length saturates rather than panicking
+        let base = "2021-01-01 00:00".parse::<crate::Minute>().unwrap();
+        let len = 1 << 33;
+        let expected = usize::try_from(len).unwrap_or(usize::MAX);
+        let range = TimeRange::maybe_new(base, len).unwrap();
+        assert_eq!(range.iter().len(), expected);
+        assert_eq!(range.iter().count(), expected);
+    }
+
+    #[test]
+    fn test_try_iter() {
+        let base = "2021-01-01 00:00".parse::<crate::Minute>().unwrap();
+        let range = TimeRange::from_bounds(base, base.succ_n(100 * 365 * 24 * 60));
+
+        assert!(matches!(
+            range.try_iter(1_000_000),
+            Err(crate::Error::RangeTooLong {
+                max: 1_000_000,
+                actual: 52_560_001,
+            })
+        ));
+        assert_eq!(range.try_iter(52_560_001).unwrap().len(), 52_560_001);
+        assert_eq!(
+            range.approximate_len_duration(),
+            chrono::Duration::minutes(52_560_001)
+        );
+
+        let base = "Jan-2021".parse::<crate::Month>().unwrap();
+        assert_eq!(
+            TimeRange::from_bounds(base, base.succ()).approximate_len_duration(),
+            chrono::Duration::days(31 + 28)
+        );
+    }
+
+    #[test]
+    fn test_chunks() {
+        let base = "2021-01-01".parse::<crate::Day>().unwrap();
+        let range = TimeRange::from_bounds(base, base.succ_n(9));
+
+        let chunks = range.chunks(num::NonZeroU64::new(4).unwrap());
+        assert_eq!(chunks.size_hint(), (3, Some(3)));
+        assert_eq!(
+            chunks.collect::<Vec<_>>(),
+            Vec::from([
+                TimeRange::from_bounds(base, base.succ_n(3)),
+                TimeRange::from_bounds(base.succ_n(4), base.succ_n(7)),
+                TimeRange::from_bounds(base.succ_n(8), base.succ_n(9)),
+            ])
+        );
+        assert_eq!(
+            range
+                .chunks(num::NonZeroU64::new(10).unwrap())
+                .collect::<Vec<_>>(),
+            Vec::from([range])
+        );
+        assert_eq!(
+            range
+                .chunks(num::NonZeroU64::new(5).unwrap())
+                .collect::<Vec<_>>(),
+            Vec::from([
+                TimeRange::from_bounds(base, base.succ_n(4)),
+                TimeRange::from_bounds(base.succ_n(5), base.succ_n(9)),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_try_from_iter() {
+        let base = "2021-01-01".parse::<crate::Day>().unwrap();
+
+        assert_eq!(
+            TimeRange::try_from_iter((0..5).map(|i| base.succ_n(i))).unwrap(),
+            TimeRange::from_bounds(base, base.succ_n(4))
+        );
+        assert!(matches!(
+            TimeRange::<crate::Day>::try_from_iter([]),
+            Err(crate::Error::EmptyRange)
+        ));
+        assert!(matches!(
+            TimeRange::try_from_iter([base, base.succ(), base.succ_n(3)]),
+            Err(crate::Error::NonContiguous { expected, actual })
+                if expected == base.succ_n(2).start_datetime()
+                    && actual == base.succ_n(3).start_datetime()
+        ));
+        assert!(matches!(
+            TimeRange::try_from_iter([base, base.succ(), base.succ()]),
+            Err(crate::Error::NonContiguous { .. })
+        ));
+        assert!(matches!(
+            TimeRange::try_from_iter([base.succ(), base]),
+            Err(crate::Error::NonContiguous { .. })
+        ));
+
+        assert_eq!(
+            TimeRange::from_set(&collections::BTreeSet::from([base, base.succ()])),
+            Some(TimeRange::from_bounds(base, base.succ()))
+        );
+        assert_eq!(
+            TimeRange::from_set(&collections::BTreeSet::from([base, base.succ_n(2)])),
+            None
+        );
+    }
+
+    #[test]
+    fn test_clamp_to() {
+        let base = "2021-01-01 00:00 => 2021-01-01 00:30"
+            .parse::<crate::HalfHour>()
+            .unwrap();
+        let range = TimeRange::from_bounds(base, base.succ_n(47));
+
+        let at = |h, m| {
+            chrono::NaiveDate::from_ymd_opt(2021, 1, 1)
+                .unwrap()
+                .and_hms_opt(h, m, 0)
+                .unwrap()
+                .and_utc()
+        };
+
+        assert_eq!(
+            range.clamp_to(at(1, 0), at(2, 0)),
+            Some(TimeRange::from_bounds(base.succ_n(2), base.succ_n(3)))
+        );
+        assert_eq!(
+            range.clamp_to(at(1, 10), at(2, 50)),
+            Some(TimeRange::from_bounds(base.succ_n(3), base.succ_n(4)))
+        );
+        assert_eq!(
+            range.clamp_to_overlapping(at(1, 10), at(2, 50)),
+            Some(TimeRange::from_bounds(base.succ_n(2), base.succ_n(5)))
+        );
+        assert_eq!(
+            range.clamp_to_overlapping(at(1, 0), at(2, 0)),
+            Some(TimeRange::from_bounds(base.succ_n(2), base.succ_n(3)))
+        );
+        assert_eq!(range.clamp_to(at(1, 10), at(1, 50)), None);
+        assert_eq!(
+            range.clamp_to_overlapping(at(1, 10), at(1, 50)),
+            Some(TimeRange::from_bounds(base.succ_n(2), base.succ_n(3)))
+        );
+        assert_eq!(range.clamp_to_overlapping(at(2, 0), at(1, 0)), None);
+
+        // windows extending past the range are trimmed to the range
+        assert_eq!(
+            range.clamp_to(
+                at(0, 0) - chrono::Duration::days(1),
+                at(0, 0) + chrono::Duration::days(2)
+            ),
+            Some(range)
+        );
+        assert_eq!(
+            range.clamp_to_overlapping(
+                at(0, 0) + chrono::Duration::days(1),
+                at(0, 0) + chrono::Duration::days(2)
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_shift() {
+        let base = "Jan-2021".parse::<crate::Month>().unwrap();
+        let range = TimeRange::from_bounds(base, base.succ_n(2));
+
+        assert_eq!(
+            range.shift_back(12),
+            TimeRange::from_bounds(
+                "Jan-2020".parse::<crate::Month>().unwrap(),
+                "Mar-2020".parse::<crate::Month>().unwrap()
+            )
+        );
+        assert_eq!(range.shift_forward(12).shift_back(12), range);
+        assert_eq!(
+            range.shift_forward(1),
+            TimeRange::from_bounds(base.succ(), base.succ_n(3))
+        );
+        assert_eq!(
+            range.extend_by(2),
+            TimeRange::from_bounds(base, base.succ_n(4))
+        );
+        assert_eq!(range.shrink_by(2), Some(TimeRange::from_bounds(base, base)));
+        assert_eq!(range.shrink_by(3), None);
+        assert_eq!(range.extend_by(0).shrink_by(0), Some(range));
+    }
+
+    #[test]
+    fn test_contains_range() {
+        let month = "Feb-2021".parse::<crate::Month>().unwrap();
+        let months = TimeRange::from_bounds(month, month);
+        let day = "2021-02-01".parse::<crate::Day>().unwrap();
+
+        assert!(months.contains_range(&TimeRange::from_bounds(day, day.succ_n(27))));
+        assert!(!months.contains_range(&TimeRange::from_bounds(day, day.succ_n(28))));
+        assert!(!months.contains_range(&TimeRange::from_bounds(day.pred(), day)));
+        assert!(months.overlaps(&TimeRange::from_bounds(day.pred(), day)));
+        assert!(months.overlaps(&TimeRange::from_bounds(day.succ_n(27), day.succ_n(28))));
+        assert!(!months.overlaps(&TimeRange::from_bounds(day.succ_n(28), day.succ_n(29))));
+        assert!(!months.overlaps(&TimeRange::from_bounds(day.pred_n(2), day.pred())));
+
+        let minute = "2021-02-28 23:59".parse::<crate::Minute>().unwrap();
+        assert!(months.contains_range(&TimeRange::from_bounds(minute, minute)));
+        assert!(!months.contains_range(&TimeRange::from_bounds(minute, minute.succ())));
+        assert!(months.overlaps(&TimeRange::from_bounds(minute, minute.succ())));
+        assert!(TimeRange::from_bounds(minute, minute.succ()).overlaps(&months));
+        assert!(!TimeRange::from_bounds(minute, minute.succ()).contains_range(&months));
+    }
+
+    #[test]
+    fn test_contains() {
+        let base = "1969-12-31 23:58".parse::<crate::Minute>().unwrap();
+        let range = TimeRange::from_bounds(base, base.succ_n(3));
+
+        assert!(!range.contains(base.pred()));
+        assert!(range.contains(base));
+        assert!(range.contains(base.succ_n(2)));
+        assert!(range.contains(base.succ_n(3)));
+        assert!(!range.contains(base.succ_n(4)));
+        assert!(!range.contains(crate::Minute::from_monotonic(i64::MIN)));
+        assert!(!range.contains(crate::Minute::from_monotonic(i64::MAX)));
+    }
+
+    #[test]
+    fn test_from_bounds() {
+        let base = "2021-01-01".parse::<crate::Day>().unwrap();
+
+        assert_eq!(
+            TimeRange::try_from_bounds(base, base.succ_n(2)).unwrap(),
+            TimeRange::new(base, num::NonZeroU64::new(3).unwrap())
+        );
+        assert_eq!(
+            TimeRange::try_from_bounds(base, base).unwrap(),
+            TimeRange::new(base, num::NonZeroU64::new(1).unwrap())
+        );
+        assert!(matches!(
+            TimeRange::try_from_bounds(base.succ_n(2), base),
+            Err(crate::Error::ReversedBounds { .. })
+        ));
+        assert_eq!(
+            TimeRange::from_unordered_bounds(base.succ_n(2), base),
+            TimeRange::new(base, num::NonZeroU64::new(3).unwrap())
+        );
+        assert_eq!(
+            TimeRange::from_unordered_bounds(base, base.succ_n(2)),
+            TimeRange::new(base, num::NonZeroU64::new(3).unwrap())
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "can't be after its end")]
+    fn test_from_ordered_bounds_reversed() {
+        let base = "2021-01-01".parse::<crate::Day>().unwrap();
+        TimeRange::from_ordered_bounds(base.succ(), base);
+    }
+
+    #[test]
+    fn test_indexes() {
+        let base = "2021-01-01".parse::<crate::Day>().unwrap();
+        let range = TimeRange::from_unordered_bounds(base, base.succ_n(3));
+
+        let indexes = range.indexes();
+        assert_eq!(indexes.len(), 4);
+        assert_eq!(
+            indexes.collect::<Vec<_>>(),
+            (0..4).map(|i| base.to_monotonic() + i).collect::<Vec<_>>()
+        );
+        assert_eq!(range.indexes().next_back(), Some(base.to_monotonic() + 3));
+        assert_eq!(
+            range.to_indexes(),
+            range.indexes().collect::<collections::BTreeSet<_>>()
+        );
+    }
+
+    #[test]
+    fn test_ranges_from_sorted() {
+        use crate::Day;
+
+        fn reference(set: &collections::BTreeSet<i64>) -> Vec<TimeRange<Day>> {
+            set.iter()
+                .filter(|idx| !set.contains(&(*idx - 1)))
+                .map(|start| {
+                    let mut end = *start;
+                    while set.contains(&(end + 1)) {
+                        end += 1;
+                    }
+                    TimeRange::from_unordered_bounds(
+                        Day::from_monotonic(*start),
+                        Day::from_monotonic(end),
+                    )
+                })
+                .collect()
+        }
+
+        // every subset of 0..12
+        for bits in 0u32..(1 << 12) {
+            let set = (0..12)
+                .filter(|i| bits & (1 << i) != 0)
+                .map(|i| i64::from(i) - 6)
+                .collect::<collections::BTreeSet<_>>();
+            let expected = reference(&set);
+
+            assert_eq!(
+                TimeRange::<Day>::ranges_from_sorted(set.iter().copied()),
+                expected
+            );
+            assert_eq!(
+                TimeRange::<Day>::ranges_from_sorted(set.iter().flat_map(|i| [*i, *i])),
+                expected
+            );
+        }
+
+        assert_eq!(TimeRange::<Day>::ranges_from_sorted([]), Vec::new());
+        assert_eq!(
+            TimeRange::<Day>::ranges_from_sorted([i64::MAX - 1, i64::MAX]),
+            Vec::from([TimeRange::new(
+                Day::from_monotonic(i64::MAX - 1),
+                num::NonZeroU64::new(2).unwrap()
+            )])
+        );
+    }
+
+    #[test]
+    fn test_cache() {
+        // the key only needs to be ordered to name and create a cache
+        let keyed_by_int = Cache::<i64, u32>::with_max_entries(num::NonZeroUsize::MIN);
+        assert!(keyed_by_int.is_empty());
+        assert_eq!(keyed_by_int.stats(), CacheStats::default());
+
+        let base = "2021-01-01 00:00".parse::<crate::Minute>().unwrap();
+        let week = TimeRange::from_unordered_bounds(base, base.succ_n(7 * 24 * 60 - 1));
+
+        let mut cache = Cache::<crate::Minute, u32>::empty();
+        assert!(matches!(
+            cache.get(week.set()),
+            CacheResponse::Miss(missing) if missing == [week]
+        ));
+
+        // only some of the requested periods have data
+        cache.add(
+            week.set(),
+            (0..100).map(|i| (base.succ_n(i * 2), i as u32)).collect(),
+        );
+        // the whole week of minutes is stored as a single range
+        assert_eq!(cache.requests.iter().collect::<Vec<_>>(), Vec::from([week]));
+
+        let day = TimeRange::from_unordered_bounds(base.succ_n(10), base.succ_n(1449));
+        assert!(matches!(
+            cache.get(day.set()),
+            CacheResponse::Hit(data) if data.len() == 95 && data[&base.succ_n(10)] == 5
+        ));
+
+        let overlapping = week.shift_forward(10);
+        let expected = TimeRange::from_unordered_bounds(week.end().succ(), overlapping.end());
+        assert!(matches!(
+            cache.get(overlapping.set()),
+            CacheResponse::Partial { data, missing }
+                if missing == [expected] && data.len() == 95 && data.first_key_value() == Some((&base.succ_n(10), &5))
+        ));
+
+        cache.add(
+            collections::BTreeSet::from([base.pred_n(2), base.pred(), week.end().succ_n(2)]),
+            collections::BTreeMap::new(),
+        );
+        assert_eq!(
+            cache.requests.iter().collect::<Vec<_>>(),
+            Vec::from([
+                TimeRange::from_unordered_bounds(base.pred_n(2), week.end()),
+                TimeRange::from_unordered_bounds(week.end().succ_n(2), week.end().succ_n(2)),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_cache_try_add() {
+        let base = "2021-01-01".parse::<crate::Day>().unwrap();
+        let range = TimeRange::from_unordered_bounds(base, base.succ_n(2));
+
+        let mut cache = Cache::<crate::Day, u32>::empty();
+        cache
+            .try_add(range.set(), range.iter().map(|d| (d, 1)).collect())
+            .unwrap();
+        // the same data again is fine
+        cache
+            .try_add(range.set(), range.iter().map(|d| (d, 1)).collect())
+            .unwrap();
+
+        let err = cache
+            .try_add(
+                range.shift_forward(2).set(),
+                range.shift_forward(2).iter().map(|d| (d, 2)).collect(),
+            )
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            crate::Error::GotNonMatchingNewData { point, old, new }
+                if point == "2021-01-03" && old == "1" && new == "2"
+        ));
+        // nothing was added by the failed call
+        assert_eq!(
+            cache.requests.iter().collect::<Vec<_>>(),
+            Vec::from([range])
+        );
+        assert_eq!(cache.data.len(), 3);
+
+        cache.overwrite(
+            range.shift_forward(2).set(),
+            range.shift_forward(2).iter().map(|d| (d, 2)).collect(),
+        );
+        assert_eq!(cache.data[&base.succ_n(2)], 2);
+        assert_eq!(cache.data[&base.succ_n(4)], 2);
+        assert_eq!(
+            cache.requests.iter().collect::<Vec<_>>(),
+            Vec::from([range.extend_by(2)])
+        );
+    }
+
+    #[test]
+    fn test_time_range_set_remove_before() {
+        let base = "2021-01-01".parse::<crate::Day>().unwrap();
+        let mut set = [
+            TimeRange::maybe_new(base, 3).unwrap(),
+            TimeRange::maybe_new(base.succ_n(5), 5).unwrap(),
+            TimeRange::maybe_new(base.succ_n(20), 2).unwrap(),
+        ]
+        .into_iter()
+        .collect::<TimeRangeSet<_>>();
+
+        set.remove_before(base.succ_n(7));
+        assert_eq!(
+            set.iter().collect::<Vec<_>>(),
+            [
+                TimeRange::maybe_new(base.succ_n(7), 3).unwrap(),
+                TimeRange::maybe_new(base.succ_n(20), 2).unwrap()
+            ]
+        );
+
+        set.remove_before(base.succ_n(30));
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn test_cache_eviction() {
+        let base = "2021-01-01".parse::<crate::Day>().unwrap();
+        let range = TimeRange::maybe_new(base, 10).unwrap();
+
+        let mut cache =
+            Cache::<crate::Day, u32>::with_max_entries(num::NonZeroUsize::new(4).unwrap());
+        cache.add(range.set(), range.iter().map(|d| (d, 1)).collect());
+        assert_eq!(cache.len(), 4);
+        assert!(matches!(
+            cache.get(range.set()),
+            CacheResponse::Partial { data, missing }
+                if missing == [TimeRange::maybe_new(base, 6).unwrap()] && data.len() == 4
+        ));
+        let recent = TimeRange::maybe_new(base.succ_n(6), 4).unwrap();
+        assert!(matches!(
+            cache.get(recent.set()),
+            CacheResponse::Hit(data) if data.len() == 4
+        ));
+
+        assert_eq!(cache.retain_recent(base.succ_n(9), 2), 2);
+        assert_eq!(cache.len(), 2);
+        assert_eq!(
+            cache.requests.iter().collect::<Vec<_>>(),
+            [TimeRange::maybe_new(base.succ_n(8), 2).unwrap()]
+        );
+
+        assert_eq!(cache.retain_recent(base.succ_n(9), 0), 2);
+        assert!(cache.is_empty());
+        assert!(cache.requests.is_empty());
+    }
+
+    #[test]
+    fn test_cache_stats() {
+        use alloc::sync::Arc;
+
+        let base = "2021-01-01".parse::<crate::Day>().unwrap();
+        let range = TimeRange::maybe_new(base, 4).unwrap();
+
+        let evicted = Arc::new(atomic::AtomicU64::new(0));
+        let mut cache =
+            Cache::<crate::Day, u32>::with_max_entries(num::NonZeroUsize::new(4).unwrap());
+        cache.set_hook({
+            let evicted = evicted.clone();
+            move |event| {
+                if let CacheEvent::Evicted(n) = event {
+                    evicted.fetch_add(n, atomic::Ordering::Relaxed);
+                }
+            }
+        });
+
+        cache.get(range.set());
+        cache.add(range.set(), range.iter().map(|d| (d, 1)).collect());
+        cache.get(range.set());
+        cache.get(range.extend_by(1).set());
+        let shifted = range.shift_forward(2);
+        cache.add(shifted.set(), shifted.iter().map(|d| (d, 1)).collect());
+
+        assert_eq!(
+            cache.stats(),
+            CacheStats {
+                hits: 1,
+                misses: 1,
+                partial_misses: 1,
+                inserted: 8,
+                evicted: 2,
+            }
+        );
+        assert_eq!(evicted.load(atomic::Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    fn test_cache_max_chunk_len() {
+        let base = "2021-01-01".parse::<crate::Day>().unwrap();
+        let fortnight = TimeRange::maybe_new(base, 14).unwrap();
+
+        let mut cache = Cache::<crate::Day, u32>::empty();
+        cache.set_max_chunk_len(num::NonZeroU64::new(6).unwrap());
+        cache.add(
+            collections::BTreeSet::from([base.succ_n(3)]),
+            collections::BTreeMap::from([(base.succ_n(3), 1)]),
+        );
+
+        assert!(matches!(
+            cache.get(fortnight.set()),
+            CacheResponse::Partial { missing, .. } if missing == [
+                TimeRange::maybe_new(base, 3).unwrap(),
+                TimeRange::maybe_new(base.succ_n(4), 6).unwrap(),
+                TimeRange::maybe_new(base.succ_n(10), 4).unwrap(),
+            ]
+        ));
+    }
+
+    #[test]
+    fn test_cache_get_range() {
+        let base = "2021-01-01 00:00".parse::<crate::Minute>().unwrap();
+        let week = TimeRange::from_unordered_bounds(base, base.succ_n(7 * 24 * 60 - 1));
+
+        let mut cache = Cache::<crate::Minute, u32>::empty();
+        assert!(matches!(
+            cache.get_range(week),
+            CacheResponse::Miss(missing) if missing == [week]
+        ));
+
+        cache.add_range(
+            week,
+            (0..100).map(|i| (base.succ_n(i * 2), i as u32)).collect(),
+        );
+        let day = TimeRange::from_unordered_bounds(base.succ_n(10), base.succ_n(1449));
+        assert!(matches!(
+            cache.get_range(day),
+            CacheResponse::Hit(data) if data.len() == 95 && data[&base.succ_n(10)] == 5
+        ));
+
+        let overlapping = week.shift_forward(10);
+        let expected = TimeRange::from_unordered_bounds(week.end().succ(), overlapping.end());
+        assert!(matches!(
+            cache.get_range(overlapping),
+            CacheResponse::Partial { data, missing } if missing == [expected] && data.len() == 95
+        ));
+
+        cache.add_range(expected, collections::BTreeMap::new());
+        assert!(matches!(
+            cache.get_range(overlapping),
+            CacheResponse::Hit(data) if data.len() == 95
+        ));
+    }
+
+    #[test]
+    fn test_cache_versioned() {
+        use chrono::TimeZone;
+
+        let base = "2021-01-01".parse::<crate::Day>().unwrap();
+        let range = TimeRange::maybe_new(base, 3).unwrap();
+        let original = Utc.with_ymd_and_hms(2021, 1, 4, 0, 0, 0).unwrap();
+        let corrected = Utc.with_ymd_and_hms(2021, 1, 10, 0, 0, 0).unwrap();
+
+        let mut cache = Cache::<crate::Day, u32>::empty();
+        cache.add_versioned(
+            range.set(),
+            range.iter().map(|d| (d, 1)).collect(),
+            original,
+        );
+        cache.add_versioned(
+            collections::BTreeSet::from([base.succ()]),
+            collections::BTreeMap::from([(base.succ(), 2)]),
+            corrected,
+        );
+        cache.add(
+            collections::BTreeSet::from([base.succ_n(3)]),
+            collections::BTreeMap::from([(base.succ_n(3), 3)]),
+        );
+
+        let request = range.extend_by(1).set();
+        assert!(matches!(
+            cache.get(request.clone()),
+            CacheResponse::Hit(data) if data == collections::BTreeMap::from([
+                (base, 1), (base.succ(), 2), (base.succ_n(2), 1), (base.succ_n(3), 3)
+            ])
+        ));
+        assert!(matches!(
+            cache.get_as_of(request.clone(), corrected - chrono::Duration::days(1)),
+            CacheResponse::Hit(data) if data == collections::BTreeMap::from([
+                (base, 1), (base.succ(), 1), (base.succ_n(2), 1), (base.succ_n(3), 3)
+            ])
+        ));
+        assert!(matches!(
+            cache.get_as_of(request, original - chrono::Duration::days(1)),
+            CacheResponse::Hit(data) if data == collections::BTreeMap::from([(base.succ_n(3), 3)])
+        ));
+    }
+
+    #[test]
+    fn test_cache_merge() {
+        let base = "2021-01-01".parse::<crate::Day>().unwrap();
+        let range = TimeRange::maybe_new(base, 3).unwrap();
+
+        let mut cache = Cache::<crate::Day, u32>::with_merge(|old, new| match (old, new) {
+            (_, 0) => MergeDecision::KeepOld,
+            (old, new) if old > new => MergeDecision::Error,
+            (old, new) => MergeDecision::Combine(old + new),
+        });
+        cache.add(range.set(), range.iter().map(|d| (d, 10)).collect());
+        cache.add(
+            range.set(),
+            collections::BTreeMap::from([(base, 0), (base.succ(), 1), (base.succ_n(2), 20)]),
+        );
+        assert_eq!(
+            cache.data,
+            collections::BTreeMap::from([(base, 10), (base.succ(), 10), (base.succ_n(2), 30)])
+        );
+
+        let err = cache
+            .try_add(
+                range.set(),
+                collections::BTreeMap::from([(base, 20), (base.succ(), 1)]),
+            )
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            crate::Error::GotNonMatchingNewData { point, old, new }
+                if point == "2021-01-02" && old == "10" && new == "1"
+        ));
+        assert_eq!(cache.data[&base], 10);
+    }
+
+    #[test]
+    fn test_iso_interval() {
+        let base = "2021-01-01 10:00".parse::<crate::Minute>().unwrap();
+        let range = TimeRange::maybe_new(base, 5).unwrap();
+        assert_eq!(range.to_string(), "2021-01-01T10:00Z/2021-01-01T10:05Z");
+        assert_eq!(range.to_string().parse::<TimeRange<_>>().unwrap(), range);
+
+        let halves = "2021-01-01T10:00Z/2021-01-01T12:00Z"
+            .parse::<TimeRange<crate::HalfHour>>()
+            .unwrap();
+        assert_eq!(halves.len().get(), 4);
+
+        assert!(matches!(
+            "2021-01-01T10:00Z/2021-01-01T10:00Z".parse::<TimeRange<crate::Minute>>(),
+            Err(crate::Error::ReversedBounds { .. })
+        ));
+        for input in [
+            "2021-01-01T10:00Z/2021-01-01T10:45Z",
+            "2021-01-01T10:00Z",
+            "2021-01-01T10:00Z/2021-01-01 11:00",
+        ] {
+            assert!(
+                input.parse::<TimeRange<crate::HalfHour>>().is_err(),
+                "{input}"
+            );
+        }
+
+        let day = "2021-01-01".parse::<crate::Day>().unwrap();
+        assert_eq!(
+            TimeRange::maybe_new(day, 2).unwrap().to_string(),
+            "2021-01-01T00:00Z/2021-01-03T00:00Z"
+        );
+    }
 }
@@ -0,0 +1,219 @@
+//! Adaptors for use with [`serde_with`](https://docs.rs/serde_with), to choose the
+//! representation of individual fields when the default serde format doesn't match an
+//! external schema.
+//!
+//! ```
+//! use resolution::{serde_as, Day, Month};
+//!
+//! #[derive(serde::Serialize, serde::Deserialize)]
+//! struct Row {
+//!     #[serde(with = "serde_with::As::<serde_as::Iso>")]
+//!     month: Month,
+//!     #[serde(with = "serde_with::As::<serde_as::Index>")]
+//!     day: Day,
+//! }
+//!
+//! let row = Row {
+//!     month: "Jan-2021".parse().unwrap(),
+//!     day: "2021-01-01".parse().unwrap(),
+//! };
+//! assert_eq!(
+//!     serde_json::to_string(&row).unwrap(),
+//!     r#"{"month":"2021-01","day":738156}"#
+//! );
+//! ```
+use crate::{FromMonotonic, Monotonic, SubDateResolution};
+use alloc::string::String;
+use core::{fmt, str};
+use serde::{de, Deserialize};
+use serde_with::{DeserializeAs, SerializeAs};
+
+/// The ISO 8601 string, as written by the alternate `Display`, `{:#}`, eg `2021-01` for a `Month`
+/// or `2021-W05` for a `Week<Monday>`, parsed with `FromStr`.
+pub struct Iso;
+
+impl<R: fmt::Display> SerializeAs<R> for Iso {
+    fn serialize_as<S>(source: &R, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_str(&format_args!("{:#}", source))
+    }
+}
+
+impl<'de, R> DeserializeAs<'de, R> for Iso
+where
+    R: str::FromStr,
+    R::Err: fmt::Display,
+{
+    fn deserialize_as<D>(deserializer: D) -> Result<R, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(de::Error::custom)
+    }
+}
+
+/// The monotonic `i64`, as with `Compact`.
+pub struct Index;
+
+impl<R: Monotonic> SerializeAs<R> for Index {
+    fn serialize_as<S>(source: &R, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_i64(source.to_monotonic())
+    }
+}
+
+impl<'de, R: FromMonotonic> DeserializeAs<'de, R> for Index {
+    fn deserialize_as<D>(deserializer: D) -> Result<R, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        i64::deserialize(deserializer).map(R::from_monotonic)
+    }
+}
+
+/// The RFC 3339 timestamp of the start of a sub-date period, eg `2021-01-01T10:05:00Z` for a
+/// `FiveMinute`, for exchanging periods with systems which only know about timestamps. Any offset
+/// is accepted when deserializing, but the timestamp must be the start of a period. Formats which
+/// aren't human readable use the monotonic `i64`, as with `Index`.
+pub struct Rfc3339;
+
+impl<R: SubDateResolution> SerializeAs<R> for Rfc3339 {
+    fn serialize_as<S>(source: &R, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        if !serializer.is_human_readable() {
+            return Index::serialize_as(source, serializer);
+        }
+        serializer.collect_str(
+            &source
+                .start_datetime()
+                .to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+        )
+    }
+}
+
+impl<'de, R> DeserializeAs<'de, R> for Rfc3339
+where
+    R: SubDateResolution<Params = ()> + FromMonotonic,
+{
+    fn deserialize_as<D>(deserializer: D) -> Result<R, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        if !deserializer.is_human_readable() {
+            return Index::deserialize_as(deserializer);
+        }
+        let s = String::deserialize(deserializer)?;
+        let datetime = chrono::DateTime::parse_from_rfc3339(&s)
+            .map_err(de::Error::custom)?
+            .to_utc();
+        let period = R::from_utc_datetime(datetime, ());
+        if period.start_datetime() != datetime {
+            return Err(de::Error::custom(format_args!(
+                "{} is not the start of a period of {}",
+                s,
+                period.name()
+            )));
+        }
+        Ok(period)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compact::tests::Binary;
+    use crate::{FiveMinute, Minute, Quarter, Sunday, TimeResolution, Week, Year};
+    use alloc::{format, vec::Vec};
+
+    #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+    struct Row {
+        #[serde(with = "serde_with::As::<Iso>")]
+        week: Week<Sunday>,
+        #[serde(with = "serde_with::As::<Iso>")]
+        quarter: Quarter,
+        #[serde(with = "serde_with::As::<Iso>")]
+        minute: Minute,
+        #[serde(with = "serde_with::As::<Index>")]
+        year: Year,
+        #[serde(with = "serde_with::As::<Vec<Index>>")]
+        quarters: Vec<Quarter>,
+    }
+
+    #[test]
+    fn test_serde_as() {
+        let quarter = "Q1-2021".parse::<Quarter>().unwrap();
+        let row = Row {
+            week: "Week starting 2021-01-31".parse().unwrap(),
+            quarter,
+            minute: "2021-01-01 10:07".parse().unwrap(),
+            year: "2021".parse().unwrap(),
+            quarters: [quarter, quarter.succ()].to_vec(),
+        };
+        let json = serde_json::to_string(&row).unwrap();
+        assert_eq!(
+            json,
+            format!(
+                r#"{{"week":"2021-W04-7","quarter":"2021-Q1","minute":"2021-01-01T10:07Z","year":2021,"quarters":[{},{}]}}"#,
+                quarter.to_monotonic(),
+                quarter.to_monotonic() + 1
+            )
+        );
+        assert_eq!(serde_json::from_str::<Row>(&json).unwrap(), row);
+    }
+
+    #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+    struct Reading {
+        #[serde(with = "serde_with::As::<Rfc3339>")]
+        period: FiveMinute,
+        #[serde(with = "serde_with::As::<Option<Rfc3339>>")]
+        previous: Option<FiveMinute>,
+    }
+
+    #[test]
+    fn test_rfc3339() {
+        let period = "2021-01-01 10:05 => 2021-01-01 10:10"
+            .parse::<FiveMinute>()
+            .unwrap();
+        let reading = Reading {
+            period,
+            previous: Some(period.pred()),
+        };
+        let json = serde_json::to_string(&reading).unwrap();
+        assert_eq!(
+            json,
+            r#"{"period":"2021-01-01T10:05:00Z","previous":"2021-01-01T10:00:00Z"}"#
+        );
+        assert_eq!(serde_json::from_str::<Reading>(&json).unwrap(), reading);
+
+        // any offset is accepted
+        assert_eq!(
+            serde_json::from_str::<Reading>(
+                r#"{"period":"2021-01-01T20:05:00+10:00","previous":null}"#
+            )
+            .unwrap()
+            .period,
+            period
+        );
+        // but only at the start of a period
+        assert!(serde_json::from_str::<Reading>(
+            r#"{"period":"2021-01-01T10:06:00Z","previous":null}"#
+        )
+        .is_err());
+
+        // the monotonic index for formats which aren't human readable
+        let binary = Binary(serde_json::Value::from(period.to_monotonic()));
+        assert_eq!(
+            <Rfc3339 as DeserializeAs<FiveMinute>>::deserialize_as(binary).unwrap(),
+            period
+        );
+        let binary = Binary(serde_json::Value::from("2021-01-01T10:05:00Z"));
+        assert!(<Rfc3339 as DeserializeAs<FiveMinute>>::deserialize_as(binary).is_err());
+    }
+}
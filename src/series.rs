@@ -0,0 +1,117 @@
+use crate::{TimeRange, TimeResolution};
+use alloc::{format, vec::Vec};
+use chrono::{DateTime, Utc};
+use polars_core::prelude::{
+    DataType, Int64Chunked, IntoSeries, PolarsError, PolarsResult, Series, TimeUnit,
+};
+
+impl<P: TimeResolution> TimeRange<P> {
+    /// The start of each period in the range as a polars `Datetime` series, in milliseconds
+    /// since the UNIX epoch, without a timezone.
+    pub fn to_series(&self, name: &str) -> Series {
+        let starts = self
+            .iter()
+            .map(|period| period.start_datetime().timestamp_millis())
+            .collect();
+        Int64Chunked::from_vec(name.into(), starts)
+            .into_datetime(TimeUnit::Milliseconds, None)
+            .into_series()
+    }
+}
+
+/// Convert a polars `Date` or `Datetime` series, such as the keys of a `group_by` on truncated
+/// dates or times, into the periods starting at each value. Nulls are kept as `None`.
+///
+/// `Datetime` values are treated as UTC, which is how polars stores them whether or not the
+/// series has a timezone. An error is returned for any value which is not the start of a period.
+pub fn periods_from_series<P>(series: &Series) -> PolarsResult<Vec<Option<P>>>
+where
+    P: TimeResolution + From<DateTime<Utc>>,
+{
+    let to_period = |datetime: Option<chrono::NaiveDateTime>| {
+        let Some(datetime) = datetime else {
+            return Ok(None);
+        };
+        let datetime = datetime.and_utc();
+        let period = P::from(datetime);
+        if period.start_datetime() != datetime {
+            return Err(PolarsError::ComputeError(
+                format!(
+                    "{} in series {} is not the start of a {}",
+                    datetime,
+                    series.name(),
+                    period.name()
+                )
+                .into(),
+            ));
+        }
+        Ok(Some(period))
+    };
+    match series.dtype() {
+        DataType::Date => series
+            .date()?
+            .as_date_iter()
+            .map(|date| to_period(date.map(|date| date.and_time(chrono::NaiveTime::MIN))))
+            .collect(),
+        DataType::Datetime(_, _) => series
+            .datetime()?
+            .as_datetime_iter()
+            .map(to_period)
+            .collect(),
+        other => Err(PolarsError::SchemaMismatch(
+            format!(
+                "Expected series {} to be a Date or Datetime, but was {}",
+                series.name(),
+                other
+            )
+            .into(),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Day, Hour, Month};
+    use polars_core::prelude::{DateChunked, Int32Chunked, NamedFrom};
+
+    #[test]
+    fn test_series() {
+        let start = "2021-01-30".parse::<Day>().unwrap();
+        let range = TimeRange::from_unordered_bounds(start, start.succ_n(3));
+        let series = range.to_series("day");
+        assert_eq!(series.name().as_str(), "day");
+        assert_eq!(
+            series.dtype(),
+            &DataType::Datetime(TimeUnit::Milliseconds, None)
+        );
+        assert_eq!(series.len(), 4);
+
+        let days = periods_from_series::<Day>(&series).unwrap();
+        assert_eq!(days, range.iter().map(Some).collect::<Vec<_>>());
+        // days aren't the start of a month, or of an hour after the first
+        assert!(periods_from_series::<Month>(&series).is_err());
+        assert_eq!(
+            periods_from_series::<Hour>(&series).unwrap()[1],
+            Some(Hour::from(start.succ().start_datetime()))
+        );
+
+        let months: DateChunked = Int32Chunked::new(
+            "month".into(),
+            // 2021-01-01 and 2021-02-01, as days since the UNIX epoch, and a null
+            [Some(18628), Some(18659), None],
+        )
+        .into_date();
+        let jan = "Jan-2021".parse::<Month>().unwrap();
+        assert_eq!(
+            periods_from_series::<Month>(&months.into_series()).unwrap(),
+            [Some(jan), Some(jan.succ()), None]
+        );
+
+        let not_dates = Int32Chunked::new("n".into(), [1, 2]).into_series();
+        assert!(matches!(
+            periods_from_series::<Day>(&not_dates),
+            Err(PolarsError::SchemaMismatch(_))
+        ));
+    }
+}
@@ -0,0 +1,150 @@
+//! `proptest` strategies for generating periods and ranges, for property testing code built on
+//! this crate.
+//!
+//! ```
+//! use proptest::prelude::*;
+//! use resolution::{strategies, Day};
+//!
+//! let mut runner = proptest::test_runner::TestRunner::default();
+//! runner
+//!     .run(&strategies::range_pair_overlapping::<Day>(100), |(a, b)| {
+//!         let intersection = a.intersection(&b).unwrap();
+//!         prop_assert!(a.contains_range(&intersection) && b.contains_range(&intersection));
+//!         Ok(())
+//!     })
+//!     .unwrap();
+//! ```
+use crate::{Day, FromMonotonic, TimeRange, TimeResolution};
+use chrono::{DateTime, NaiveDate, Utc};
+use core::{fmt, num};
+use proptest::{prelude::*, strategy::Strategy};
+
+// periods are generated between the start of these years, which is wide enough to cover
+// negative years and years with more than four digits, while leaving room to step forwards
+// and back without reaching the limits of `chrono`
+const MIN_YEAR: i32 = -10_000;
+const MAX_YEAR: i32 = 10_000;
+
+fn start_of_year(year: i32) -> DateTime<Utc> {
+    NaiveDate::from_ymd_opt(year, 1, 1)
+        .expect("valid date")
+        .and_time(chrono::NaiveTime::MIN)
+        .and_utc()
+}
+
+/// Any period of the resolution `P`, starting between the years -10000 and 10000.
+pub fn any_period<P>() -> impl Strategy<Value = P>
+where
+    P: TimeResolution + FromMonotonic + From<DateTime<Utc>> + fmt::Debug,
+{
+    let min = P::from(start_of_year(MIN_YEAR)).to_monotonic();
+    let max = P::from(start_of_year(MAX_YEAR)).to_monotonic();
+    (min..=max).prop_map(P::from_monotonic)
+}
+
+/// Any `Day`, between the years -10000 and 10000.
+pub fn any_day() -> impl Strategy<Value = Day> {
+    any_period()
+}
+
+/// Any `TimeRange` of between 1 and `max_len` periods, starting as for `any_period`.
+///
+/// Panics if `max_len` is 0.
+pub fn any_range<P>(max_len: u64) -> impl Strategy<Value = TimeRange<P>>
+where
+    P: TimeResolution + FromMonotonic + From<DateTime<Utc>> + fmt::Debug,
+{
+    (any_period::<P>(), len(max_len)).prop_map(|(start, len)| TimeRange::new(start, len))
+}
+
+/// Pairs of ranges, each of between 1 and `max_len` periods, which overlap by at least one
+/// period. Either range may start first.
+///
+/// Panics if `max_len` is 0.
+pub fn range_pair_overlapping<P>(
+    max_len: u64,
+) -> impl Strategy<Value = (TimeRange<P>, TimeRange<P>)>
+where
+    P: TimeResolution + FromMonotonic + From<DateTime<Utc>> + fmt::Debug,
+{
+    any_range::<P>(max_len)
+        .prop_flat_map(move |a| (Just(a), 0..a.len().get(), len(max_len), any::<bool>()))
+        .prop_map(|(a, offset, len, swap)| {
+            let b = TimeRange::new(a.start().succ_n(offset), len);
+            if swap {
+                (b, a)
+            } else {
+                (a, b)
+            }
+        })
+}
+
+/// Pairs of ranges, each of between 1 and `max_len` periods, which don't overlap, but may be
+/// adjacent. Either range may start first.
+///
+/// Panics if `max_len` is 0.
+pub fn range_pair_disjoint<P>(max_len: u64) -> impl Strategy<Value = (TimeRange<P>, TimeRange<P>)>
+where
+    P: TimeResolution + FromMonotonic + From<DateTime<Utc>> + fmt::Debug,
+{
+    (
+        any_range::<P>(max_len),
+        0..=max_len,
+        len(max_len),
+        any::<bool>(),
+    )
+        .prop_map(|(a, gap, len, swap)| {
+            let b = TimeRange::new(a.end().succ_n(gap + 1), len);
+            if swap {
+                (b, a)
+            } else {
+                (a, b)
+            }
+        })
+}
+
+fn len(max_len: u64) -> impl Strategy<Value = num::NonZeroU64> {
+    assert!(
+        max_len > 0,
+        "The maximum length of a range must be at least 1"
+    );
+    (1..=max_len).prop_map(|len| num::NonZeroU64::new(len).expect("At least 1"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DateResolution, FiveMinute, Month, Quarter, Sunday, Week, Year};
+
+    proptest! {
+        #[test]
+        fn test_any_period(day in any_day(), minute in any_period::<FiveMinute>()) {
+            prop_assert!(day.start() >= start_of_year(MIN_YEAR).date_naive());
+            prop_assert!(day.start() <= start_of_year(MAX_YEAR).date_naive());
+            prop_assert!(minute.start_datetime() >= start_of_year(MIN_YEAR));
+            prop_assert!(minute.start_datetime() <= start_of_year(MAX_YEAR));
+        }
+
+        #[test]
+        fn test_any_range(
+            week in any_range::<Week<Sunday>>(10),
+            month in any_range::<Month>(1),
+            _quarter in any_range::<Quarter>(10),
+            _year in any_range::<Year>(10),
+        ) {
+            prop_assert!(week.len().get() <= 10);
+            prop_assert_eq!(month.len().get(), 1);
+        }
+
+        #[test]
+        fn test_range_pairs(
+            (a, b) in range_pair_overlapping::<Day>(20),
+            (c, d) in range_pair_disjoint::<Day>(20),
+        ) {
+            prop_assert!(a.overlaps(&b));
+            prop_assert!(a.intersection(&b).is_some());
+            prop_assert!(!c.overlaps(&d));
+            prop_assert!(c.intersection(&d).is_none());
+        }
+    }
+}
@@ -1,7 +1,7 @@
-use alloc::{fmt, str};
-use alloc::{format, string::String};
+#[cfg(feature = "alloc")]
+use alloc::{borrow, format, string::String};
 use chrono::{DateTime, Datelike, NaiveDate, NaiveTime, Utc};
-use core::marker;
+use core::{fmt, marker, str};
 
 use crate::{DateResolution, FromMonotonic};
 
@@ -16,6 +16,22 @@ mod private {
     impl Sealed for super::Sunday {}
 }
 
+/// The day of the week on which a `Week` starts, one of `Monday` to `Sunday`.
+///
+/// This trait is sealed, so can't be implemented outside this crate, which allows it to have
+/// hidden required items such as `DAYS_FROM_MONDAY` without them being part of the public API:
+///
+/// ```compile_fail
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+/// struct Holiday;
+///
+/// impl resolution::StartDay for Holiday {
+///     const NAME: &'static str = "Holiday";
+///     fn weekday() -> chrono::Weekday {
+///         chrono::Weekday::Mon
+///     }
+/// }
+/// ```
 pub trait StartDay:
     private::Sealed
     + Send
@@ -30,6 +46,12 @@ pub trait StartDay:
     + Ord
 {
     const NAME: &'static str;
+    // the `TimeResolution::name` of a `Week` starting on this day
+    #[doc(hidden)]
+    const WEEK_NAME: &'static str;
+    // the number of days from the start of a week starting on Monday
+    #[doc(hidden)]
+    const DAYS_FROM_MONDAY: i64;
     fn weekday() -> chrono::Weekday;
 }
 
@@ -50,55 +72,79 @@ pub struct Sunday;
 
 impl StartDay for Monday {
     const NAME: &'static str = "Monday";
+    const WEEK_NAME: &'static str = "Week[StartDay:Monday]";
+    const DAYS_FROM_MONDAY: i64 = 0;
     fn weekday() -> chrono::Weekday {
         chrono::Weekday::Mon
     }
 }
 impl StartDay for Tuesday {
     const NAME: &'static str = "Tuesday";
+    const WEEK_NAME: &'static str = "Week[StartDay:Tuesday]";
+    const DAYS_FROM_MONDAY: i64 = 1;
     fn weekday() -> chrono::Weekday {
         chrono::Weekday::Tue
     }
 }
 impl StartDay for Wednesday {
     const NAME: &'static str = "Wednesday";
+    const WEEK_NAME: &'static str = "Week[StartDay:Wednesday]";
+    const DAYS_FROM_MONDAY: i64 = 2;
     fn weekday() -> chrono::Weekday {
         chrono::Weekday::Wed
     }
 }
 impl StartDay for Thursday {
     const NAME: &'static str = "Thursday";
+    const WEEK_NAME: &'static str = "Week[StartDay:Thursday]";
+    const DAYS_FROM_MONDAY: i64 = 3;
     fn weekday() -> chrono::Weekday {
         chrono::Weekday::Thu
     }
 }
 impl StartDay for Friday {
     const NAME: &'static str = "Friday";
+    const WEEK_NAME: &'static str = "Week[StartDay:Friday]";
+    const DAYS_FROM_MONDAY: i64 = 4;
     fn weekday() -> chrono::Weekday {
         chrono::Weekday::Fri
     }
 }
 impl StartDay for Saturday {
     const NAME: &'static str = "Saturday";
+    const WEEK_NAME: &'static str = "Week[StartDay:Saturday]";
+    const DAYS_FROM_MONDAY: i64 = 5;
     fn weekday() -> chrono::Weekday {
         chrono::Weekday::Sat
     }
 }
 impl StartDay for Sunday {
     const NAME: &'static str = "Sunday";
+    const WEEK_NAME: &'static str = "Week[StartDay:Sunday]";
+    const DAYS_FROM_MONDAY: i64 = 6;
     fn weekday() -> chrono::Weekday {
         chrono::Weekday::Sun
     }
 }
 
+/// With `serde`, a `Week` is serialized as a string, as written by `Display`, for human readable
+/// formats, or otherwise as the monotonic `i64`. The struct form `{"n":..,"start_day":..}`,
+/// previously used for all formats, is still accepted for human readable formats. For a form
+/// which doesn't depend on the epoch of the monotonic `i64` in any format, see `StartDate`.
 #[derive(Clone, Copy, Debug, Eq, PartialOrd, PartialEq, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
-#[cfg_attr(feature = "serde", serde(try_from = "Week_", into = "Week_"))]
+#[repr(transparent)]
 pub struct Week<D: StartDay> {
     n: i64,
     d: marker::PhantomData<D>,
 }
 
+// SAFETY: `Week` is `repr(transparent)` over an `i64`, as `PhantomData` is zero sized, so every
+// bit pattern is a valid `Week`
+#[cfg(feature = "bytemuck")]
+unsafe impl<D: StartDay> bytemuck::Zeroable for Week<D> {}
+#[cfg(feature = "bytemuck")]
+unsafe impl<D: StartDay> bytemuck::Pod for Week<D> {}
+
 #[cfg(feature = "serde")]
 impl<D: StartDay> TryFrom<Week_> for Week<D> {
     type Error = String;
@@ -117,37 +163,151 @@ impl<D: StartDay> TryFrom<Week_> for Week<D> {
 }
 
 #[cfg(feature = "serde")]
-impl<D: StartDay> From<Week<D>> for Week_ {
-    fn from(w: Week<D>) -> Self {
-        use alloc::string::ToString;
-        Week_ {
-            n: w.n,
-            start_day: D::NAME.to_string(),
-        }
+#[derive(serde::Deserialize)]
+struct Week_ {
+    n: i64,
+    start_day: String,
+}
+
+#[cfg(feature = "serde")]
+impl<'de, D: StartDay> serde::Deserialize<'de> for Week<D> {
+    fn deserialize<De>(deserializer: De) -> Result<Week<D>, De::Error>
+    where
+        De: serde::Deserializer<'de>,
+    {
+        crate::compact::deserialize_readable_or_legacy::<_, Week_, _>(deserializer)
     }
 }
 
 #[cfg(feature = "serde")]
-#[derive(serde::Deserialize, serde::Serialize)]
-struct Week_ {
-    n: i64,
-    start_day: String,
+impl<D: StartDay> serde::Serialize for Week<D> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        crate::compact::serialize_readable(self, serializer)
+    }
 }
 
 impl<D: StartDay> fmt::Display for Week<D> {
+    /// The alternate form, `{:#}`, is the ISO 8601 week, eg `2021-W05`. For weeks which don't
+    /// start on Monday this is the ISO week date of the first day, eg `2021-W05-7`.
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "Week starting {}", crate::DateResolution::start(self))
+        let start = crate::DateResolution::start(self);
+        if f.alternate() {
+            let iso = start.iso_week();
+            crate::write_iso_year(f, iso.year().into())?;
+            write!(f, "-W{:02}", iso.week())?;
+            if D::weekday() != chrono::Weekday::Mon {
+                write!(f, "-{}", start.weekday().number_from_monday())?;
+            }
+            Ok(())
+        } else {
+            write!(f, "Week starting {}", start)
+        }
     }
 }
 
-fn base(wd: chrono::Weekday) -> chrono::NaiveDate {
-    chrono::NaiveDate::from_ymd_opt(2021, 1, 4 + wd.num_days_from_monday()).expect("valid date")
-}
-
 impl<D: StartDay> Week<D> {
+    /// The start date of the `Week` with a monotonic index of `0`, which is the first `D` on or
+    /// after 2021-01-04, eg 2021-01-04 for `Week<Monday>` or 2021-01-10 for `Week<Sunday>`.
+    pub const EPOCH: NaiveDate =
+        NaiveDate::from_ymd_opt(2021, 1, 4 + D::DAYS_FROM_MONDAY as u32).expect("valid date");
     pub fn new(date: NaiveDate) -> Self {
         date.into()
     }
+    /// The week starting on `D2` which contains the given `weekday` of this week.
+    pub fn containing_week<D2: StartDay>(&self, weekday: chrono::Weekday) -> Week<D2> {
+        let days = (i64::from(weekday.num_days_from_monday()) - D::DAYS_FROM_MONDAY).rem_euclid(7);
+        Week::from_date(
+            crate::DateResolution::start(self) + chrono::Duration::days(days),
+            (),
+        )
+    }
+    // the middle day of the week, which decides the year the week belongs to, as with the
+    // Thursday of an ISO week
+    fn middle(&self) -> NaiveDate {
+        crate::DateResolution::start(self) + chrono::Duration::days(3)
+    }
+    /// The year this week belongs to, which is the year containing the fourth day of the week.
+    /// For `Week<Monday>` this is the ISO 8601 week-numbering year, so may differ from the year
+    /// of the start or end date for the weeks around new year.
+    pub fn year_num(&self) -> i32 {
+        self.middle().year()
+    }
+    /// The number of this week in `year_num`, from `1` to `53`, where week `1` is the first week
+    /// with its fourth day in the year. For `Week<Monday>` this is the ISO 8601 week number.
+    pub fn week_of_year(&self) -> u32 {
+        self.middle().ordinal0() / 7 + 1
+    }
+    /// The weeks starting on `D2` which overlap this week, that is, the week containing the
+    /// first day of this week and the week containing the last day. These are the same week when
+    /// `D2` is `D`, and otherwise consecutive weeks.
+    pub fn rekey<D2: StartDay>(&self) -> (Week<D2>, Week<D2>) {
+        (
+            self.containing_week(D::weekday()),
+            self.containing_week(D::weekday().pred()),
+        )
+    }
+}
+
+impl Week<Monday> {
+    /// The ISO 8601 week, eg `2021-W05`
+    #[cfg(feature = "alloc")]
+    pub fn to_iso_string(&self) -> String {
+        format!("{self:#}")
+    }
+    /// Parse an ISO 8601 week, eg `2021-W05`
+    pub fn from_iso_str(s: &str) -> Result<Self, crate::Error> {
+        parse_iso_week(s)
+    }
+}
+
+const ISO_WEEK_FORMAT: &str = "%G-W%V";
+const ISO_WEEK_DATE_FORMAT: &str = "%G-W%V-%u";
+
+// parse the forms written by the alternate `Display`, `2021-W05` for weeks starting on Monday,
+// or the ISO week date of the first day, eg `2021-W05-7`, for weeks starting on other days
+fn parse_iso_week<D: StartDay>(s: &str) -> Result<Week<D>, crate::Error> {
+    // split on the last `-W`, as the year may be negative
+    let (year, rest) = s
+        .rsplit_once("-W")
+        .ok_or_else(|| crate::Error::custom("Week", format_args!("{s}")))?;
+    let (week, weekday, format) = match rest.split_once('-') {
+        Some((week, weekday)) => (week, Some(weekday), ISO_WEEK_DATE_FORMAT),
+        None => (rest, None, ISO_WEEK_FORMAT),
+    };
+    fn field<T: str::FromStr<Err = core::num::ParseIntError>>(
+        value: &str,
+        name: &'static str,
+        position: usize,
+        input: &str,
+        format: &'static str,
+    ) -> Result<T, crate::Error> {
+        value
+            .parse()
+            .map_err(|source| crate::Error::parse_int(source, name, position, input, format))
+    }
+    let week = field(week, "week", year.len() + 2, s, format)?;
+    let year = field(year, "year", 0, s, format)?;
+    let weekday = match weekday {
+        Some(weekday) => field(weekday, "weekday", s.len() - weekday.len(), s, format)?,
+        None => 1u8,
+    };
+
+    let date = weekday
+        .checked_sub(1)
+        .and_then(|weekday| chrono::Weekday::try_from(weekday).ok())
+        .and_then(|weekday| NaiveDate::from_isoywd_opt(year, week, weekday))
+        .ok_or_else(|| crate::Error::custom("Week", format_args!("Invalid ISO week `{}`", s)))?;
+    if date.weekday() != D::weekday() {
+        return Err(crate::Error::UnexpectedStartDate {
+            date,
+            actual: date.weekday(),
+            required: D::weekday(),
+        });
+    }
+    Ok(Week::from_date(date, ()))
 }
 
 impl<D: StartDay> From<NaiveDate> for Week<D> {
@@ -158,15 +318,13 @@ impl<D: StartDay> From<NaiveDate> for Week<D> {
 
 impl<D: StartDay> str::FromStr for Week<D> {
     type Err = crate::Error;
+    /// As well as the form written by `Display`, eg `Week starting 2021-02-01`, this accepts
+    /// the ISO 8601 forms written by the alternate `Display`, eg `2021-W05` or `2021-W04-7`.
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if s.len() != 24 {
-            return Err(crate::Error::UnexpectedInputLength {
-                actual: s.len(),
-                required: 24,
-                format: "Week starting %Y-%m-%d",
-            });
-        }
-        let date = chrono::NaiveDate::parse_from_str(&s[14..24], "%Y-%m-%d")?;
+        let Some(start) = s.strip_prefix("Week starting ") else {
+            return parse_iso_week(s);
+        };
+        let date = chrono::NaiveDate::parse_from_str(start, "%Y-%m-%d")?;
         if date.weekday() != D::weekday() {
             return Err(crate::Error::UnexpectedStartDate {
                 date,
@@ -175,7 +333,7 @@ impl<D: StartDay> str::FromStr for Week<D> {
             });
         };
 
-        let week_num = (date - base(D::weekday())).num_days() / 7;
+        let week_num = (date - Self::EPOCH).num_days().div_euclid(7);
 
         Ok(Week::from_monotonic(week_num))
     }
@@ -183,14 +341,14 @@ impl<D: StartDay> str::FromStr for Week<D> {
 
 impl<D: StartDay> DateResolution for Week<D> {
     fn start(&self) -> chrono::NaiveDate {
-        base(D::weekday()) + chrono::Duration::days(self.n * 7)
+        Self::EPOCH + chrono::Duration::days(self.n * 7)
     }
     type Params = ();
 
     fn params(&self) -> Self::Params {}
 
     fn from_date(date: NaiveDate, _params: Self::Params) -> Self {
-        let week_num = (date - base(D::weekday())).num_days() / 7;
+        let week_num = (date - Self::EPOCH).num_days().div_euclid(7);
 
         Week::from_monotonic(week_num)
     }
@@ -198,18 +356,31 @@ impl<D: StartDay> DateResolution for Week<D> {
 
 impl<D: StartDay> crate::TimeResolution for Week<D> {
     fn succ_n(&self, n: u64) -> Week<D> {
-        Week::from_monotonic(self.n + i64::try_from(n).unwrap())
+        self.checked_succ_n(n).expect("Week out of range")
     }
     fn pred_n(&self, n: u64) -> Week<D> {
-        Week::from_monotonic(self.n - i64::try_from(n).unwrap())
+        self.checked_pred_n(n).expect("Week out of range")
+    }
+    fn checked_succ_n(&self, n: u64) -> Option<Week<D>> {
+        self.n.checked_add_unsigned(n).map(Week::from_monotonic)
+    }
+    fn checked_pred_n(&self, n: u64) -> Option<Week<D>> {
+        self.n.checked_sub_unsigned(n).map(Week::from_monotonic)
     }
     fn start_datetime(&self) -> DateTime<Utc> {
         crate::DateResolution::start(self)
             .and_time(NaiveTime::MIN)
             .and_utc()
     }
-    fn name(&self) -> String {
-        format!("Week[StartDay:{}]", D::NAME)
+    fn write_name(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        out.write_str(D::WEEK_NAME)
+    }
+    #[cfg(feature = "alloc")]
+    fn name(&self) -> borrow::Cow<'static, str> {
+        D::WEEK_NAME.into()
+    }
+    fn fixed_duration() -> Option<chrono::Duration> {
+        Some(chrono::Duration::days(7))
     }
 }
 
@@ -231,16 +402,33 @@ impl<D: StartDay> crate::FromMonotonic for Week<D> {
     }
 }
 
+// the number of days from 0000-01-01 to the `EPOCH` for weeks starting on Monday
+const BASE_DAY: i64 = 738_159;
+
+impl<D: StartDay> crate::Bounded for Week<D> {
+    // the first week starting on or after the first supported date
+    const MIN: Week<D> = Week {
+        n: -((BASE_DAY + D::DAYS_FROM_MONDAY - crate::MIN_DAY).div_euclid(7)),
+        d: marker::PhantomData,
+    };
+    // the last week where the following week starts on or before the last supported date
+    const MAX: Week<D> = Week {
+        n: (crate::MAX_DAY - BASE_DAY - D::DAYS_FROM_MONDAY).div_euclid(7) - 1,
+        d: marker::PhantomData,
+    };
+}
+
 impl<D: StartDay> From<DateTime<Utc>> for Week<D> {
     fn from(date: DateTime<Utc>) -> Self {
         date.date_naive().into()
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "alloc"))]
 mod tests {
     use super::*;
-    use crate::{DateResolution, TimeResolution};
+    use crate::{DateResolution, Monotonic, TimeResolution};
+    use alloc::string::ToString;
 
     #[test]
     #[cfg(feature = "serde")]
@@ -315,4 +503,139 @@ mod tests {
             .is_err(),);
         assert!("Week starting 2021-12-06".parse::<Week<Sunday>>().is_err(),);
     }
+
+    #[test]
+    fn test_parse_iso() {
+        let date = chrono::NaiveDate::from_ymd_opt(2021, 2, 1).unwrap();
+        let week = Week::<Monday>::from(date);
+        assert_eq!(week.to_iso_string(), "2021-W05");
+        assert_eq!(Week::from_iso_str("2021-W05").unwrap(), week);
+        assert_eq!("2021-W05".parse::<Week<Monday>>().unwrap(), week);
+        assert_eq!("2021-W05-1".parse::<Week<Monday>>().unwrap(), week);
+
+        let sunday = Week::<Sunday>::from(date);
+        assert_eq!(
+            format!("{sunday:#}").parse::<Week<Sunday>>().unwrap(),
+            sunday
+        );
+
+        // the first ISO week of 2021 starts in 2020
+        let first = Week::<Monday>::from_iso_str("2021-W01").unwrap();
+        assert_eq!(
+            first.start(),
+            chrono::NaiveDate::from_ymd_opt(2021, 1, 4).unwrap()
+        );
+        assert_eq!(
+            Week::<Monday>::from(chrono::NaiveDate::from_ymd_opt(2021, 1, 1).unwrap())
+                .to_iso_string(),
+            "2020-W53"
+        );
+
+        assert!(matches!(
+            "2021-W05".parse::<Week<Sunday>>(),
+            Err(crate::Error::UnexpectedStartDate { .. })
+        ));
+        assert!(matches!(
+            "2021-W0x".parse::<Week<Monday>>(),
+            Err(crate::Error::ParseIntDetailed {
+                field: "week",
+                position: 6,
+                ..
+            })
+        ));
+        for input in [
+            "2021-W54",
+            "2021-W00",
+            "2021-W05-8",
+            "2021-05",
+            "Week starting",
+            "é",
+        ] {
+            assert!(input.parse::<Week<Monday>>().is_err(), "{input}");
+        }
+    }
+
+    #[test]
+    fn test_rekey() {
+        use crate::DateResolutionExt;
+
+        let date = chrono::NaiveDate::from_ymd_opt(2021, 12, 8).unwrap();
+        let week = Week::<Monday>::from(date);
+
+        let (first, last) = week.rekey::<Sunday>();
+        assert_eq!(
+            first.start(),
+            chrono::NaiveDate::from_ymd_opt(2021, 12, 5).unwrap()
+        );
+        assert_eq!(first.succ(), last);
+        assert_eq!(last.start(), week.end());
+
+        let (first, last) = week.rekey::<Monday>();
+        assert_eq!(first, week);
+        assert_eq!(last, week);
+
+        assert_eq!(
+            week.containing_week::<Sunday>(chrono::Weekday::Wed),
+            Week::<Sunday>::from(date)
+        );
+        assert_eq!(
+            week.containing_week::<Sunday>(chrono::Weekday::Sun),
+            Week::<Sunday>::from(week.end())
+        );
+        assert_eq!(
+            week.containing_week::<Thursday>(chrono::Weekday::Thu)
+                .start(),
+            chrono::NaiveDate::from_ymd_opt(2021, 12, 9).unwrap()
+        );
+
+        // every day of the week is in one of the two overlapping weeks
+        let week = Week::<Thursday>::from(date);
+        let (first, last) = week.rekey::<Tuesday>();
+        for day in week.start().iter_days().take(7) {
+            assert!(first.contains_date(day) || last.contains_date(day));
+        }
+    }
+
+    #[test]
+    fn test_week_of_year() {
+        let mut date = chrono::NaiveDate::from_ymd_opt(2019, 12, 1).unwrap();
+        while date.year() < 2027 {
+            let week = Week::<Monday>::from(date);
+            let iso = date.iso_week();
+            assert_eq!(week.week_of_year(), iso.week(), "{date}");
+            assert_eq!(week.year_num(), iso.year(), "{date}");
+            date = date.succ_opt().unwrap();
+        }
+
+        // 2021-01-01 is a Friday, so the Sunday week starting 2020-12-27 belongs to 2020
+        let date = chrono::NaiveDate::from_ymd_opt(2021, 1, 1).unwrap();
+        let week = Week::<Sunday>::from(date);
+        assert_eq!((week.year_num(), week.week_of_year()), (2020, 53));
+        assert_eq!(
+            (week.succ().year_num(), week.succ().week_of_year()),
+            (2021, 1)
+        );
+        let week = Week::<Friday>::from(date);
+        assert_eq!((week.year_num(), week.week_of_year()), (2021, 1));
+        assert_eq!(
+            (week.pred().year_num(), week.pred().week_of_year()),
+            (2020, 52)
+        );
+    }
+
+    #[test]
+    fn test_pre_epoch() {
+        let dt = chrono::NaiveDate::from_ymd_opt(2021, 1, 3).unwrap();
+        let wk = Week::<Monday>::from(dt);
+        assert_eq!(
+            wk.start(),
+            chrono::NaiveDate::from_ymd_opt(2020, 12, 28).unwrap()
+        );
+        assert_eq!(wk.to_monotonic(), -1);
+
+        let dt = chrono::NaiveDate::from_ymd_opt(-1, 6, 15).unwrap();
+        let wk = Week::<Sunday>::from(dt);
+        assert!(wk.start() <= dt && wk.succ().start() > dt);
+        assert_eq!(wk, wk.to_string().parse().unwrap());
+    }
 }
@@ -1,16 +1,24 @@
 use crate::{month, DateResolution, DateResolutionExt};
-use alloc::string::{String, ToString};
+#[cfg(feature = "alloc")]
+use alloc::borrow;
 use chrono::{DateTime, Datelike, NaiveDate, NaiveTime, Utc};
 use core::{convert::TryFrom, fmt, str};
 
 #[derive(Clone, Copy, Debug, Eq, PartialOrd, PartialEq, Ord, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[cfg_attr(feature = "serde", serde(transparent))]
+#[repr(transparent)]
 pub struct Year(i64);
 
+// SAFETY: `Year` is `repr(transparent)` over an `i64`, so every bit pattern is a valid `Year`
+#[cfg(feature = "bytemuck")]
+unsafe impl bytemuck::Zeroable for Year {}
+#[cfg(feature = "bytemuck")]
+unsafe impl bytemuck::Pod for Year {}
+
 impl crate::DateResolution for Year {
     fn start(&self) -> chrono::NaiveDate {
-        chrono::NaiveDate::from_ymd_opt(self.year_num(), 1, 1).expect("valid time")
+        self.try_start().expect("Not pre/post historic")
     }
     type Params = ();
 
@@ -29,17 +37,27 @@ impl From<NaiveDate> for Year {
 
 impl crate::TimeResolution for Year {
     fn succ_n(&self, n: u64) -> Year {
-        Year(self.0 + i64::try_from(n).unwrap())
+        self.checked_succ_n(n).expect("Year out of range")
     }
     fn pred_n(&self, n: u64) -> Year {
-        Year(self.0 - i64::try_from(n).unwrap())
+        self.checked_pred_n(n).expect("Year out of range")
+    }
+    fn checked_succ_n(&self, n: u64) -> Option<Year> {
+        self.0.checked_add_unsigned(n).map(Year)
+    }
+    fn checked_pred_n(&self, n: u64) -> Option<Year> {
+        self.0.checked_sub_unsigned(n).map(Year)
     }
     fn start_datetime(&self) -> DateTime<Utc> {
         self.start().and_time(NaiveTime::MIN).and_utc()
     }
 
-    fn name(&self) -> String {
-        "Year".to_string()
+    fn write_name(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        out.write_str("Year")
+    }
+    #[cfg(feature = "alloc")]
+    fn name(&self) -> borrow::Cow<'static, str> {
+        "Year".into()
     }
 }
 
@@ -58,6 +76,11 @@ impl crate::FromMonotonic for Year {
     }
 }
 
+impl crate::Bounded for Year {
+    const MIN: Year = Year(crate::MIN_YEAR);
+    const MAX: Year = Year(crate::MAX_YEAR - 1);
+}
+
 impl From<DateTime<Utc>> for Year {
     fn from(d: DateTime<Utc>) -> Self {
         d.date_naive().into()
@@ -65,6 +88,8 @@ impl From<DateTime<Utc>> for Year {
 }
 
 impl Year {
+    /// The start date of the `Year` with a monotonic index of `0`, the year 0.
+    pub const EPOCH: NaiveDate = NaiveDate::from_ymd_opt(0, 1, 1).expect("valid date");
     pub fn first_month(&self) -> month::Month {
         self.start().into()
     }
@@ -78,30 +103,152 @@ impl Year {
         self.end().into()
     }
     pub fn year_num(&self) -> i32 {
-        i32::try_from(self.0).expect("Not pre/post historic")
+        self.try_year_num().expect("Not pre/post historic")
+    }
+    /// As `year_num`, but returning an error rather than panicking if the year doesn't fit in an
+    /// `i32`.
+    pub fn try_year_num(&self) -> Result<i32, crate::Error> {
+        i32::try_from(self.0).map_err(|_| crate::Error::YearOutOfRange { year: self.0 })
+    }
+    /// As `start`, but returning an error rather than panicking if the year is outside the range
+    /// supported by `chrono`.
+    pub fn try_start(&self) -> Result<NaiveDate, crate::Error> {
+        chrono::NaiveDate::from_ymd_opt(self.try_year_num()?, 1, 1)
+            .ok_or(crate::Error::YearOutOfRange { year: self.0 })
     }
     pub fn new(year: i32) -> Self {
         Year(i64::from(year))
     }
+    /// The year `year`, or an error if it is outside the range supported by `chrono`.
+    pub fn from_parts(year: i32) -> Result<Self, crate::Error> {
+        NaiveDate::from_ymd_opt(year, 1, 1)
+            .map(Year::from)
+            .ok_or(crate::Error::YearOutOfRange { year: year.into() })
+    }
+    /// Whether this year has a 29th of February.
+    pub fn is_leap(&self) -> bool {
+        self.start().leap_year()
+    }
+    /// The number of days in this year, either `365` or `366`.
+    pub fn num_days(&self) -> i64 {
+        if self.is_leap() {
+            366
+        } else {
+            365
+        }
+    }
+    /// The number of hours in this year, either `8760` or `8784`.
+    pub fn num_hours(&self) -> u64 {
+        self.num_sub_periods::<crate::Hour>()
+    }
+    /// The number of five minute periods in this year, either `105120` or `105408`.
+    pub fn num_five_minutes(&self) -> u64 {
+        self.num_sub_periods::<crate::FiveMinute>()
+    }
+    /// Every day of this year, in order.
+    pub fn days(&self) -> crate::TimeRange<super::Day> {
+        self.rescale()
+    }
+    /// The twelve months of this year, in order.
+    pub fn months(&self) -> crate::TimeRange<month::Month> {
+        self.rescale()
+    }
+    /// The four quarters of this year, in order.
+    pub fn quarters(&self) -> crate::TimeRange<super::Quarter> {
+        self.rescale()
+    }
 }
 
 impl fmt::Display for Year {
+    /// The alternate form, `{:#}`, is ISO 8601, eg `2021`, or `0021` for the year 21.
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.0)
+        if f.alternate() {
+            crate::write_iso_year(f, self.0)
+        } else {
+            write!(f, "{}", self.0)
+        }
     }
 }
 
 impl str::FromStr for Year {
     type Err = crate::Error;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(Year(s.parse()?))
+        let year = s
+            .parse()
+            .map_err(|source| crate::Error::parse_int(source, "year", 0, s, "%Y"))?;
+        Ok(Year(year))
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "alloc"))]
 mod tests {
     use super::*;
     use crate::{DateResolution, TimeResolution};
+    use alloc::string::ToString;
+
+    #[test]
+    fn test_days() {
+        let days = Year::new(2020).days();
+        assert_eq!(days.len().get(), 366);
+        assert_eq!(days.start().to_string(), "2020-01-01");
+        assert_eq!(days.end().to_string(), "2020-12-31");
+        assert_eq!(Year::new(2021).days().len().get(), 365);
+    }
+
+    #[test]
+    fn test_from_parts() {
+        assert_eq!(Year::from_parts(2021).unwrap(), Year::new(2021));
+        assert_eq!(Year::from_parts(-1).unwrap().to_string(), "-1");
+        assert!(matches!(
+            Year::from_parts(i32::MAX),
+            Err(crate::Error::YearOutOfRange { year }) if year == i64::from(i32::MAX)
+        ));
+        assert!(Year::from_parts(i32::MIN).is_err());
+    }
+
+    #[test]
+    fn test_num_days() {
+        for (year, leap) in [
+            (2020, true),
+            (2021, false),
+            (2000, true),
+            (1900, false),
+            (-4, true),
+        ] {
+            let year = Year::new(year);
+            assert_eq!(year.is_leap(), leap);
+            assert_eq!(year.num_days(), DateResolutionExt::num_days(&year));
+        }
+    }
+
+    #[test]
+    fn test_num_sub_periods() {
+        assert_eq!(Year::new(2020).num_hours(), 366 * 24);
+        assert_eq!(Year::new(2021).num_hours(), 365 * 24);
+        assert_eq!(Year::new(2020).num_five_minutes(), 366 * 288);
+        assert_eq!(
+            Year::new(2021).num_five_minutes(),
+            Year::new(2021).count_in::<crate::FiveMinute>()
+        );
+        assert_eq!(
+            Year::new(2021).num_sub_periods::<crate::Minutes<1440>>(),
+            365
+        );
+    }
+
+    #[test]
+    fn test_months_and_quarters() {
+        let year = Year::new(2021);
+        let months = year.months();
+        assert_eq!(months.len().get(), 12);
+        assert_eq!(months.start(), year.first_month());
+        assert_eq!(months.end(), year.last_month());
+
+        let quarters = year.quarters();
+        assert_eq!(quarters.len().get(), 4);
+        assert_eq!(quarters.start().to_string(), "Q1-2021");
+        assert_eq!(quarters.end().to_string(), "Q4-2021");
+    }
 
     #[test]
     #[cfg(feature = "serde")]
@@ -1,12 +1,11 @@
 use crate::DateResolution;
 use crate::DateResolutionExt;
+use crate::FromMonotonic;
 use crate::LongerThan;
 use crate::LongerThanOrEqual;
 use crate::Monotonic;
 use crate::SubDateResolution;
 use crate::TimeResolution;
-use alloc::format;
-use alloc::string::String;
 use chrono::DateTime;
 use chrono::FixedOffset;
 use chrono::NaiveDate;
@@ -16,6 +15,7 @@ use chrono::TimeDelta;
 use chrono::TimeZone;
 use chrono::Utc;
 use core::fmt;
+use core::str;
 
 pub trait FixedTimeZone: TimeZone + Copy + fmt::Debug {
     fn new() -> Self;
@@ -27,6 +27,59 @@ impl FixedTimeZone for Utc {
     }
 }
 
+/// `FixedEast` is a zero sized `FixedTimeZone` which is `SECONDS` east of UTC. This allows defining
+/// marker types for fixed time zones, such that `Zoned` periods in them can be stored as bare `i64`s
+/// via `Monotonic` and `FromMonotonic`, for example:
+///
+/// ```
+/// type Aest = resolution::FixedEast<{ 10 * 60 * 60 }>;
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct FixedEast<const SECONDS: i32>;
+
+impl<const SECONDS: i32> FixedEast<SECONDS> {
+    // evaluated at compile time, so an out of range offset is a compile error wherever it is used
+    const OFFSET: FixedOffset = match FixedOffset::east_opt(SECONDS) {
+        Some(offset) => offset,
+        None => panic!("FixedEast offset must be less than one day"),
+    };
+}
+
+impl<const SECONDS: i32> fmt::Debug for FixedEast<SECONDS> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "FixedEast[{}]", Self::OFFSET)
+    }
+}
+
+impl<const SECONDS: i32> TimeZone for FixedEast<SECONDS> {
+    type Offset = FixedOffset;
+
+    fn from_offset(_: &Self::Offset) -> Self {
+        FixedEast
+    }
+    fn offset_from_local_date(&self, _: &NaiveDate) -> chrono::MappedLocalTime<Self::Offset> {
+        chrono::MappedLocalTime::Single(Self::OFFSET)
+    }
+    fn offset_from_local_datetime(
+        &self,
+        _: &chrono::NaiveDateTime,
+    ) -> chrono::MappedLocalTime<Self::Offset> {
+        chrono::MappedLocalTime::Single(Self::OFFSET)
+    }
+    fn offset_from_utc_date(&self, _: &NaiveDate) -> Self::Offset {
+        Self::OFFSET
+    }
+    fn offset_from_utc_datetime(&self, _: &chrono::NaiveDateTime) -> Self::Offset {
+        Self::OFFSET
+    }
+}
+
+impl<const SECONDS: i32> FixedTimeZone for FixedEast<SECONDS> {
+    fn new() -> Self {
+        FixedEast
+    }
+}
+
 /// `Zoned` stores a `TimeResolution` representing the local time in the zone, plus the relevant
 /// offset and zone itself. This is intended to allow assertion that a given resolution is in a certain
 /// timezone and thus allow finding the start and end times of that resolution with their correct UTC offsets.
@@ -35,7 +88,8 @@ impl FixedTimeZone for Utc {
 /// this restriction will be removed later)
 ///
 /// note: this works perfectly well with _fixed_ and _non-fixed_ timezones, but many implementations are only
-/// available for fixed timezones.
+/// available for fixed timezones. With the `tz` feature, `TimeResolution` is also implemented for `chrono_tz::Tz`,
+/// where the offset is found from the zone after each step so that iterating crosses DST changes correctly.
 pub struct Zoned<R, Z>
 where
     R: TimeResolution,
@@ -58,10 +112,14 @@ where
     R: SubDateResolution<Params = ()>,
     Z: FixedTimeZone,
 {
-    fn deserialize<D>(deserializer: D) -> std::result::Result<Zoned<R, Z>, D::Error>
+    fn deserialize<D>(deserializer: D) -> Result<Zoned<R, Z>, D::Error>
     where
         D: serde::de::Deserializer<'de>,
     {
+        if !deserializer.is_human_readable() {
+            let start = deserialize_timestamp::<D>(i64::deserialize(deserializer)?)?;
+            return Ok(start.with_timezone(&Z::new()).into());
+        }
         let local = chrono::NaiveDateTime::deserialize(deserializer)?;
 
         // unwrap here is fine because by the rules of `FixedTimeZone`
@@ -73,7 +131,7 @@ where
 }
 
 #[cfg(feature = "serde")]
-impl<'de, R, Z> serde::Serialize for Zoned<R, Z>
+impl<R, Z> serde::Serialize for Zoned<R, Z>
 where
     R: SubDateResolution<Params = ()>,
     Z: FixedTimeZone,
@@ -82,34 +140,287 @@ where
     where
         S: serde::Serializer,
     {
+        if !serializer.is_human_readable() {
+            return serializer.serialize_i64(self.start_datetime().timestamp());
+        }
         self.local_start_datetime()
             .naive_local()
             .serialize(serializer)
     }
 }
 
+// binary formats store the start of the period as a UTC timestamp, in seconds
+#[cfg(feature = "serde")]
+fn deserialize_timestamp<'de, D: serde::de::Deserializer<'de>>(
+    timestamp: i64,
+) -> Result<DateTime<Utc>, D::Error> {
+    DateTime::from_timestamp(timestamp, 0).ok_or_else(|| {
+        serde::de::Error::custom(format_args!("Timestamp {} is out of range", timestamp))
+    })
+}
+
+#[cfg(all(feature = "serde", feature = "tz"))]
+#[derive(serde::Deserialize, serde::Serialize)]
+struct ZonedTz_ {
+    start: DateTime<FixedOffset>,
+    zone: chrono_tz::Tz,
+}
+
+#[cfg(all(feature = "serde", feature = "tz"))]
+impl<'de, R> serde::de::Deserialize<'de> for Zoned<R, chrono_tz::Tz>
+where
+    R: SubDateResolution<Params = ()>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Zoned<R, chrono_tz::Tz>, D::Error>
+    where
+        D: serde::de::Deserializer<'de>,
+    {
+        if !deserializer.is_human_readable() {
+            let (timestamp, zone) = <(i64, chrono_tz::Tz)>::deserialize(deserializer)?;
+            let start = deserialize_timestamp::<D>(timestamp)?;
+            return Ok(start.with_timezone(&zone).into());
+        }
+        let zoned = ZonedTz_::deserialize(deserializer)?;
+        Ok(zoned.start.with_timezone(&zoned.zone).into())
+    }
+}
+
+#[cfg(all(feature = "serde", feature = "tz"))]
+impl<R> serde::Serialize for Zoned<R, chrono_tz::Tz>
+where
+    R: SubDateResolution<Params = ()>,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        if !serializer.is_human_readable() {
+            return (self.start_datetime().timestamp(), self.zone).serialize(serializer);
+        }
+        ZonedTz_ {
+            start: self.local_start_datetime().fixed_offset(),
+            zone: self.zone,
+        }
+        .serialize(serializer)
+    }
+}
+
+#[cfg(feature = "tz")]
+fn tz_from_name(name: &str) -> Result<chrono_tz::Tz, crate::Error> {
+    name.parse()
+        .map_err(|_| crate::Error::custom("Tz", format_args!("{name}")))
+}
+
+#[cfg(feature = "tz")]
+impl<R> Zoned<R, chrono_tz::Tz>
+where
+    R: SubDateResolution<Params = ()>,
+{
+    /// Find the period containing `datetime` in the IANA time zone called `name`, eg `Australia/Sydney`
+    pub fn from_utc_in_tz(datetime: DateTime<Utc>, name: &str) -> Result<Self, crate::Error> {
+        Ok(datetime.with_timezone(&tz_from_name(name)?).into())
+    }
+}
+
+#[cfg(feature = "tz")]
+impl<R> Zoned<R, chrono_tz::Tz>
+where
+    R: DateResolution<Params = ()>,
+{
+    /// Find the period containing the local `date` in the IANA time zone called `name`, eg `Australia/Sydney`
+    pub fn from_date_in_tz(date: NaiveDate, name: &str) -> Result<Self, crate::Error> {
+        Ok(Zoned::from_date(date, tz_from_name(name)?))
+    }
+}
+
+impl<R, Z> Zoned<R, Z>
+where
+    R: TimeResolution,
+    Z: TimeZone + Copy + fmt::Debug,
+{
+    // for zones whose offset never changes, where stepping is the same as stepping the local period
+    fn step_local(&self, n: u64, fwd: bool) -> Option<Self> {
+        Some(Zoned {
+            local_resolution: if fwd {
+                self.local_resolution.checked_succ_n(n)?
+            } else {
+                self.local_resolution.checked_pred_n(n)?
+            },
+            ..*self
+        })
+    }
+
+    fn write_zoned_name(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        out.write_str("Zoned[")?;
+        self.local_resolution.write_name(out)?;
+        write!(out, ",{:?}]", self.zone)
+    }
+}
+
 impl<R, Z> TimeResolution for Zoned<R, Z>
 where
     R: TimeResolution,
     Z: FixedTimeZone,
 {
     fn succ_n(&self, n: u64) -> Self {
-        Zoned {
-            local_resolution: self.local_resolution.succ_n(n),
-            ..*self
-        }
+        self.step_local(n, true)
+            .expect("Step is within the supported range of periods")
     }
     fn pred_n(&self, n: u64) -> Self {
-        Zoned {
-            local_resolution: self.local_resolution.pred_n(n),
-            ..*self
-        }
+        self.step_local(n, false)
+            .expect("Step is within the supported range of periods")
+    }
+    fn checked_succ_n(&self, n: u64) -> Option<Self> {
+        self.step_local(n, true)
+    }
+    fn checked_pred_n(&self, n: u64) -> Option<Self> {
+        self.step_local(n, false)
     }
     fn start_datetime(&self) -> DateTime<Utc> {
         self.utc_start_datetime()
     }
-    fn name(&self) -> String {
-        format!("Zoned[{},{:?}]", self.local_resolution.name(), self.zone)
+    fn write_name(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        self.write_zoned_name(out)
+    }
+    fn fixed_duration() -> Option<chrono::Duration> {
+        // the offset of a `FixedTimeZone` never changes
+        R::fixed_duration()
+    }
+}
+
+// `FixedOffset` can't be a `FixedTimeZone` as there is no sensible default offset, but it still
+// never changes offset
+impl<R> TimeResolution for Zoned<R, FixedOffset>
+where
+    R: TimeResolution,
+{
+    fn succ_n(&self, n: u64) -> Self {
+        self.step_local(n, true)
+            .expect("Step is within the supported range of periods")
+    }
+    fn pred_n(&self, n: u64) -> Self {
+        self.step_local(n, false)
+            .expect("Step is within the supported range of periods")
+    }
+    fn checked_succ_n(&self, n: u64) -> Option<Self> {
+        self.step_local(n, true)
+    }
+    fn checked_pred_n(&self, n: u64) -> Option<Self> {
+        self.step_local(n, false)
+    }
+    fn start_datetime(&self) -> DateTime<Utc> {
+        self.utc_start_datetime()
+    }
+    fn write_name(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        self.write_zoned_name(out)
+    }
+    fn fixed_duration() -> Option<chrono::Duration> {
+        R::fixed_duration()
+    }
+}
+
+impl<R> Zoned<R, FixedOffset>
+where
+    R: SubDateResolution<Params = ()>,
+{
+    /// Find the period containing `datetime`, keeping only its current offset rather than its
+    /// zone. This allows using zones which aren't `Copy`, or where only the current offset is
+    /// relevant, for example `Zoned::with_fixed_offset(chrono::Local::now())`.
+    ///
+    /// Periods stepped to from the returned value also use this offset, even across DST changes
+    /// in the original zone.
+    pub fn with_fixed_offset<Z: TimeZone>(datetime: DateTime<Z>) -> Self {
+        datetime.fixed_offset().into()
+    }
+}
+
+#[cfg(feature = "tz")]
+impl<R> TimeResolution for Zoned<R, chrono_tz::Tz>
+where
+    R: TimeResolution,
+{
+    fn succ_n(&self, n: u64) -> Self {
+        self.step_in_tz(n, true)
+            .expect("Step is within the supported range of periods")
+    }
+    fn pred_n(&self, n: u64) -> Self {
+        self.step_in_tz(n, false)
+            .expect("Step is within the supported range of periods")
+    }
+    fn checked_succ_n(&self, n: u64) -> Option<Self> {
+        self.step_in_tz(n, true)
+    }
+    fn checked_pred_n(&self, n: u64) -> Option<Self> {
+        self.step_in_tz(n, false)
+    }
+    fn start_datetime(&self) -> DateTime<Utc> {
+        self.utc_start_datetime()
+    }
+    fn write_name(&self, out: &mut dyn fmt::Write) -> fmt::Result {
+        self.write_zoned_name(out)
+    }
+    fn fixed_duration() -> Option<chrono::Duration> {
+        // sub-daily periods are always the same elapsed time, but local days can be 23 or 25 hours
+        R::fixed_duration().filter(|period| *period < TimeDelta::days(1))
+    }
+}
+
+#[cfg(feature = "tz")]
+impl<R> Zoned<R, chrono_tz::Tz>
+where
+    R: TimeResolution,
+{
+    // step `n` periods, finding the offset from the zone for the new period rather than
+    // reusing the current offset, which may no longer apply after a DST change. Returns `None`
+    // if the new period is out of range.
+    fn step_in_tz(&self, n: u64, forward: bool) -> Option<Self> {
+        match R::fixed_duration() {
+            // sub-daily periods are a fixed amount of elapsed time, so step in UTC and then
+            // find the local period, as local times may be skipped or repeated by a DST change
+            Some(period) if period < TimeDelta::days(1) => {
+                let elapsed = i64::try_from(n)
+                    .ok()
+                    .and_then(|n| period.num_milliseconds().checked_mul(n))
+                    .and_then(TimeDelta::try_milliseconds)?;
+                let utc = if forward {
+                    self.utc_start_datetime().checked_add_signed(elapsed)?
+                } else {
+                    self.utc_start_datetime().checked_sub_signed(elapsed)?
+                };
+                let local = utc.with_timezone(&self.zone);
+                let local_steps = (local.naive_local() - self.local_start_datetime().naive_local())
+                    .num_milliseconds()
+                    / period.num_milliseconds();
+                Some(Zoned {
+                    local_resolution: if local_steps >= 0 {
+                        self.local_resolution
+                            .checked_succ_n(local_steps.unsigned_abs())?
+                    } else {
+                        self.local_resolution
+                            .checked_pred_n(local_steps.unsigned_abs())?
+                    },
+                    current_offset: local.offset().fix(),
+                    zone: self.zone,
+                })
+            }
+            // otherwise the periods are made up of local days, so step in local time and then
+            // find the offset at the start of the new period
+            _ => {
+                let local_resolution = if forward {
+                    self.local_resolution.checked_succ_n(n)?
+                } else {
+                    self.local_resolution.checked_pred_n(n)?
+                };
+                Some(Zoned {
+                    local_resolution,
+                    current_offset: local_offset_at_start_of_date(
+                        local_resolution.start_datetime().date_naive(),
+                        self.zone,
+                    ),
+                    zone: self.zone,
+                })
+            }
+        }
     }
 }
 
@@ -142,6 +453,25 @@ where
     }
 }
 
+impl<R, Z> Zoned<R, Z>
+where
+    R: SubDateResolution<Params = ()>,
+    Z: TimeZone + Copy + fmt::Debug,
+{
+    /// Re-express this period in another `zone`, as the period in that zone containing the same
+    /// UTC start time.
+    ///
+    /// When the offset between the zones is not a multiple of the period length (for example
+    /// half-hours between `Australia/Sydney` and `Asia/Kathmandu`), the periods don't line up and
+    /// the returned period will start before this one.
+    pub fn to_zone<Z2>(&self, zone: Z2) -> Zoned<R, Z2>
+    where
+        Z2: TimeZone + Copy + fmt::Debug,
+    {
+        self.utc_start_datetime().with_timezone(&zone).into()
+    }
+}
+
 impl<R, Z> Zoned<R, Z>
 where
     R: TimeResolution,
@@ -186,9 +516,153 @@ where
     }
 }
 
+/// Written as the local period followed by the zone, eg `2021-01-01 FixedEast[+10:00]`
+impl<R, Z> fmt::Display for Zoned<R, Z>
+where
+    R: TimeResolution,
+    Z: FixedTimeZone,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {:?}", self.local_resolution, self.zone)
+    }
+}
+
+// whether `expected` is exactly what `Debug` writes for `value`, compared as it is written rather
+// than by allocating the output
+fn debug_matches(value: &impl fmt::Debug, expected: &str) -> bool {
+    struct Matcher<'a>(&'a str);
+
+    impl fmt::Write for Matcher<'_> {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            self.0 = self.0.strip_prefix(s).ok_or(fmt::Error)?;
+            Ok(())
+        }
+    }
+
+    let mut matcher = Matcher(expected);
+    fmt::write(&mut matcher, format_args!("{:?}", value)).is_ok() && matcher.0.is_empty()
+}
+
+impl<R, Z> str::FromStr for Zoned<R, Z>
+where
+    R: TimeResolution,
+    Z: FixedTimeZone,
+{
+    type Err = crate::Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let zone = Z::new();
+        // the zone is written last, and may itself contain spaces
+        let local = s
+            .rmatch_indices(' ')
+            .find(|(idx, _)| debug_matches(&zone, &s[idx + 1..]))
+            .map(|(idx, _)| &s[..idx])
+            .ok_or_else(|| crate::Error::custom("Zoned", format_args!("{s}")))?;
+        let local_resolution = local.parse::<R>().map_err(|e| e.within(0, s))?;
+        Ok(Zoned {
+            local_resolution,
+            // the offset of a `FixedTimeZone` never changes, so can be found at any time
+            current_offset: zone
+                .offset_from_utc_datetime(&chrono::NaiveDateTime::default())
+                .fix(),
+            zone,
+        })
+    }
+}
+
+/// Written as the local period followed by the offset, eg `2021-01-01 +10:00`
+impl<R> fmt::Display for Zoned<R, FixedOffset>
+where
+    R: TimeResolution,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.local_resolution, self.zone)
+    }
+}
+
+impl<R> str::FromStr for Zoned<R, FixedOffset>
+where
+    R: TimeResolution,
+{
+    type Err = crate::Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (local, offset) = s
+            .rsplit_once(' ')
+            .ok_or_else(|| crate::Error::custom("Zoned", format_args!("{s}")))?;
+        let local_resolution = local.parse::<R>().map_err(|e| e.within(0, s))?;
+        let zone = offset.parse::<FixedOffset>()?;
+        Ok(Zoned {
+            local_resolution,
+            current_offset: zone,
+            zone,
+        })
+    }
+}
+
+/// Written as the local period followed by its offset and the zone, eg
+/// `2021-01-01 +11:00 Australia/Sydney`, as the offset is needed for the local periods which are
+/// repeated when the offset decreases.
+#[cfg(feature = "tz")]
+impl<R> fmt::Display for Zoned<R, chrono_tz::Tz>
+where
+    R: TimeResolution,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {}",
+            self.local_resolution, self.current_offset, self.zone
+        )
+    }
+}
+
+#[cfg(feature = "tz")]
+impl<R> str::FromStr for Zoned<R, chrono_tz::Tz>
+where
+    R: TimeResolution,
+{
+    type Err = crate::Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || crate::Error::custom("Zoned", format_args!("{s}"));
+        let (rest, zone) = s.rsplit_once(' ').ok_or_else(invalid)?;
+        let (local, offset) = rest.rsplit_once(' ').ok_or_else(invalid)?;
+        let local_resolution = local.parse::<R>().map_err(|e| e.within(0, s))?;
+        let current_offset = offset.parse::<FixedOffset>()?;
+        let zone = tz_from_name(zone)?;
+        // the offset must apply in the zone at the local start, unless the start was skipped
+        let local_start = local_resolution.start_datetime().naive_utc();
+        let valid = match zone.offset_from_local_datetime(&local_start) {
+            chrono::MappedLocalTime::Single(a) => a.fix() == current_offset,
+            chrono::MappedLocalTime::Ambiguous(a, b) => {
+                a.fix() == current_offset || b.fix() == current_offset
+            }
+            chrono::MappedLocalTime::None => true,
+        };
+        if !valid {
+            return Err(invalid());
+        }
+        Ok(Zoned {
+            local_resolution,
+            current_offset,
+            zone,
+        })
+    }
+}
+
 impl<R, Z> Monotonic for Zoned<R, Z>
 where
-    Z: TimeZone + Copy + fmt::Debug,
+    Z: FixedTimeZone,
+    R: TimeResolution,
+{
+    fn to_monotonic(&self) -> i64 {
+        self.local_resolution.to_monotonic()
+    }
+    fn between(&self, other: Self) -> i64 {
+        other.to_monotonic() - self.to_monotonic()
+    }
+}
+
+impl<R> Monotonic for Zoned<R, FixedOffset>
+where
     R: TimeResolution,
 {
     fn to_monotonic(&self) -> i64 {
@@ -199,6 +673,51 @@ where
     }
 }
 
+#[cfg(feature = "tz")]
+impl<R> Monotonic for Zoned<R, chrono_tz::Tz>
+where
+    R: TimeResolution,
+{
+    fn to_monotonic(&self) -> i64 {
+        // count elapsed rather than local periods, so that the local periods which are
+        // repeated when the offset decreases have distinct values
+        self.local_resolution.to_monotonic() - offset_in_periods::<R>(self.current_offset)
+    }
+    fn between(&self, other: Self) -> i64 {
+        other.to_monotonic() - self.to_monotonic()
+    }
+}
+
+impl<R, Z> FromMonotonic for Zoned<R, Z>
+where
+    R: TimeResolution + FromMonotonic,
+    Z: FixedTimeZone,
+{
+    fn from_monotonic(idx: i64) -> Self {
+        let zone = Z::new();
+        // the offset of a `FixedTimeZone` never changes, so can be found at any time
+        let current_offset = zone
+            .offset_from_utc_datetime(&chrono::NaiveDateTime::default())
+            .fix();
+        Zoned {
+            local_resolution: R::from_monotonic(idx),
+            current_offset,
+            zone,
+        }
+    }
+}
+
+// the offset as a number of sub-daily periods, or zero for periods made up of local days
+#[cfg(feature = "tz")]
+fn offset_in_periods<R: TimeResolution>(offset: FixedOffset) -> i64 {
+    match R::fixed_duration() {
+        Some(period) if period < TimeDelta::days(1) => {
+            (i64::from(offset.local_minus_utc()) * 1000).div_euclid(period.num_milliseconds())
+        }
+        _ => 0,
+    }
+}
+
 impl<R1, R2, Z> LongerThan<Zoned<R2, Z>> for Zoned<R1, Z>
 where
     R1: TimeResolution,
@@ -223,6 +742,11 @@ where
     Z: FixedTimeZone,
 {
     type Params = Z;
+    // the offset of a `FixedTimeZone` never changes, so every local day is the same length
+    const PERIODS_PER_DAY: u32 = R::PERIODS_PER_DAY;
+    fn exact_duration() -> chrono::Duration {
+        R::exact_duration()
+    }
     fn params(&self) -> Self::Params {
         self.zone()
     }
@@ -264,6 +788,10 @@ where
     fn from_date(date: NaiveDate, params: Self::Params) -> Self {
         Zoned::from_date(date, params)
     }
+
+    fn from_utc_datetime(datetime: DateTime<Utc>, params: Self::Params) -> Self {
+        Zoned::from_date(datetime.with_timezone(&params).date_naive(), params)
+    }
 }
 
 impl<R, Z> Zoned<R, Z>
@@ -277,6 +805,26 @@ where
     pub fn end(&self) -> NaiveDate {
         self.local_resolution.end()
     }
+    /// The elapsed time from the start of the first date to the end of the last date of the
+    /// period in the zone. This accounts for offset changes within the period, so for example a
+    /// day on which daylight saving starts is only 23 hours long.
+    pub fn actual_duration(&self) -> chrono::Duration {
+        self.utc_end_exclusive() - self.utc_start()
+    }
+    /// The UTC time at the start of the first date of the period, using the offset in the zone
+    /// at that time.
+    pub fn utc_start(&self) -> DateTime<Utc> {
+        local_start_of_date(self.start(), self.zone).to_utc()
+    }
+    /// The UTC time at the end of the last date of the period, using the offset in the zone at
+    /// that time, which may be different to the offset at the start of the period.
+    pub fn utc_end_exclusive(&self) -> DateTime<Utc> {
+        local_start_of_date(self.end() + chrono::Days::new(1), self.zone).to_utc()
+    }
+    /// The period containing the current date in `zone` according to `clock`.
+    pub fn current_in<C: crate::Clock>(clock: C, zone: Z) -> Self {
+        Zoned::from_date(clock.now().with_timezone(&zone).date_naive(), zone)
+    }
     pub fn from_date(date: NaiveDate, zone: Z) -> Self {
         Zoned {
             local_resolution: R::from_date(date, ()),
@@ -288,6 +836,15 @@ where
 }
 
 fn local_offset_at_start_of_date<Z>(date: NaiveDate, tz: Z) -> FixedOffset
+where
+    Z: TimeZone + Copy,
+{
+    local_start_of_date(date, tz).offset().fix()
+}
+
+// the first valid local time on the date, which is midnight unless there is an offset
+// transition at midnight
+fn local_start_of_date<Z>(date: NaiveDate, tz: Z) -> DateTime<Z>
 where
     Z: TimeZone + Copy,
 {
@@ -295,8 +852,7 @@ where
         .filter_map(|minutes_offset| {
             let local_start =
                 date.and_time(NaiveTime::MIN) + TimeDelta::try_minutes(minutes_offset)?;
-            let with_tz = local_start.and_local_timezone(tz).single()?;
-            Some(with_tz.offset().fix())
+            local_start.and_local_timezone(tz).single()
         })
         .next()
         // possible to panic, but _extremely_ unlikely
@@ -388,10 +944,11 @@ where
 {
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "alloc"))]
 mod tests {
     use crate::DateResolution;
     use crate::Day;
+    use crate::FixedEast;
     use crate::FixedTimeZone;
     use crate::Minutes;
     use crate::Zoned;
@@ -479,45 +1036,6 @@ mod tests {
             }
         }
 
-        #[derive(Debug, Clone, Copy)]
-        struct FixedEast<const N: i32>;
-
-        impl<const N: i32> chrono::TimeZone for FixedEast<N> {
-            type Offset = FixedOffset;
-
-            fn from_offset(_: &Self::Offset) -> Self {
-                Self
-            }
-
-            fn offset_from_local_date(
-                &self,
-                _: &chrono::prelude::NaiveDate,
-            ) -> chrono::MappedLocalTime<Self::Offset> {
-                unimplemented!()
-            }
-
-            fn offset_from_local_datetime(
-                &self,
-                _: &chrono::prelude::NaiveDateTime,
-            ) -> chrono::MappedLocalTime<Self::Offset> {
-                chrono::MappedLocalTime::Single(chrono::FixedOffset::east_opt(N).unwrap())
-            }
-
-            fn offset_from_utc_date(&self, _: &chrono::prelude::NaiveDate) -> Self::Offset {
-                unimplemented!()
-            }
-
-            fn offset_from_utc_datetime(&self, _: &chrono::prelude::NaiveDateTime) -> Self::Offset {
-                chrono::FixedOffset::east_opt(N).unwrap()
-            }
-        }
-
-        impl<const N: i32> FixedTimeZone for FixedEast<N> {
-            fn new() -> Self {
-                FixedEast
-            }
-        }
-
         fn test_for_zone<F: FixedTimeZone>() {
             subdate_fixed::<1, _>(F::new());
             subdate_fixed::<2, _>(F::new());
@@ -569,4 +1087,294 @@ mod tests {
             date::<Day>(tz);
         }
     }
+
+    #[cfg(feature = "tz")]
+    #[test]
+    fn test_in_tz() {
+        let utc = chrono::NaiveDate::from_ymd_opt(2022, 1, 1)
+            .unwrap()
+            .and_hms_opt(13, 5, 0)
+            .unwrap()
+            .and_utc();
+
+        let zoned =
+            Zoned::<Minutes<5>, crate::Tz>::from_utc_in_tz(utc, "Australia/Sydney").unwrap();
+        assert_eq!(zoned.zone(), chrono_tz::Australia::Sydney);
+        assert_eq!(zoned.utc_start_datetime(), utc);
+        assert_eq!(
+            zoned.local_start_datetime().naive_local(),
+            chrono::NaiveDate::from_ymd_opt(2022, 1, 2)
+                .unwrap()
+                .and_hms_opt(0, 5, 0)
+                .unwrap()
+        );
+
+        assert!(Zoned::<Minutes<5>, crate::Tz>::from_utc_in_tz(utc, "Mars/Olympus_Mons").is_err());
+
+        let date = chrono::NaiveDate::from_ymd_opt(2022, 3, 1).unwrap();
+        let zoned_day = Zoned::<Day, crate::Tz>::from_date_in_tz(date, "Asia/Kathmandu").unwrap();
+        assert_eq!(zoned_day.zone(), chrono_tz::Asia::Kathmandu);
+        assert_eq!(zoned_day.start(), date);
+
+        #[cfg(feature = "serde")]
+        {
+            let json = serde_json::to_string(&zoned).unwrap();
+            assert_eq!(
+                json,
+                r#"{"start":"2022-01-02T00:05:00+11:00","zone":"Australia/Sydney"}"#
+            );
+            assert_eq!(
+                serde_json::from_str::<Zoned<Minutes<5>, crate::Tz>>(&json).unwrap(),
+                zoned
+            );
+        }
+    }
+
+    #[cfg(feature = "tz")]
+    #[test]
+    fn test_tz_dst() {
+        use crate::{HalfHour, TimeRange, TimeResolution};
+        use chrono::Offset;
+
+        let sydney = chrono_tz::Australia::Sydney;
+        // daylight saving ends on 2022-04-03 and starts on 2022-10-02
+        for (date, expected) in [((2022, 4, 3), 50), ((2022, 10, 2), 46), ((2022, 10, 3), 48)] {
+            let date = chrono::NaiveDate::from_ymd_opt(date.0, date.1, date.2).unwrap();
+            let first = Zoned::<HalfHour, crate::Tz>::from(
+                date.and_time(chrono::NaiveTime::MIN)
+                    .and_local_timezone(sydney)
+                    .unwrap(),
+            );
+
+            let periods = core::iter::successors(Some(first), |p| Some(p.succ()))
+                .take_while(|p| p.local_start_datetime().date_naive() == date)
+                .collect::<Vec<_>>();
+            assert_eq!(periods.len() as u64, expected);
+            for pair in periods.windows(2) {
+                assert_eq!(
+                    pair[1].utc_start_datetime() - pair[0].utc_start_datetime(),
+                    chrono::Duration::minutes(30)
+                );
+                assert_eq!(pair[1].pred(), pair[0]);
+            }
+
+            let last = *periods.last().unwrap();
+            assert_eq!(first.succ_n(expected - 1), last);
+            assert_eq!(last.pred_n(expected - 1), first);
+            assert_eq!(
+                TimeRange::from_unordered_bounds(first, last).len().get(),
+                expected
+            );
+        }
+
+        let day = Zoned::<Day, crate::Tz>::from_date(
+            chrono::NaiveDate::from_ymd_opt(2022, 4, 2).unwrap(),
+            sydney,
+        );
+        let next = day.succ();
+        assert_eq!(
+            next.start(),
+            chrono::NaiveDate::from_ymd_opt(2022, 4, 3).unwrap()
+        );
+        assert_eq!(
+            next.local_start_datetime().offset().fix().local_minus_utc(),
+            11 * 3600
+        );
+        assert_eq!(
+            next.succ()
+                .local_start_datetime()
+                .offset()
+                .fix()
+                .local_minus_utc(),
+            10 * 3600
+        );
+        assert_eq!(next.succ().pred(), next);
+    }
+
+    #[cfg(feature = "tz")]
+    #[test]
+    fn test_tz_checked_steps() {
+        use crate::{Hour, TimeResolution};
+
+        let start = chrono::NaiveDate::from_ymd_opt(2022, 4, 2)
+            .unwrap()
+            .and_hms_opt(10, 0, 0)
+            .unwrap()
+            .and_utc();
+        let hour = Zoned::<Hour, crate::Tz>::from_utc_in_tz(start, "Australia/Sydney").unwrap();
+        assert_eq!(hour.checked_succ_n(24), Some(hour.succ_n(24)));
+        assert_eq!(hour.checked_pred_n(24), Some(hour.pred_n(24)));
+        assert_eq!(hour.checked_succ_n(1_000_000_000_000), None);
+        assert_eq!(hour.checked_pred_n(1_000_000_000_000), None);
+        assert_eq!(hour.checked_succ_n(u64::MAX), None);
+
+        let day = Zoned::<Day, crate::Tz>::from_date_in_tz(start.date_naive(), "Australia/Sydney")
+            .unwrap();
+        assert_eq!(day.checked_succ_n(1), Some(day.succ()));
+        assert_eq!(day.checked_pred_n(1), Some(day.pred()));
+        assert_eq!(day.checked_succ_n(u64::MAX), None);
+        assert_eq!(day.checked_pred_n(u64::MAX), None);
+    }
+
+    #[test]
+    fn test_to_zone() {
+        let sydney = chrono::NaiveDate::from_ymd_opt(2022, 1, 1)
+            .unwrap()
+            .and_hms_opt(10, 30, 0)
+            .unwrap()
+            .and_local_timezone(chrono_tz::Australia::Sydney)
+            .unwrap();
+        let zoned = Zoned::<Minutes<30>, _>::from(sydney);
+
+        let adelaide = zoned.to_zone(chrono_tz::Australia::Adelaide);
+        assert_eq!(adelaide.utc_start_datetime(), zoned.utc_start_datetime());
+        assert_eq!(
+            adelaide.local_start_datetime().naive_local(),
+            sydney.naive_local() - chrono::Duration::minutes(30)
+        );
+        assert_eq!(adelaide.to_zone(chrono_tz::Australia::Sydney), zoned);
+
+        let utc = zoned.to_zone(chrono::Utc);
+        assert_eq!(utc.local_start_datetime(), sydney.to_utc());
+
+        // the half-hours in Kathmandu start 15 minutes out of step with Sydney
+        let kathmandu = zoned.to_zone(chrono_tz::Asia::Kathmandu);
+        assert_eq!(
+            zoned.utc_start_datetime() - kathmandu.utc_start_datetime(),
+            chrono::Duration::minutes(15)
+        );
+    }
+
+    #[test]
+    fn test_actual_duration() {
+        use crate::{Month, Week};
+
+        let sydney = chrono_tz::Australia::Sydney;
+        let date = |m, d| chrono::NaiveDate::from_ymd_opt(2022, m, d).unwrap();
+
+        for (day, hours) in [(date(4, 3), 25), (date(10, 2), 23), (date(10, 3), 24)] {
+            assert_eq!(
+                Zoned::<Day, _>::from_date(day, sydney).actual_duration(),
+                chrono::Duration::hours(hours)
+            );
+        }
+        assert_eq!(
+            Zoned::<Week<crate::Monday>, _>::from_date(date(9, 26), sydney).actual_duration(),
+            chrono::Duration::hours(7 * 24 - 1)
+        );
+        assert_eq!(
+            Zoned::<Month, _>::from_date(date(4, 1), sydney).actual_duration(),
+            chrono::Duration::hours(30 * 24 + 1)
+        );
+        assert_eq!(
+            Zoned::<Month, _>::from_date(date(4, 1), chrono::Utc).actual_duration(),
+            chrono::Duration::days(30)
+        );
+    }
+
+    #[test]
+    fn test_utc_bounds() {
+        use crate::Month;
+
+        let sydney = chrono_tz::Australia::Sydney;
+        let utc = |m, d, h| {
+            chrono::NaiveDate::from_ymd_opt(2022, m, d)
+                .unwrap()
+                .and_hms_opt(h, 0, 0)
+                .unwrap()
+                .and_utc()
+        };
+
+        // april starts in daylight saving time (+11) and ends in standard time (+10)
+        let april = Zoned::<Month, _>::from_date(
+            chrono::NaiveDate::from_ymd_opt(2022, 4, 1).unwrap(),
+            sydney,
+        );
+        assert_eq!(april.utc_start(), utc(3, 31, 13));
+        assert_eq!(april.utc_end_exclusive(), utc(4, 30, 14));
+
+        let january = Zoned::<Month, _>::from_date(
+            chrono::NaiveDate::from_ymd_opt(2022, 1, 1).unwrap(),
+            chrono::Utc,
+        );
+        assert_eq!(january.utc_start(), utc(1, 1, 0));
+        assert_eq!(january.utc_end_exclusive(), utc(2, 1, 0));
+    }
+
+    #[test]
+    fn test_with_fixed_offset() {
+        use crate::TimeResolution;
+
+        let sydney = chrono::NaiveDate::from_ymd_opt(2022, 4, 3)
+            .unwrap()
+            .and_hms_opt(1, 30, 0)
+            .unwrap()
+            .and_local_timezone(chrono_tz::Australia::Sydney)
+            .unwrap();
+        let zoned = Zoned::<Minutes<30>, FixedOffset>::with_fixed_offset(sydney);
+        assert_eq!(zoned.zone(), FixedOffset::east_opt(11 * 3600).unwrap());
+        assert_eq!(zoned.utc_start_datetime(), sydney.to_utc());
+
+        // the offset is kept even though daylight saving ends at 3am
+        let later = zoned.succ_n(4);
+        assert_eq!(later.zone(), zoned.zone());
+        assert_eq!(
+            later.local_start_datetime().naive_local(),
+            sydney.naive_local() + chrono::Duration::hours(2)
+        );
+        assert_eq!(later.pred_n(4), zoned);
+    }
+
+    #[test]
+    fn test_from_monotonic() {
+        use crate::{FromMonotonic, Monotonic, TimeResolution};
+
+        type Aest = FixedEast<{ 10 * 60 * 60 }>;
+        type Nepal = FixedEast<{ 5 * 60 * 60 + 45 * 60 }>;
+
+        fn roundtrip<R: TimeResolution + FromMonotonic + core::fmt::Debug>(start: R) {
+            for period in core::iter::successors(Some(start), |p| Some(p.succ())).take(100) {
+                assert_eq!(R::from_monotonic(period.to_monotonic()), period);
+            }
+        }
+
+        let local = chrono::NaiveDate::from_ymd_opt(2022, 1, 1)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap();
+        roundtrip(Zoned::<Minutes<30>, Aest>::from(
+            local.and_local_timezone(Aest::new()).unwrap(),
+        ));
+        roundtrip(Zoned::<Minutes<30>, Nepal>::from(
+            local.and_local_timezone(Nepal::new()).unwrap(),
+        ));
+
+        // the index of a fixed zone is that of the local period, as the offset never changes
+        let zoned =
+            Zoned::<Minutes<30>, Aest>::from(local.and_local_timezone(Aest::new()).unwrap());
+        assert_eq!(
+            zoned.to_monotonic(),
+            Minutes::<30>::from(local.and_utc()).to_monotonic()
+        );
+        roundtrip(Zoned::<Day, Aest>::from_date(local.date(), Aest::new()));
+        roundtrip(Zoned::<crate::Month, chrono::Utc>::from_date(
+            local.date(),
+            chrono::Utc,
+        ));
+
+        let formatted = crate::format_erased_resolution(
+            |tid, val| {
+                assert_eq!(tid, core::any::TypeId::of::<Zoned<Minutes<30>, Aest>>());
+                alloc::format!(
+                    "Zoned:{}",
+                    Zoned::<Minutes<30>, Aest>::from_monotonic(val).local_start_datetime()
+                )
+            },
+            core::any::TypeId::of::<Zoned<Minutes<30>, Aest>>(),
+            Zoned::<Minutes<30>, Aest>::from(local.and_local_timezone(Aest::new()).unwrap())
+                .to_monotonic(),
+        );
+        assert_eq!(formatted, "Zoned:2022-01-01 00:00:00 +10:00");
+        assert_eq!(alloc::format!("{:?}", Aest::new()), "FixedEast[+10:00]");
+    }
 }